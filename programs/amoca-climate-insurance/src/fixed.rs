@@ -0,0 +1,292 @@
+//! Deterministic fixed-point arithmetic
+//!
+//! Account fields stay `f64` on the wire, but trigger comparisons, severity
+//! and distance math run on integers scaled by `FIXED_POINT_ONE`, so every
+//! validator reaches bit-for-bit identical results. `Fixed::from_f64` decodes
+//! the IEEE 754 bit pattern directly rather than relying on float operations.
+
+use std::ops::{Add, Sub};
+
+/// Scale of fixed-point values used in payout and distance math (12 decimal places)
+pub const FIXED_POINT_ONE: u128 = 1_000_000_000_000;
+
+/// Signed counterpart of `FIXED_POINT_ONE` for trigonometry
+const FIXED_ONE: i128 = FIXED_POINT_ONE as i128;
+
+/// Pi scaled by `FIXED_POINT_ONE`
+const PI_FIXED: i128 = 3_141_592_653_590;
+
+/// Mean Earth radius used for great-circle distances
+const EARTH_RADIUS_KM: i128 = 6_371;
+
+/// Signed fixed-point number with 12 decimal places
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(i128);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(FIXED_ONE);
+
+    /// Exact conversion from the bits of an `f64`, truncating past 12 decimals
+    ///
+    /// NaN maps to zero and out-of-range values, including infinities, saturate.
+    pub fn from_f64(value: f64) -> Fixed {
+        let bits = value.to_bits();
+        let negative = bits >> 63 == 1;
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & ((1 << 52) - 1)) as i128;
+
+        if biased_exponent == 0x7ff {
+            return match (fraction, negative) {
+                (0, false) => Fixed(i128::MAX),
+                (0, true) => Fixed(i128::MIN),
+                _ => Fixed::ZERO,
+            };
+        }
+
+        // value = mantissa * 2^shift
+        let (mantissa, shift) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased_exponent - 1075)
+        };
+
+        // mantissa * FIXED_ONE < 2^93, so any left shift below 34 fits in an i128
+        let scaled = mantissa * FIXED_ONE;
+        let magnitude = match shift {
+            s if s >= 34 => i128::MAX,
+            s if s >= 0 => scaled << s,
+            s if s > -127 => scaled >> -s,
+            _ => 0,
+        };
+        Fixed(if negative { -magnitude } else { magnitude })
+    }
+
+    pub fn abs(self) -> Fixed {
+        Fixed(self.0.saturating_abs())
+    }
+
+    /// `self * bps / 10_000`, truncated toward zero
+    pub fn scale_bps(self, bps: u16) -> Fixed {
+        Fixed(self.0.saturating_mul(bps as i128) / 10_000)
+    }
+
+    /// `self / denominator` scaled to `scale`, e.g. 10_000 for basis points
+    ///
+    /// Returns zero for a non-positive denominator.
+    pub fn ratio(self, denominator: Fixed, scale: i128) -> i128 {
+        if denominator.0 <= 0 {
+            return 0;
+        }
+        self.0.saturating_mul(scale) / denominator.0
+    }
+
+    /// Weighted mean of `(value, weight)` pairs, `None` when the weights sum to zero
+    pub fn weighted_mean(values: impl Iterator<Item = (Fixed, u16)>) -> Option<Fixed> {
+        let (sum, total_weight) = values.fold((0i128, 0i128), |(sum, total), (value, weight)| {
+            (sum.saturating_add(value.0.saturating_mul(weight as i128)), total + weight as i128)
+        });
+        (total_weight > 0).then(|| Fixed(sum / total_weight))
+    }
+
+    /// Median of `values`, averaging the middle pair of an even count; `None` when empty
+    ///
+    /// Sorts `values` in place.
+    pub fn median(values: &mut [Fixed]) -> Option<Fixed> {
+        values.sort_unstable();
+        let mid = values.len() / 2;
+        match values.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(values[mid]),
+            _ => Some(Fixed(values[mid - 1].0 / 2 + values[mid].0 / 2)),
+        }
+    }
+
+    /// Whether each value lies within `limit` standard deviations of the mean
+    ///
+    /// Compares squared deviations so no square root is needed.
+    pub fn within_deviations(values: &[Fixed], limit: i128) -> Vec<bool> {
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let count = values.len() as i128;
+        let mean = values.iter().fold(0i128, |sum, v| sum.saturating_add(v.0)) / count;
+        let squared: Vec<i128> = values
+            .iter()
+            .map(|v| {
+                let deviation = v.0.saturating_sub(mean);
+                deviation.saturating_mul(deviation)
+            })
+            .collect();
+        let sum_squared = squared.iter().fold(0i128, |sum, &sq| sum.saturating_add(sq));
+
+        // |deviation| <= limit * sigma  <=>  deviation^2 * count <= limit^2 * sum of squares
+        let bound = sum_squared.saturating_mul(limit.saturating_mul(limit));
+        squared.iter().map(|&sq| sq.saturating_mul(count) <= bound).collect()
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(rhs.0))
+    }
+}
+
+/// Great-circle distance in kilometres via the haversine formula
+///
+/// All trigonometry runs on integers so every validator computes the same
+/// distance.
+pub fn haversine_distance(lat1: Fixed, lon1: Fixed, lat2: Fixed, lon2: Fixed) -> Fixed {
+    let (lat1, lat2) = (lat1.0, lat2.0);
+    let delta_lat = lat2 - lat1;
+    let mut delta_lon = lon2.0 - lon1.0;
+    if delta_lon > 180 * FIXED_ONE {
+        delta_lon -= 360 * FIXED_ONE;
+    } else if delta_lon < -180 * FIXED_ONE {
+        delta_lon += 360 * FIXED_ONE;
+    }
+
+    let to_radians = |degrees: i128| degrees * PI_FIXED / (180 * FIXED_ONE);
+    let sin_half_lat = sin_fixed(to_radians(delta_lat) / 2);
+    let sin_half_lon = sin_fixed(to_radians(delta_lon) / 2);
+    let cos_product = cos_fixed(to_radians(lat1)) * cos_fixed(to_radians(lat2)) / FIXED_ONE;
+
+    // a = sin²(Δφ/2) + cos φ1 · cos φ2 · sin²(Δλ/2)
+    let a = (sin_half_lat * sin_half_lat / FIXED_ONE
+        + cos_product * (sin_half_lon * sin_half_lon / FIXED_ONE) / FIXED_ONE)
+        .clamp(0, FIXED_ONE);
+    let central_angle = 2 * atan2_fixed(sqrt_fixed(a), sqrt_fixed(FIXED_ONE - a));
+
+    Fixed(EARTH_RADIUS_KM * central_angle)
+}
+
+/// Sine via Taylor series, accurate for |x| <= pi/2
+fn sin_fixed(x: i128) -> i128 {
+    let x_squared = x * x / FIXED_ONE;
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..12 {
+        term = -term * x_squared / FIXED_ONE / ((2 * n) * (2 * n + 1));
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Cosine via Taylor series, accurate for |x| <= pi/2
+fn cos_fixed(x: i128) -> i128 {
+    let x_squared = x * x / FIXED_ONE;
+    let mut term = FIXED_ONE;
+    let mut sum = FIXED_ONE;
+    for n in 1..12 {
+        term = -term * x_squared / FIXED_ONE / ((2 * n - 1) * (2 * n));
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Arctangent of a non-negative value
+///
+/// Arguments above 1 are reflected around pi/2, then halved twice with
+/// atan(z) = 2·atan(z / (1 + sqrt(1 + z²))) so the Taylor series converges fast.
+fn atan_fixed(z: i128) -> i128 {
+    if z > FIXED_ONE {
+        return PI_FIXED / 2 - atan_fixed(FIXED_ONE * FIXED_ONE / z);
+    }
+
+    let mut reduced = z;
+    for _ in 0..2 {
+        reduced = reduced * FIXED_ONE
+            / (FIXED_ONE + sqrt_fixed(FIXED_ONE + reduced * reduced / FIXED_ONE));
+    }
+
+    let reduced_squared = reduced * reduced / FIXED_ONE;
+    let mut power = reduced;
+    let mut sum = reduced;
+    for n in 1..12 {
+        power = -power * reduced_squared / FIXED_ONE;
+        if power == 0 {
+            break;
+        }
+        sum += power / (2 * n + 1);
+    }
+    sum * 4
+}
+
+/// Two-argument arctangent for a non-negative `y` and `x`
+fn atan2_fixed(y: i128, x: i128) -> i128 {
+    if x == 0 {
+        return PI_FIXED / 2;
+    }
+    atan_fixed(y * FIXED_ONE / x)
+}
+
+/// Square root of a non-negative fixed-point value
+fn sqrt_fixed(x: i128) -> i128 {
+    integer_sqrt((x.max(0) as u128) * FIXED_POINT_ONE) as i128
+}
+
+/// Integer square root by Newton's method
+fn integer_sqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+/// Compute e^(-x) for a fixed-point `x`, returning a fixed-point result
+///
+/// The integer part is applied as repeated multiplication by 1/e and the
+/// fractional part through its Taylor series, keeping the result
+/// deterministic across validators.
+pub fn exp_neg_fixed(x: u128) -> u128 {
+    const INV_E: u128 = 367_879_441_171; // 1/e scaled by FIXED_POINT_ONE
+    const TAYLOR_TERMS: u128 = 16;
+
+    let whole = x / FIXED_POINT_ONE;
+    if whole >= 32 {
+        return 0; // Below fixed-point precision
+    }
+    let frac = x % FIXED_POINT_ONE;
+
+    // e^(-frac) = sum of (-frac)^n / n!
+    let mut term = FIXED_POINT_ONE;
+    let mut sum = FIXED_POINT_ONE as i128;
+    for n in 1..=TAYLOR_TERMS {
+        term = term * frac / (FIXED_POINT_ONE * n);
+        if term == 0 {
+            break;
+        }
+        if n % 2 == 1 {
+            sum -= term as i128;
+        } else {
+            sum += term as i128;
+        }
+    }
+
+    let mut result = sum.max(0) as u128;
+    for _ in 0..whole {
+        result = result * INV_E / FIXED_POINT_ONE;
+    }
+    result
+}
@@ -1,11 +1,169 @@
+#![allow(deprecated)] // anchor-lang 0.31 `#[program]` still expands to AccountInfo::realloc
+
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{self, Token, TokenAccount, Transfer}
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{
+    self, spl_token::instruction::AuthorityType, CloseAccount, Mint, MintTo, SetAuthority, Token,
+    TokenAccount, Transfer,
 };
 
+mod fixed;
+
+use fixed::{exp_neg_fixed, haversine_distance, Fixed, FIXED_POINT_ONE};
+
 declare_id!("8a2BSK86azg8kL6Cbd2wvEswnn2eKyS3CSZSgXpfTzTc");
 
+/// `require!` that first logs which account failed and the values involved,
+/// so rejections can be diagnosed from transaction logs
+macro_rules! require_logged {
+    ($invariant:expr, $error:expr, $($context:tt)+) => {
+        if !($invariant) {
+            msg!($($context)+);
+            return Err(error!($error));
+        }
+    };
+}
+
+/// Number of readings kept per climate data type in a policy's readings buffer
+pub const MAX_READINGS_PER_TYPE: usize = 16;
+
+/// Readings further than this many standard deviations from their window's mean
+/// are discarded as outliers before aggregation
+pub const OUTLIER_STDDEV_LIMIT: i128 = 2;
+
+/// Number of payouts kept in a policy's claim log
+pub const MAX_CLAIM_RECORDS: usize = 16;
+
+/// Minimum confidence level for a reading to be accepted, and the default a policy counts
+pub const MIN_CONFIDENCE_LEVEL: u8 = 50;
+
+/// Distinct oracles that must agree on a breach when a policy doesn't specify
+pub const DEFAULT_MIN_ORACLE_CONSENSUS: u8 = 2;
+
+/// Most oracle sources a policy may list
+pub const MAX_ORACLE_SOURCES: usize = 5;
+
+/// Most installments a policy's premium may be split into
+pub const MAX_PREMIUM_INSTALLMENTS: usize = 12;
+
+/// Number of `ClimateDataType` variants, the most data types an oracle may support
+pub const CLIMATE_DATA_TYPE_COUNT: usize = 8;
+
+/// Largest coverage radius a policy may declare
+pub const MAX_COVERAGE_RADIUS_KM: f64 = 5_000.0;
+
+/// Default longest coverage period a policy may be created or renewed for (1 year)
+pub const DEFAULT_MAX_POLICY_DURATION_SECONDS: i64 = 365 * 86_400;
+
+/// Evaluation interval a policy gets when it doesn't specify one (1 hour)
+pub const DEFAULT_MONITORING_FREQUENCY_SECONDS: u32 = 3600;
+
+/// Bounds on a policy's evaluation interval, from 10 minutes to 7 days
+pub const MIN_MONITORING_FREQUENCY_SECONDS: u32 = 600;
+pub const MAX_MONITORING_FREQUENCY_SECONDS: u32 = 7 * 86_400;
+
+/// Length of a leap year, bounding `SeasonWindow` offsets
+pub const SECONDS_PER_LEAP_YEAR: i64 = 366 * 86_400;
+
+/// Length of each `EpochStats` analytics bucket
+pub const STATS_BUCKET_SECONDS: i64 = 86_400;
+
+/// Time after creation to pay the premium before an unpaid policy can be closed
+pub const PREMIUM_GRACE_PERIOD_SECONDS: i64 = 7 * 86_400;
+
+/// Time after expiry reserved for the holder to claim a no-data refund before
+/// `expire_policy` may lapse the policy
+pub const NO_DATA_REFUND_WINDOW_SECONDS: i64 = 7 * 86_400;
+
+/// Fixed-point scale of `FxRate.rate`: premium mint base units per reference unit, in millionths
+pub const FX_RATE_SCALE: u128 = 1_000_000;
+
+/// Oldest an FX rate may be when a denominated policy is created or pays out
+pub const FX_RATE_MAX_AGE_SECONDS: i64 = 3600;
+
+/// Margin over the spot FX rate, in bps, at which denominated coverage is
+/// committed against the pool, absorbing currency moves over the term
+pub const FX_COMMITMENT_MARGIN_BPS: u16 = 2_000;
+
+/// Number of `ClimateRiskType` variants, sizing `GlobalState.coverage_limits`
+pub const CLIMATE_RISK_TYPE_COUNT: usize = 7;
+
+/// Default time after a policy starts before it can pay out, indexed by
+/// `ClimateRiskType`; slow-onset perils wait longer than forecastable storms
+pub const DEFAULT_WAITING_PERIODS_SECONDS: [i64; CLIMATE_RISK_TYPE_COUNT] = [
+    30 * 86_400, // DroughtProtection
+    7 * 86_400,  // FloodInsurance
+    7 * 86_400,  // HurricaneCoverage
+    30 * 86_400, // AgriculturalClimate
+    3 * 86_400,  // WildfireProtection
+    30 * 86_400, // SeaLevelRise
+    3 * 86_400,  // ExtremeTemperature
+];
+
+/// Geohash characters keying a `RegionExposure` cell (about 156 km square)
+pub const REGION_GEOHASH_PRECISION: usize = 3;
+
+/// Base32 alphabet of geohash cells
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Policies an owner's `PolicyRegistry` can index at once
+pub const MAX_POLICIES_PER_OWNER: usize = 32;
+
+/// Reputation lost for each monitoring interval an oracle misses
+pub const REPUTATION_DECAY_PER_INTERVAL: u16 = 5;
+
+/// Oracles whose reputation decays below this are deactivated
+pub const MIN_ORACLE_REPUTATION: u16 = 20;
+
+/// Time after an oracle's last submission before it can unregister and reclaim its bond,
+/// leaving a window to dispute its final data
+pub const ORACLE_UNBOND_COOLDOWN_SECONDS: i64 = 7 * 86_400;
+
+/// Upper bound on `min_oracle_bond`: one million tokens of a 6-decimal premium mint
+pub const MAX_MIN_ORACLE_BOND: u64 = 1_000_000_000_000;
+
+/// Upper bound on `keeper_reward`: 100 tokens of a 6-decimal premium mint, so
+/// a misconfigured tip cannot drain the fee vault in a few cranks
+pub const MAX_KEEPER_REWARD: u64 = 100_000_000;
+
+/// Upper bound on `max_loss_ratio_bps`: a day paying out 100x its premiums
+pub const MAX_LOSS_RATIO_BPS: u32 = 1_000_000;
+
+/// Schema generation of `GlobalState` written by this program
+pub const GLOBAL_STATE_VERSION: u8 = 5;
+
+/// Schema generation of `ClimatePolicy` written by this program
+pub const CLIMATE_POLICY_VERSION: u8 = 10;
+
+/// Default reputation below which an oracle's readings are ignored by trigger evaluation
+pub const DEFAULT_REPUTATION_FLOOR: u16 = 50;
+
+/// Default weight a submission batch's average confidence carries in an oracle's
+/// reputation moving average (20%)
+pub const DEFAULT_REPUTATION_SMOOTHING_BPS: u16 = 2_000;
+
+/// Default time a triggered policy stays Triggered awaiting its payout before
+/// the trigger lapses and the policy returns to monitoring
+pub const DEFAULT_TRIGGER_GRACE_PERIOD_SECONDS: i64 = 3 * 86_400;
+
+/// Default pool capital required per unit of committed coverage (1%, i.e. 100x leverage)
+pub const DEFAULT_SOLVENCY_RATIO_BPS: u16 = 100;
+
+/// Basis point denominator
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Breach severity in basis points; 10_000 means a reading 100% past its threshold
+pub const MAX_SEVERITY_BPS: u16 = 10_000;
+
+/// Scale of `decay_constant` (thousandths), so 1_000 is k = 1.0
+const DECAY_CONSTANT_SCALE: u128 = 1_000;
+
 /// AMOCA Climate Insurance Program
 /// Provides parametric climate insurance with automated triggers
 /// based on verifiable environmental data from oracles
@@ -17,19 +175,58 @@ pub mod amoca_climate_insurance {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let global_state = &mut ctx.accounts.global_state;
         global_state.bump = ctx.bumps.global_state;
+        global_state.risk_pool_bump = Pubkey::find_program_address(&[b"risk_pool"], ctx.program_id).1;
         global_state.total_policies = 0;
         global_state.total_premiums_collected = 0;
         global_state.total_payouts = 0;
+        global_state.total_coverage_committed = 0;
+        global_state.solvency_ratio_bps = DEFAULT_SOLVENCY_RATIO_BPS;
+        global_state.reputation_floor = DEFAULT_REPUTATION_FLOOR;
+        global_state.protocol_fee_bps = 0;
+        global_state.total_fees_collected = 0;
+        global_state.fee_vault_bump = 0;
+        global_state.premium_mint = Pubkey::default();
+        global_state.sol_coverage_committed = 0;
+        global_state.sol_premiums_collected = 0;
+        global_state.sol_payouts = 0;
+        global_state.risk_pool_vault = Pubkey::default();
+        global_state.max_policy_duration_seconds = DEFAULT_MAX_POLICY_DURATION_SECONDS;
+        global_state.total_reinsurance = 0;
+        global_state.triggered_claims = 0;
+        global_state.sol_triggered_claims = 0;
+        global_state.min_oracle_bond = 0;
+        global_state.coverage_limits = [u64::MAX; CLIMATE_RISK_TYPE_COUNT];
+        global_state.waiting_periods = DEFAULT_WAITING_PERIODS_SECONDS;
+        global_state.authority_program = None;
+        global_state.max_region_exposure = u64::MAX;
+        global_state.policy_creation_interval_seconds = 0;
+        global_state.no_data_refund_bps = BPS_DENOMINATOR as u16;
+        global_state.reputation_smoothing_bps = DEFAULT_REPUTATION_SMOOTHING_BPS;
+        global_state.min_premium_rate_bps = 0;
+        global_state.trigger_grace_period_seconds = DEFAULT_TRIGGER_GRACE_PERIOD_SECONDS;
         global_state.is_paused = false;
+        global_state.version = GLOBAL_STATE_VERSION;
+        global_state.max_stacked_coverage = u64::MAX;
+        global_state.keeper_reward = 0;
+        global_state.max_loss_ratio_bps = 0;
+        global_state.payouts_halted = false;
+        global_state.global_max_single_policy_payout = u64::MAX;
         global_state.authority = ctx.accounts.authority.key();
+        global_state.pending_authority = None;
         
         msg!("AMOCA Climate Insurance Program initialized");
         Ok(())
     }
 
     /// Create a new parametric climate insurance policy
-    pub fn create_climate_policy(
-        ctx: Context<CreateClimatePolicy>,
+    ///
+    /// `remaining_accounts` holds the `OracleData` of each of `oracle_sources`,
+    /// in the same order, so the sources can be checked against the data types
+    /// the policy's risk type is judged on. Once `max_stacked_coverage` is
+    /// configured they are followed by each policy in the owner's registry, in
+    /// registry order, whose overlapping coverage of the same peril is capped.
+    pub fn create_climate_policy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateClimatePolicy<'info>>,
         params: PolicyParams,
     ) -> Result<()> {
         let clock = Clock::get()?;
@@ -37,41 +234,239 @@ pub mod amoca_climate_insurance {
 
         // Validate policy parameters
         require!(params.coverage_amount > 0, AmocaError::InvalidCoverageAmount);
+
+        // Denominated coverage is converted into the premium mint at payout
+        require!(
+            params.coverage_denomination.is_some() == params.fx_oracle.is_some()
+                && (params.coverage_denomination.is_none() || params.payment_mode == PaymentMode::SplToken),
+            AmocaError::InvalidCoverageDenomination
+        );
+        // Pool limits, counters and premiums are all in the premium mint, so
+        // denominated coverage is held there at a rate above today's
+        let commitment_fx_rate = match params.coverage_denomination {
+            None => FX_RATE_SCALE as u64,
+            Some(denomination) => designated_fx_rate(
+                ctx.accounts.fx_rate.as_deref(),
+                ctx.accounts.policy.key(),
+                params.fx_oracle,
+                denomination,
+                current_time,
+            )?
+            .commitment_rate()?,
+        };
+        let committed_coverage = convert_rounding_up(params.coverage_amount, commitment_fx_rate)?;
+        require!(
+            committed_coverage <= ctx.accounts.global_state.coverage_limit(params.policy_type),
+            AmocaError::CoverageExceedsTypeLimit
+        );
         require!(params.end_timestamp > current_time, AmocaError::InvalidPolicyDuration);
+        require!(
+            params.end_timestamp - current_time <= ctx.accounts.global_state.max_policy_duration_seconds,
+            AmocaError::PolicyDurationTooLong
+        );
         require!(params.premium_amount > 0, AmocaError::InvalidPremiumAmount);
-
-        // Validate geographic bounds
         require!(
-            params.geographic_bounds.latitude >= -90.0 && params.geographic_bounds.latitude <= 90.0,
-            AmocaError::InvalidGeographicBounds
+            ctx.accounts.global_state.is_adequate_premium(params.premium_amount, committed_coverage),
+            AmocaError::PremiumRatioTooLow
+        );
+        require!(
+            params.payout_formula != PayoutFormula::Exponential || params.decay_constant > 0,
+            AmocaError::InvalidDecayConstant
+        );
+        let min_oracle_consensus = params.min_oracle_consensus.unwrap_or(DEFAULT_MIN_ORACLE_CONSENSUS);
+        require!(min_oracle_consensus > 0, AmocaError::InvalidOracleConsensus);
+        let sources = &params.oracle_sources;
+        require!(
+            !sources.is_empty()
+                && sources.len() <= MAX_ORACLE_SOURCES
+                && sources.len() >= min_oracle_consensus as usize
+                && sources.iter().enumerate().all(|(i, source)| !sources[..i].contains(source)),
+            AmocaError::InvalidOracleSources
+        );
+        let (oracle_accounts, registry_policy_accounts) = ctx
+            .remaining_accounts
+            .split_at(sources.len().min(ctx.remaining_accounts.len()));
+        verify_oracle_capabilities(
+            params.policy_type,
+            &params.trigger_conditions,
+            sources,
+            min_oracle_consensus,
+            params.require_cross_validation,
+            oracle_accounts,
+        )?;
+        let min_confidence = params.min_confidence.unwrap_or(MIN_CONFIDENCE_LEVEL);
+        require!(
+            (MIN_CONFIDENCE_LEVEL..=100).contains(&min_confidence),
+            AmocaError::InvalidMinConfidence
+        );
+        let monitoring_frequency = params
+            .monitoring_frequency
+            .unwrap_or(DEFAULT_MONITORING_FREQUENCY_SECONDS);
+        require!(
+            is_valid_monitoring_frequency(monitoring_frequency),
+            AmocaError::InvalidMonitoringFrequency
         );
+        let max_single_payout = params.max_single_payout.unwrap_or(params.coverage_amount);
         require!(
-            params.geographic_bounds.longitude >= -180.0 && params.geographic_bounds.longitude <= 180.0,
-            AmocaError::InvalidGeographicBounds
+            max_single_payout > 0 && max_single_payout <= params.coverage_amount,
+            AmocaError::InvalidPayoutCap
         );
+        // A deductible at or above the per-payout cap would swallow every
+        // payout; the cap is itself within coverage
+        require!(
+            params.deductible_amount < max_single_payout,
+            AmocaError::InvalidDeductible
+        );
+        validate_trigger_conditions(params.payout_formula, &params.trigger_conditions)?;
+        validate_premium_schedule(
+            &params.premium_schedule,
+            params.premium_amount,
+            current_time + PREMIUM_GRACE_PERIOD_SECONDS,
+            params.end_timestamp,
+        )?;
+
+        // Validate geographic bounds
+        require!(params.geographic_bounds.is_valid(), AmocaError::InvalidGeographicBounds);
+        if let Some(range) = params.altitude_range {
+            require!(range.is_valid(), AmocaError::InvalidAltitudeRange);
+        }
+
+        // Overlapping policies on the same peril would pay out repeatedly for
+        // one event, so their combined coverage is capped
+        let max_stacked_coverage = ctx.accounts.global_state.max_stacked_coverage;
+        if max_stacked_coverage != u64::MAX {
+            let stacked = stacked_coverage(
+                ctx.accounts.owner.key(),
+                &ctx.accounts.policy_registry.policy_ids,
+                registry_policy_accounts,
+                |policy| {
+                    policy.stacks_with(
+                        params.policy_type,
+                        params.payment_mode,
+                        params.coverage_denomination,
+                        &params.geographic_bounds,
+                    )
+                },
+            )?
+            .checked_add(committed_coverage)
+            .ok_or(AmocaError::MathOverflow)?;
+            require_logged!(
+                stacked <= max_stacked_coverage,
+                AmocaError::StackedCoverageExceeded,
+                "Overlapping {:?} coverage of {} exceeds the limit of {}",
+                params.policy_type,
+                stacked,
+                max_stacked_coverage
+            );
+        }
 
         let policy = &mut ctx.accounts.policy;
         policy.bump = ctx.bumps.policy;
         policy.owner = ctx.accounts.owner.key();
+        policy.creator = ctx.accounts.owner.key();
+        policy.pending_owner = None;
         policy.status = PolicyStatus::Inactive;
         policy.policy_type = params.policy_type;
         policy.geographic_bounds = params.geographic_bounds;
         policy.trigger_thresholds = params.trigger_conditions;
         policy.coverage_amount = params.coverage_amount;
         policy.premium_amount = params.premium_amount;
+        policy.protocol_fee_paid = 0;
+        policy.paid_out_so_far = 0;
         policy.start_timestamp = current_time;
         policy.end_timestamp = params.end_timestamp;
-        policy.last_data_update = current_time;
-        policy.monitoring_frequency = 3600; // 1 hour default
+        policy.waiting_period_seconds = ctx.accounts.global_state.waiting_period(params.policy_type);
+        policy.premium_deadline = current_time + PREMIUM_GRACE_PERIOD_SECONDS;
+        policy.last_data_update = 0; // Not yet evaluated, so the first evaluation is never throttled
+        policy.monitoring_frequency = monitoring_frequency;
         policy.risk_score = 50; // Default medium risk
-        policy.payout_calculation = PayoutFormula::LinearScale;
+        policy.risk_period_start = current_time;
+        policy.payout_calculation = params.payout_formula;
+        policy.decay_constant = params.decay_constant;
+        policy.last_severity = SeverityBreakdown::default();
         policy.oracle_sources = params.oracle_sources;
+        policy.min_oracle_consensus = min_oracle_consensus;
+        policy.min_confidence = min_confidence;
+        policy.altitude_range = params.altitude_range;
+        policy.max_single_payout = max_single_payout;
+        policy.deductible_amount = params.deductible_amount;
+        policy.index_value = 0;
+        policy.beneficiary = params.beneficiary.unwrap_or(ctx.accounts.owner.key());
+        policy.payment_mode = params.payment_mode;
+        policy.coverage_denomination = params.coverage_denomination;
+        policy.fx_oracle = params.fx_oracle;
+        policy.commitment_fx_rate = commitment_fx_rate;
+        policy.transferred_so_far = 0;
+        policy.full_term_premium = params.premium_amount;
+        policy.breach_started_at = None;
+        policy.last_payout_at = None;
+        policy.pending_claim = 0;
+        policy.triggered_at = 0;
+        policy.filed_claim = 0;
+        policy.qualifying_readings = 0;
+        policy.haircut_bps = 0;
+        policy.haircut_shortfall = 0;
+        policy.policy_mint = None;
+        policy.version = CLIMATE_POLICY_VERSION;
+        policy.require_cross_validation = params.require_cross_validation;
+        policy.is_frozen = false;
+        policy.premium_schedule = params.premium_schedule;
+        policy.premium_paid_so_far = 0;
+        policy.trigger_reward_cycle = 0;
+        policy.payout_reward_cycle = 0;
+
+        // Track the coverage written in this policy's region
+        let region_exposure = &mut ctx.accounts.region_exposure;
+        if region_exposure.geohash == [0; REGION_GEOHASH_PRECISION] {
+            region_exposure.bump = ctx.bumps.region_exposure;
+            region_exposure.geohash = region_geohash(&params.geographic_bounds);
+        }
+        region_exposure.add(
+            params.payment_mode,
+            committed_coverage,
+            ctx.accounts.global_state.max_region_exposure,
+        )?;
+
+        let climate_readings = &mut ctx.accounts.climate_readings;
+        climate_readings.bump = ctx.bumps.climate_readings;
+        climate_readings.policy = policy.key();
+        climate_readings.buffers = Vec::new();
 
         // Update global state
         let global_state = &mut ctx.accounts.global_state;
         global_state.total_policies = global_state.total_policies.checked_add(1)
             .ok_or(AmocaError::MathOverflow)?;
 
+        let registry = &mut ctx.accounts.policy_registry;
+        if registry.owner == Pubkey::default() {
+            registry.bump = ctx.bumps.policy_registry;
+            registry.owner = ctx.accounts.owner.key();
+        }
+        require!(
+            registry.policy_ids.len() < MAX_POLICIES_PER_OWNER,
+            AmocaError::PolicyRegistryFull
+        );
+        require!(
+            !registry.policy_ids.contains(&params.policy_id),
+            AmocaError::PolicyIdTaken
+        );
+        require!(
+            current_time.saturating_sub(registry.last_policy_created)
+                >= global_state.policy_creation_interval_seconds,
+            AmocaError::PolicyCreationTooFrequent
+        );
+        registry.policy_ids.push(params.policy_id);
+        registry.last_policy_created = current_time;
+
+        emit!(PolicyCreated {
+            policy: ctx.accounts.policy.key(),
+            owner: ctx.accounts.owner.key(),
+            policy_type: params.policy_type,
+            coverage_amount: params.coverage_amount,
+            premium_amount: params.premium_amount,
+            end_timestamp: params.end_timestamp,
+        });
+
         msg!("Climate policy created for owner: {}", ctx.accounts.owner.key());
         msg!("Policy type: {:?}, Coverage: {}", params.policy_type, params.coverage_amount);
 
@@ -79,381 +474,5491 @@ pub mod amoca_climate_insurance {
     }
 
     /// Deposit premium to activate climate insurance policy
+    ///
+    /// Policies with a premium schedule activate once the first installment
+    /// clears and take later installments through further deposits; paying
+    /// off what is due returns a `Lapsed` policy to monitoring.
     pub fn deposit_premium(
         ctx: Context<DepositPremium>,
         _policy_id: u64,
         amount: u64,
     ) -> Result<()> {
         let policy = &mut ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
         
         // Verify policy status
-        require!(policy.status == PolicyStatus::Inactive, AmocaError::PolicyAlreadyActive);
-        require!(amount >= policy.premium_amount, AmocaError::InsufficientPremium);
+        let activating = policy.status == PolicyStatus::Inactive;
+        if activating {
+            require!(
+                current_time <= policy.premium_deadline,
+                AmocaError::PremiumDeadlinePassed
+            );
+            match policy.premium_schedule.first() {
+                Some(first) => {
+                    require!(amount >= first.amount, AmocaError::InsufficientPremium);
+                    require!(amount <= policy.premium_amount, AmocaError::InvalidPremiumAmount);
+                }
+                None => require!(amount >= policy.premium_amount, AmocaError::InsufficientPremium),
+            }
+        } else {
+            // Only installment plans take further deposits
+            require!(
+                !policy.premium_schedule.is_empty()
+                    && matches!(
+                        policy.status,
+                        PolicyStatus::Active
+                            | PolicyStatus::Monitoring
+                            | PolicyStatus::Triggered
+                            | PolicyStatus::Lapsed
+                    ),
+                AmocaError::PolicyAlreadyActive
+            );
+            require!(
+                amount > 0 && amount <= policy.premium_amount.saturating_sub(policy.premium_paid_so_far),
+                AmocaError::InvalidPremiumAmount
+            );
+        }
 
-        // Transfer premium from user to risk pool
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.risk_pool_token_account.to_account_info(),
-            authority: ctx.accounts.owner.to_account_info(),
+        // Transfer premium from user to the fee vault and risk pool
+        let pool = PoolTransfer {
+            mode: policy.payment_mode,
+            user: Some(ctx.accounts.owner.to_account_info()),
+            user_token_account: ctx.accounts.user_token_account.as_ref(),
+            risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
-
-        // Activate policy
-        policy.status = PolicyStatus::Active;
-        policy.premium_amount = amount;
+        let (protocol_fee, pool_balance) = pool.collect_premium(
+            ctx.accounts.fee_vault.as_ref(),
+            amount,
+            ctx.accounts.global_state.protocol_fee_bps,
+        )?;
 
-        // Update global state
+        // Activating the policy commits its coverage against the pool
         let global_state = &mut ctx.accounts.global_state;
-        global_state.total_premiums_collected = global_state.total_premiums_collected
+        if activating {
+            global_state.commit_coverage(policy.payment_mode, policy.committed_exposure()?, pool_balance)?;
+            policy.status = PolicyStatus::Active;
+        }
+        if policy.premium_schedule.is_empty() {
+            policy.premium_amount = amount;
+            policy.full_term_premium = amount;
+        }
+        policy.premium_paid_so_far = policy.premium_paid_so_far
             .checked_add(amount)
             .ok_or(AmocaError::MathOverflow)?;
+        policy.protocol_fee_paid = policy.protocol_fee_paid
+            .checked_add(protocol_fee)
+            .ok_or(AmocaError::MathOverflow)?;
+        if policy.status == PolicyStatus::Lapsed && !policy.has_overdue_installment(current_time) {
+            policy.status = PolicyStatus::Monitoring;
+        }
+
+        // Update global state
+        global_state.record_premium(policy.payment_mode, amount, protocol_fee)?;
+        ctx.accounts.epoch_stats.record_premium(ctx.bumps.epoch_stats, policy.payment_mode, amount)?;
+
+        emit!(PremiumDeposited {
+            policy: policy.key(),
+            owner: ctx.accounts.owner.key(),
+            amount,
+            protocol_fee,
+        });
 
         msg!("Premium deposited: {} for policy", amount);
         Ok(())
     }
 
-    /// Submit climate data from authorized oracles
-    pub fn submit_climate_data(
-        ctx: Context<SubmitClimateData>,
-        data_points: Vec<ClimateDataPoint>,
+    /// Extend a policy's coverage period against a fresh premium
+    pub fn renew_policy(
+        ctx: Context<RenewPolicy>,
+        _policy_id: u64,
+        additional_duration: i64,
+        premium_amount: u64,
     ) -> Result<()> {
-        let oracle_data = &mut ctx.accounts.oracle_data;
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp;
+        let policy = &mut ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
 
-        // Validate oracle is authorized
-        require!(oracle_data.is_active, AmocaError::OracleNotAuthorized);
-        
-        // Validate data points
-        require!(!data_points.is_empty(), AmocaError::InvalidOracleData);
-        require!(data_points.len() <= 10, AmocaError::TooManyDataPoints);
+        require!(
+            matches!(
+                policy.status,
+                PolicyStatus::Active | PolicyStatus::Monitoring | PolicyStatus::Expired
+            ),
+            AmocaError::PolicyNotRenewable
+        );
+        require!(
+            policy.premium_paid_so_far >= policy.premium_amount,
+            AmocaError::PremiumInstallmentsOutstanding
+        );
+        require!(additional_duration > 0, AmocaError::InvalidPolicyDuration);
+        require!(premium_amount > 0, AmocaError::InvalidPremiumAmount);
+        require!(
+            policy.committed_value(policy.coverage_amount)?
+                <= ctx.accounts.global_state.coverage_limit(policy.policy_type),
+            AmocaError::CoverageExceedsTypeLimit
+        );
 
-        for data_point in &data_points {
-            // Check data recency (within last hour)
-            require!(
-                current_time - data_point.timestamp <= 3600,
-                AmocaError::StaleOracleData
-            );
-            
-            // Check confidence level
-            require!(
-                data_point.confidence_level >= 50,
-                AmocaError::LowConfidenceData
-            );
+        // Extend from the later of the old end and now, so a lapsed policy
+        // gets the full additional duration
+        let end_timestamp = policy.end_timestamp
+            .max(current_time)
+            .checked_add(additional_duration)
+            .ok_or(AmocaError::MathOverflow)?;
+        require!(
+            end_timestamp - current_time <= ctx.accounts.global_state.max_policy_duration_seconds,
+            AmocaError::PolicyDurationTooLong
+        );
+        // The premium floor applies per term as long as the one the policy was
+        // priced for, so a longer renewal owes proportionally more
+        let previous_term = (policy.end_timestamp - policy.start_timestamp).max(1) as u64;
+        require!(
+            ctx.accounts.global_state.is_adequate_premium(
+                mul_div(premium_amount, previous_term, (end_timestamp - current_time) as u64)?,
+                policy.committed_exposure()?,
+            ),
+            AmocaError::PremiumRatioTooLow
+        );
+
+        let pool = PoolTransfer {
+            mode: policy.payment_mode,
+            user: Some(ctx.accounts.owner.to_account_info()),
+            user_token_account: ctx.accounts.user_token_account.as_ref(),
+            risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+        };
+        let (protocol_fee, pool_balance) = pool.collect_premium(
+            ctx.accounts.fee_vault.as_ref(),
+            premium_amount,
+            ctx.accounts.global_state.protocol_fee_bps,
+        )?;
+
+        // Expired policies released their coverage and must commit it again
+        let global_state = &mut ctx.accounts.global_state;
+        if policy.status == PolicyStatus::Expired {
+            global_state.commit_coverage(
+                policy.payment_mode,
+                policy.committed_exposure()?,
+                pool_balance,
+            )?;
+            ctx.accounts.region_exposure.add(
+                policy.payment_mode,
+                policy.committed_exposure()?,
+                global_state.max_region_exposure,
+            )?;
+            policy.breach_started_at = None;
         }
 
-        // Update oracle data
-        oracle_data.last_update = current_time;
-        oracle_data.data_points_count = oracle_data.data_points_count
-            .checked_add(data_points.len() as u32)
-            .ok_or(AmocaError::MathOverflow)?;
+        policy.end_timestamp = end_timestamp;
+        policy.start_timestamp = current_time;
+        policy.premium_amount = premium_amount;
+        policy.full_term_premium = premium_amount;
+        policy.premium_paid_so_far = premium_amount;
+        policy.premium_schedule.clear();
+        policy.protocol_fee_paid = protocol_fee;
+        policy.qualifying_readings = 0;
+        policy.status = PolicyStatus::Active;
 
-        // Update reputation based on data quality
-        let avg_confidence: u8 = data_points.iter()
-            .map(|dp| dp.confidence_level)
-            .sum::<u8>() / data_points.len() as u8;
-        
-        oracle_data.reputation_score = (oracle_data.reputation_score as u16 + avg_confidence as u16) / 2;
-        oracle_data.reputation_score = oracle_data.reputation_score.min(100);
+        global_state.record_premium(policy.payment_mode, premium_amount, protocol_fee)?;
+        ctx.accounts.epoch_stats.record_premium(ctx.bumps.epoch_stats, policy.payment_mode, premium_amount)?;
 
-        msg!("Climate data submitted: {} points from oracle", data_points.len());
+        emit!(PolicyRenewed {
+            policy: policy.key(),
+            owner: ctx.accounts.owner.key(),
+            premium_amount,
+            end_timestamp: policy.end_timestamp,
+        });
+
+        msg!("Policy renewed until {}", policy.end_timestamp);
         Ok(())
     }
 
-    /// Evaluate climate triggers for a policy
-    pub fn evaluate_climate_trigger(
-        ctx: Context<EvaluateClimateTrigger>,
+    /// Raise an in-force policy's coverage against a pro-rated premium
+    ///
+    /// The increment is priced at the policy's full-term premium per unit of
+    /// coverage for the remaining term, and the added coverage is committed against
+    /// the pool and its region as a new policy's would be. Once
+    /// `max_stacked_coverage` is configured, `remaining_accounts` holds each
+    /// policy in the owner's registry, in registry order, as for creation.
+    pub fn increase_coverage<'info>(
+        ctx: Context<'_, '_, 'info, 'info, IncreaseCoverage<'info>>,
         _policy_id: u64,
+        additional_coverage: u64,
     ) -> Result<()> {
         let policy = &mut ctx.accounts.policy;
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp;
+        let current_time = Clock::get()?.unix_timestamp;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
 
-        // Verify policy is active or monitoring
         require!(
-            policy.status == PolicyStatus::Active || policy.status == PolicyStatus::Monitoring,
-            AmocaError::PolicyNotActive
+            matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring),
+            AmocaError::CoverageNotIncreasable
+        );
+        require!(current_time < policy.end_timestamp, AmocaError::PolicyExpired);
+        require!(additional_coverage > 0, AmocaError::InvalidCoverageAmount);
+        // The top-up isn't part of the installment plan, so the plan is
+        // settled first
+        require!(
+            policy.premium_paid_so_far >= policy.premium_amount,
+            AmocaError::PremiumInstallmentsOutstanding
         );
 
-        // Check if policy has expired
-        require!(current_time <= policy.end_timestamp, AmocaError::PolicyExpired);
+        let coverage_amount = policy.coverage_amount
+            .checked_add(additional_coverage)
+            .ok_or(AmocaError::MathOverflow)?;
+        require!(
+            policy.committed_value(coverage_amount)? <= ctx.accounts.global_state.coverage_limit(policy.policy_type),
+            AmocaError::CoverageExceedsTypeLimit
+        );
+        // Committed at the policy's own rate, as the difference in its
+        // remaining coverage's value so later releases match
+        let added_exposure = policy
+            .committed_value(
+                policy.remaining_coverage()
+                    .checked_add(additional_coverage)
+                    .ok_or(AmocaError::MathOverflow)?,
+            )?
+            .checked_sub(policy.committed_exposure()?)
+            .ok_or(AmocaError::MathOverflow)?;
 
-        // Evaluate trigger conditions (simplified logic)
-        let trigger_met = evaluate_trigger_conditions(policy, &ctx.accounts.oracle_data)?;
-        
-        if trigger_met {
-            policy.status = PolicyStatus::Triggered;
-            msg!("Climate trigger conditions met for policy");
-        } else {
-            policy.status = PolicyStatus::Monitoring;
+        // The raised coverage counts against the owner's overlapping coverage
+        // of the peril as a new policy's would; the registry includes this
+        // policy at its current coverage
+        let max_stacked_coverage = ctx.accounts.global_state.max_stacked_coverage;
+        if max_stacked_coverage != u64::MAX {
+            let stacked = stacked_coverage(
+                policy.owner,
+                &ctx.accounts.policy_registry.policy_ids,
+                ctx.remaining_accounts,
+                |other| {
+                    other.stacks_with(
+                        policy.policy_type,
+                        policy.payment_mode,
+                        policy.coverage_denomination,
+                        &policy.geographic_bounds,
+                    )
+                },
+            )?
+            .checked_add(added_exposure)
+            .ok_or(AmocaError::MathOverflow)?;
+            require_logged!(
+                stacked <= max_stacked_coverage,
+                AmocaError::StackedCoverageExceeded,
+                "Overlapping {:?} coverage of {} exceeds the limit of {}",
+                policy.policy_type,
+                stacked,
+                max_stacked_coverage
+            );
         }
 
-        // Update last evaluation timestamp
-        policy.last_data_update = current_time;
+        // Price the increment at the policy's full-term rate, which earlier
+        // pro-rated top-ups don't dilute, rounding in the pool's favour
+        let full_term_increment = (policy.full_term_premium as u128)
+            .checked_mul(additional_coverage as u128)
+            .ok_or(AmocaError::MathOverflow)?
+            .div_ceil(policy.coverage_amount as u128);
+        let full_term_increment = u64::try_from(full_term_increment).map_err(|_| AmocaError::MathOverflow)?;
+        let premium_amount = prorata_amount(
+            full_term_increment,
+            policy.start_timestamp,
+            policy.end_timestamp,
+            current_time,
+        )?;
+        require!(premium_amount > 0, AmocaError::InvalidPremiumAmount);
+        require!(
+            ctx.accounts.global_state.is_adequate_premium(premium_amount, added_exposure),
+            AmocaError::PremiumRatioTooLow
+        );
+
+        let pool = PoolTransfer {
+            mode: policy.payment_mode,
+            user: Some(ctx.accounts.owner.to_account_info()),
+            user_token_account: ctx.accounts.user_token_account.as_ref(),
+            risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+        };
+        let (protocol_fee, pool_balance) = pool.collect_premium(
+            ctx.accounts.fee_vault.as_ref(),
+            premium_amount,
+            ctx.accounts.global_state.protocol_fee_bps,
+        )?;
 
-        msg!("Trigger evaluation completed");
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.commit_coverage(policy.payment_mode, added_exposure, pool_balance)?;
+        ctx.accounts.region_exposure.extend(
+            policy.payment_mode,
+            added_exposure,
+            global_state.max_region_exposure,
+        )?;
+
+        // An uncapped policy stays uncapped
+        if policy.max_single_payout == policy.coverage_amount {
+            policy.max_single_payout = coverage_amount;
+        }
+        policy.coverage_amount = coverage_amount;
+        policy.full_term_premium = policy.full_term_premium
+            .checked_add(full_term_increment)
+            .ok_or(AmocaError::MathOverflow)?;
+        policy.premium_amount = policy.premium_amount
+            .checked_add(premium_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        policy.premium_paid_so_far = policy.premium_paid_so_far
+            .checked_add(premium_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        policy.protocol_fee_paid = policy.protocol_fee_paid
+            .checked_add(protocol_fee)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        global_state.record_premium(policy.payment_mode, premium_amount, protocol_fee)?;
+        ctx.accounts.epoch_stats.record_premium(ctx.bumps.epoch_stats, policy.payment_mode, premium_amount)?;
+
+        emit!(CoverageIncreased {
+            policy: policy.key(),
+            owner: ctx.accounts.owner.key(),
+            additional_coverage,
+            coverage_amount,
+            premium_amount,
+            protocol_fee,
+        });
+
+        msg!("Coverage increased to {} for premium {}", coverage_amount, premium_amount);
         Ok(())
     }
 
-    /// Execute automated climate payout
-    pub fn execute_climate_payout(
-        ctx: Context<ExecuteClimatePayout>,
-        policy_id: u64,
-        payout_amount: u64,
+    /// Replace a policy's trigger conditions before its premium is paid
+    pub fn update_trigger_conditions(
+        ctx: Context<UpdateTriggerConditions>,
+        _policy_id: u64,
+        trigger_conditions: TriggerConditions,
     ) -> Result<()> {
         let policy = &mut ctx.accounts.policy;
-        
-        // Verify policy is triggered
-        require!(policy.status == PolicyStatus::Triggered, AmocaError::TriggerNotMet);
-        
-        // Validate payout amount
-        require!(payout_amount > 0, AmocaError::InvalidPayoutAmount);
-        require!(payout_amount <= policy.coverage_amount, AmocaError::ExcessivePayoutAmount);
-
-        // Calculate payout based on parametric formula
-        let calculated_payout = calculate_payout_amount(policy)?;
-        require!(payout_amount <= calculated_payout, AmocaError::ExcessivePayoutAmount);
-
-        // Execute payout transfer
-        let seeds = &[
-            b"risk_pool".as_ref(),
-            &[ctx.accounts.global_state.bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.risk_pool_token_account.to_account_info(),
-            to: ctx.accounts.policyholder_token_account.to_account_info(),
-            authority: ctx.accounts.risk_pool_pda.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, payout_amount)?;
+        // Pricing is fixed once the premium is in the pool
+        require!(policy.status == PolicyStatus::Inactive, AmocaError::PolicyLocked);
+        validate_trigger_conditions(policy.payout_calculation, &trigger_conditions)?;
 
-        // Update policy status
-        policy.status = PolicyStatus::Claimed;
+        policy.trigger_thresholds = trigger_conditions;
 
-        // Update global state
-        let global_state = &mut ctx.accounts.global_state;
-        global_state.total_payouts = global_state.total_payouts
-            .checked_add(payout_amount)
-            .ok_or(AmocaError::MathOverflow)?;
+        emit!(TriggerConditionsUpdated {
+            policy: policy.key(),
+            owner: policy.owner,
+        });
 
-        msg!("Climate payout executed: {}", payout_amount);
+        msg!("Trigger conditions updated for policy");
         Ok(())
     }
 
-    /// Pause the program (admin only)
-    pub fn pause_program(ctx: Context<AdminAction>) -> Result<()> {
-        let global_state = &mut ctx.accounts.global_state;
-        global_state.is_paused = true;
-        msg!("Program paused by authority");
+    /// Change who receives payouts, until a trigger has fired
+    ///
+    /// Tokenized policies pay the token holder, so they have no beneficiary to change.
+    pub fn update_beneficiary(
+        ctx: Context<UpdateBeneficiary>,
+        _policy_id: u64,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        require!(policy.policy_mint.is_none(), AmocaError::PolicyIsTokenized);
+
+        require!(
+            matches!(
+                policy.status,
+                PolicyStatus::Inactive | PolicyStatus::Active | PolicyStatus::Monitoring
+            ) && policy.paid_out_so_far == 0,
+            AmocaError::BeneficiaryLocked
+        );
+
+        let previous_beneficiary = policy.beneficiary;
+        policy.beneficiary = beneficiary;
+
+        emit!(BeneficiaryUpdated {
+            policy: policy.key(),
+            previous_beneficiary,
+            beneficiary,
+        });
+
+        msg!("Beneficiary updated to: {}", beneficiary);
         Ok(())
     }
 
-    /// Unpause the program (admin only)
-    pub fn unpause_program(ctx: Context<AdminAction>) -> Result<()> {
-        let global_state = &mut ctx.accounts.global_state;
-        global_state.is_paused = false;
-        msg!("Program unpaused by authority");
+    /// Offer a policy to a new owner, who takes it over with
+    /// `accept_policy_transfer` (owner only)
+    ///
+    /// Passing `None` withdraws a pending offer. Tokenized policies change
+    /// hands with their policy token instead.
+    pub fn offer_policy_transfer(
+        ctx: Context<OfferPolicyTransfer>,
+        _policy_id: u64,
+        new_owner: Option<Pubkey>,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        require!(policy.policy_mint.is_none(), AmocaError::PolicyIsTokenized);
+        require!(
+            !matches!(policy.status, PolicyStatus::Claimed | PolicyStatus::Expired),
+            AmocaError::PolicyNotTransferable
+        );
+        if let Some(new_owner) = new_owner {
+            require_keys_neq!(new_owner, policy.owner, AmocaError::PolicyNotTransferable);
+        }
+
+        policy.pending_owner = new_owner;
+
+        emit!(PolicyTransferOffered {
+            policy: policy.key(),
+            owner: policy.owner,
+            pending_owner: new_owner,
+        });
+
+        msg!("Policy transfer offered to: {:?}", new_owner);
         Ok(())
     }
-}
 
-// Helper functions
+    /// Take over a policy offered by `offer_policy_transfer` (pending owner only)
+    ///
+    /// The policy moves between the owners' registries. A beneficiary left at
+    /// the previous owner follows the policy to the new one.
+    pub fn accept_policy_transfer(
+        ctx: Context<AcceptPolicyTransfer>,
+        policy_id: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let new_owner = ctx.accounts.new_owner.key();
+        require!(policy.policy_mint.is_none(), AmocaError::PolicyIsTokenized);
+        require!(
+            !matches!(policy.status, PolicyStatus::Claimed | PolicyStatus::Expired),
+            AmocaError::PolicyNotTransferable
+        );
 
-/// Evaluate trigger conditions based on policy and oracle data
-fn evaluate_trigger_conditions(
-    policy: &ClimatePolicy,
-    _oracle_account: &UncheckedAccount,
-) -> Result<bool> {
-    // Simplified trigger evaluation logic
-    // In production, this would:
-    // 1. Read data from multiple oracle feeds
-    // 2. Compare against trigger thresholds
-    // 3. Apply consensus mechanisms
-    // 4. Calculate confidence scores
-    
-    // For demonstration, return based on risk score
-    Ok(policy.risk_score > 80)
+        let registry = &mut ctx.accounts.new_owner_registry;
+        if registry.owner == Pubkey::default() {
+            registry.bump = ctx.bumps.new_owner_registry;
+            registry.owner = new_owner;
+        }
+        require!(
+            registry.policy_ids.len() < MAX_POLICIES_PER_OWNER,
+            AmocaError::PolicyRegistryFull
+        );
+        require!(!registry.policy_ids.contains(&policy_id), AmocaError::PolicyIdTaken);
+        registry.policy_ids.push(policy_id);
+        ctx.accounts.owner_registry.remove(policy_id);
+
+        let previous_owner = policy.owner;
+        if policy.beneficiary == previous_owner {
+            policy.beneficiary = new_owner;
+        }
+        policy.owner = new_owner;
+        policy.pending_owner = None;
+
+        emit!(PolicyTransferred {
+            policy: policy.key(),
+            previous_owner,
+            new_owner,
+        });
+
+        msg!("Policy transferred to: {}", new_owner);
+        Ok(())
+    }
+
+    /// Change how often a policy is evaluated, e.g. daily for slow-onset drought
+    /// or hourly for hurricanes (holder only)
+    ///
+    /// The interval also spaces trigger evaluations and sets how fresh readings
+    /// must be to count.
+    pub fn set_monitoring_frequency(
+        ctx: Context<SetMonitoringFrequency>,
+        _policy_id: u64,
+        monitoring_frequency: u32,
+    ) -> Result<()> {
+        require!(
+            is_valid_monitoring_frequency(monitoring_frequency),
+            AmocaError::InvalidMonitoringFrequency
+        );
+        let policy = &mut ctx.accounts.policy;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
+        require!(
+            matches!(
+                policy.status,
+                PolicyStatus::Inactive | PolicyStatus::Active | PolicyStatus::Monitoring
+            ),
+            AmocaError::PolicyNotActive
+        );
+
+        let previous_frequency = policy.monitoring_frequency;
+        policy.monitoring_frequency = monitoring_frequency;
+
+        emit!(MonitoringFrequencyUpdated {
+            policy: policy.key(),
+            previous_frequency,
+            monitoring_frequency,
+        });
+
+        msg!("Monitoring frequency set to {} seconds", monitoring_frequency);
+        Ok(())
+    }
+
+    /// Mint a 1-of-1 token representing the policy, making its ownership transferable
+    ///
+    /// Once tokenized, premiums, renewals and cancellation require holding the
+    /// token, and payouts go to its holder instead of `beneficiary`.
+    pub fn init_policy_nft(ctx: Context<InitPolicyNft>, policy_id: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        require!(
+            !matches!(policy.status, PolicyStatus::Expired | PolicyStatus::Claimed),
+            AmocaError::PolicyLocked
+        );
+
+        let id_bytes = policy_id.to_le_bytes();
+        let seeds = &[
+            b"policy".as_ref(),
+            policy.creator.as_ref(),
+            id_bytes.as_ref(),
+            &[policy.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.policy_mint.to_account_info(),
+                    to: ctx.accounts.owner_policy_token.to_account_info(),
+                    authority: policy.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        // Revoke minting so the supply stays at exactly one
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: policy.to_account_info(),
+                    account_or_mint: ctx.accounts.policy_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        policy.policy_mint = Some(ctx.accounts.policy_mint.key());
+
+        emit!(PolicyTokenized {
+            policy: policy.key(),
+            owner: policy.owner,
+            policy_mint: ctx.accounts.policy_mint.key(),
+        });
+
+        msg!("Policy tokenized: {}", ctx.accounts.policy_mint.key());
+        Ok(())
+    }
+
+    /// Submit climate data from authorized oracles
+    pub fn submit_climate_data(
+        ctx: Context<SubmitClimateData>,
+        data_points: Vec<ClimateDataPoint>,
+    ) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        let climate_readings = &mut ctx.accounts.climate_readings;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        // Validate oracle is authorized, both globally and by the policy
+        require_logged!(
+            oracle_data.is_active,
+            AmocaError::OracleNotAuthorized,
+            "Oracle {} is deactivated",
+            oracle_data.provider
+        );
+        require_logged!(
+            ctx.accounts.policy.oracle_sources.contains(&oracle_data.provider),
+            AmocaError::OracleNotAuthorized,
+            "Oracle {} is not a source of policy {}",
+            oracle_data.provider,
+            ctx.accounts.policy.key()
+        );
+        
+        // Validate data points
+        require!(!data_points.is_empty(), AmocaError::InvalidOracleData);
+        require!(data_points.len() <= 10, AmocaError::TooManyDataPoints);
+
+        // Submissions are spaced at the oracle's feed cadence, so flooding
+        // batches can't farm the reputation average
+        require_logged!(
+            oracle_data.data_points_count == 0
+                || current_time.saturating_sub(oracle_data.last_update) >= oracle_data.min_submission_interval as i64,
+            AmocaError::SubmissionTooFrequent,
+            "Oracle {} submitted at {}; next submission allowed at {}",
+            oracle_data.provider,
+            oracle_data.last_update,
+            oracle_data.last_update.saturating_add(oracle_data.min_submission_interval as i64)
+        );
+
+        for data_point in &data_points {
+            // Readings can only be attributed to the oracle signing for them
+            require_logged!(
+                data_point.source_id == ctx.accounts.oracle_provider.key(),
+                AmocaError::InvalidOracleData,
+                "Data point attributed to {} was submitted by oracle {}",
+                data_point.source_id,
+                ctx.accounts.oracle_provider.key()
+            );
+            require_logged!(
+                oracle_data.supported_data_types.contains(&data_point.data_type),
+                AmocaError::UnsupportedDataType,
+                "Oracle {} does not report {:?}",
+                oracle_data.provider,
+                data_point.data_type
+            );
+            require_logged!(
+                data_point.unit == data_point.data_type.unit(),
+                AmocaError::InvalidMeasurementUnit,
+                "{:?} is reported in {:?}, got {:?}",
+                data_point.data_type,
+                data_point.data_type.unit(),
+                data_point.unit
+            );
+
+            // Check data recency (within last hour)
+            require!(
+                current_time - data_point.timestamp <= 3600,
+                AmocaError::StaleOracleData
+            );
+            
+            // Check confidence level
+            require!(
+                data_point.confidence_level >= MIN_CONFIDENCE_LEVEL,
+                AmocaError::LowConfidenceData
+            );
+
+            // Check the hash binds to the reported reading
+            require!(
+                data_point.verification_hash == data_point.canonical_hash()?,
+                AmocaError::InvalidVerificationHash
+            );
+        }
+
+        // Persist readings into the policy's ring buffer, counting those taken
+        // during the term that the policy's evaluation would accept
+        let policy = &mut ctx.accounts.policy;
+        let relevant = relevant_thresholds(policy.policy_type, &policy.trigger_thresholds);
+        let reputation_floor = ctx.accounts.global_state.reputation_floor;
+        let mut qualifying = 0u32;
+        for data_point in &data_points {
+            let reading = ClimateReading {
+                oracle: oracle_data.provider,
+                location: data_point.location,
+                value: data_point.value,
+                timestamp: data_point.timestamp,
+                confidence_level: data_point.confidence_level,
+                reputation_score: oracle_data.reputation_score,
+                oracle_type: oracle_data.oracle_type,
+            };
+            let term = MeasurementWindow { start: policy.start_timestamp, end: policy.end_timestamp };
+            if relevant.iter().any(|check| check.data_type == data_point.data_type)
+                && is_qualifying_reading(&reading, policy, reputation_floor, term)
+            {
+                qualifying += 1;
+            }
+            climate_readings.record(data_point.data_type, reading);
+        }
+        policy.qualifying_readings = policy.qualifying_readings.saturating_add(qualifying);
+
+        // Re-score live exposure from how close readings sit to the thresholds
+        if let Some(score) = live_risk_score(
+            policy,
+            climate_readings,
+            ctx.accounts.global_state.reputation_floor,
+            current_time,
+        ) {
+            policy.update_risk_score(score, current_time);
+        }
+
+        // Update oracle data
+        oracle_data.last_update = current_time;
+        oracle_data.data_points_count = oracle_data.data_points_count
+            .checked_add(data_points.len() as u32)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        // Update reputation based on data quality
+        let avg_confidence = average_confidence(&data_points);
+        oracle_data.reputation_score = smoothed_reputation(
+            oracle_data.reputation_score,
+            avg_confidence,
+            ctx.accounts.global_state.reputation_smoothing_bps,
+        )?;
+
+        emit!(ClimateDataSubmitted {
+            oracle: oracle_data.provider,
+            policy: ctx.accounts.policy.key(),
+            data_points: data_points.len() as u8,
+            reputation_score: oracle_data.reputation_score,
+            risk_score: ctx.accounts.policy.risk_score,
+        });
+
+        msg!("Climate data submitted: {} points from oracle", data_points.len());
+        Ok(())
+    }
+
+    /// Publish the premium mint's exchange rate for a reference currency
+    /// (active oracles only)
+    ///
+    /// Denominated policies designating this oracle convert their payouts at
+    /// the latest rate it published.
+    pub fn submit_fx_rate(ctx: Context<SubmitFxRate>, denomination: [u8; 3], rate: u64) -> Result<()> {
+        require!(ctx.accounts.oracle_data.is_active, AmocaError::OracleNotAuthorized);
+        require!(rate > 0, AmocaError::InvalidFxRate);
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let fx_rate = &mut ctx.accounts.fx_rate;
+        fx_rate.bump = ctx.bumps.fx_rate;
+        fx_rate.provider = ctx.accounts.oracle_provider.key();
+        fx_rate.denomination = denomination;
+        fx_rate.rate = rate;
+        fx_rate.updated_at = current_time;
+
+        emit!(FxRateSubmitted {
+            provider: fx_rate.provider,
+            denomination,
+            rate,
+            timestamp: current_time,
+        });
+
+        msg!("FX rate submitted: {}", rate);
+        Ok(())
+    }
+
+    /// Evaluate climate triggers for a policy
+    pub fn evaluate_climate_trigger(
+        ctx: Context<EvaluateClimateTrigger>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        require_logged!(
+            !policy.is_frozen,
+            AmocaError::PolicyFrozen,
+            "Policy {} is frozen",
+            policy.key()
+        );
+
+        // A missed installment lapses the policy instead of evaluating it
+        if lapse_if_overdue(policy, current_time) {
+            return Ok(());
+        }
+
+        // Verify policy is active or monitoring; a triggered policy keeps its
+        // trigger through the grace period, so the payout can still land
+        let grace_period = ctx.accounts.global_state.trigger_grace_period_seconds;
+        require_logged!(
+            policy.status != PolicyStatus::Triggered || policy.has_trigger_lapsed(grace_period, current_time),
+            AmocaError::TriggerGracePeriodActive,
+            "Policy {} stays Triggered until {}",
+            policy.key(),
+            policy.triggered_at.saturating_add(grace_period)
+        );
+        require_logged!(
+            matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring | PolicyStatus::Triggered),
+            AmocaError::PolicyNotActive,
+            "Policy {} is {:?}, expected Active or Monitoring",
+            policy.key(),
+            policy.status
+        );
+
+        // Check if policy has expired
+        require_logged!(
+            current_time <= policy.end_timestamp,
+            AmocaError::PolicyExpired,
+            "Policy {} ended at {}, now {}",
+            policy.key(),
+            policy.end_timestamp,
+            current_time
+        );
+
+        // At most one evaluation per monitoring interval
+        require_logged!(
+            current_time.saturating_sub(policy.last_data_update) >= policy.monitoring_frequency as i64,
+            AmocaError::EvaluationTooFrequent,
+            "Policy {} was evaluated at {}; next evaluation due at {}",
+            policy.key(),
+            policy.last_data_update,
+            policy.last_data_update.saturating_add(policy.monitoring_frequency as i64)
+        );
+
+        // Triggers must reflect current conditions, not a stale snapshot
+        require_logged!(
+            has_fresh_reading(
+                policy,
+                &ctx.accounts.climate_readings,
+                ctx.accounts.global_state.reputation_floor,
+                current_time,
+            ),
+            AmocaError::StaleOracleData,
+            "Policy {} has no qualifying reading since {}",
+            policy.key(),
+            current_time.saturating_sub(policy.monitoring_frequency as i64 * 2)
+        );
+
+        let window = MeasurementWindow::trailing(current_time, policy.trigger_thresholds.measurement_period);
+        let triggered = apply_trigger_evaluation(
+            policy,
+            &ctx.accounts.climate_readings,
+            &mut ctx.accounts.global_state,
+            window,
+            current_time,
+        )?;
+
+        // Tip the evaluator for cranking the trigger
+        if triggered {
+            let cycle = policy.triggered_at;
+            let reward = pay_keeper_reward(
+                &mut policy.trigger_reward_cycle,
+                cycle,
+                ctx.accounts.global_state.keeper_reward,
+                ctx.accounts.fee_vault.as_ref(),
+                ctx.accounts.keeper_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                ctx.accounts.global_state.fee_vault_bump,
+            )?;
+            if reward > 0 {
+                emit!(KeeperRewarded {
+                    keeper: ctx.accounts.evaluator.key(),
+                    policy: policy.key(),
+                    amount: reward,
+                    triggered_at: cycle,
+                });
+            }
+        }
+
+        msg!("Trigger evaluation completed");
+        Ok(())
+    }
+
+    /// Evaluate many policies in one transaction (permissionless keeper crank)
+    ///
+    /// `remaining_accounts` holds `(policy, climate_readings)` pairs, with each
+    /// policy writable. Policies that are not active, have expired, are not
+    /// yet due for evaluation or lack fresh data are skipped rather than
+    /// failing the batch; those with a missed installment are lapsed.
+    pub fn batch_evaluate_triggers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchEvaluateTriggers<'info>>,
+    ) -> Result<()> {
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        require!(
+            pairs.len() > 0 && pairs.remainder().is_empty(),
+            AmocaError::InvalidBatchAccounts
+        );
+        let batch_size = pairs.len() as u32;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let global_state = &mut ctx.accounts.global_state;
+        let mut evaluated: u32 = 0;
+        let mut triggered: u32 = 0;
+
+        for pair in pairs {
+            require!(pair[0].is_writable, AmocaError::InvalidBatchAccounts);
+            let mut policy = Box::new(Account::<ClimatePolicy>::try_from(&pair[0])?);
+            let climate_readings = Box::new(Account::<ClimateReadings>::try_from(&pair[1])?);
+            require_keys_eq!(climate_readings.policy, policy.key(), AmocaError::InvalidBatchAccounts);
+
+            if lapse_if_overdue(&mut policy, current_time) {
+                policy.exit(&crate::ID)?;
+                continue;
+            }
+            if !policy.is_due_for_evaluation(global_state.trigger_grace_period_seconds, current_time)
+                || !has_fresh_reading(&policy, &climate_readings, global_state.reputation_floor, current_time)
+            {
+                msg!("Skipped policy {} ({:?}): not due or no fresh data", policy.key(), policy.status);
+                continue;
+            }
+
+            let window = MeasurementWindow::trailing(current_time, policy.trigger_thresholds.measurement_period);
+            if apply_trigger_evaluation(&mut policy, &climate_readings, global_state, window, current_time)? {
+                triggered += 1;
+            }
+            evaluated += 1;
+            policy.exit(&crate::ID)?;
+        }
+
+        emit!(BatchTriggersEvaluated {
+            evaluated,
+            triggered,
+            skipped: batch_size - evaluated,
+            timestamp: current_time,
+        });
+
+        msg!("Batch evaluation: {} evaluated, {} triggered", evaluated, triggered);
+        Ok(())
+    }
+
+    /// Re-run trigger evaluation over a past window of the persisted readings,
+    /// so a trigger missed during a feed outage or keeper lag can still fire
+    /// (admin only)
+    ///
+    /// The window must lie within the policy's term and be no longer than its
+    /// measurement period. The window is judged on a copy of the policy, so
+    /// live evaluation state (breach tracking and evaluation time) is
+    /// untouched; a trigger found records its severity, which the claim is
+    /// filed and logged against, and reserves its claim as of now, and the
+    /// payout follows the usual claim, waiting and grace periods.
+    pub fn evaluate_historical_window(
+        ctx: Context<EvaluateHistoricalWindow>,
+        _policy_id: u64,
+        window_start: i64,
+        window_end: i64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require_logged!(
+            !policy.is_frozen,
+            AmocaError::PolicyFrozen,
+            "Policy {} is frozen",
+            policy.key()
+        );
+        require_logged!(
+            matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring),
+            AmocaError::PolicyNotActive,
+            "Policy {} is {:?}, expected Active or Monitoring",
+            policy.key(),
+            policy.status
+        );
+        let measurement_period = policy.trigger_thresholds.measurement_period as i64 * 86_400;
+        require_logged!(
+            policy.start_timestamp <= window_start
+                && window_start < window_end
+                && window_end <= policy.end_timestamp.min(current_time)
+                && window_end - window_start <= measurement_period,
+            AmocaError::InvalidEvaluationWindow,
+            "Window {}..{} is not a past span of at most {}s within policy {}'s term {}..{}",
+            window_start,
+            window_end,
+            measurement_period,
+            policy.key(),
+            policy.start_timestamp,
+            policy.end_timestamp
+        );
+
+        let window = MeasurementWindow { start: window_start, end: window_end };
+        let mut replay = policy.clone().into_inner();
+        let outcome = if replay.trigger_thresholds.is_in_season(window.end) {
+            evaluate_trigger_conditions(
+                &mut replay,
+                &ctx.accounts.climate_readings,
+                ctx.accounts.global_state.reputation_floor,
+                window,
+            )?
+        } else {
+            TriggerOutcome { triggered: false, severity: SeverityBreakdown::default() }
+        };
+
+        let triggered = outcome.triggered;
+        if triggered {
+            policy.last_severity = outcome.severity;
+            policy.index_value = outcome.severity.peak_bps();
+            let claim = reserved_claim(
+                policy,
+                &outcome.severity,
+                ctx.accounts.global_state.global_max_single_policy_payout,
+            )?;
+            policy.pending_claim = claim;
+            ctx.accounts.global_state.record_triggered_claim(policy.payment_mode, policy.committed_value(claim)?)?;
+            policy.status = PolicyStatus::Triggered;
+            policy.triggered_at = current_time;
+        }
+
+        emit!(HistoricalWindowEvaluated {
+            policy: policy.key(),
+            authority: ctx.accounts.authority.key(),
+            window_start,
+            window_end,
+            triggered,
+        });
+
+        msg!("Historical window {}..{} evaluated for policy {}", window_start, window_end, policy.key());
+        Ok(())
+    }
+
+    /// Execute automated climate payout
+    pub fn execute_climate_payout(
+        ctx: Context<ExecuteClimatePayout>,
+        _policy_id: u64,
+        payout_amount: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+
+        // Payouts draw on the pool, so only registered keepers and the
+        // policy's own claimants may execute them
+        require_logged!(
+            ctx.accounts.keeper.is_some()
+                || policy.is_claimant(ctx.accounts.executor.key(), ctx.accounts.policy_token.as_ref())?,
+            AmocaError::NotAuthorizedKeeper,
+            "Executor {} is neither a keeper nor a claimant of policy {}",
+            ctx.accounts.executor.key(),
+            policy.key()
+        );
+        
+        require_logged!(
+            !policy.is_frozen,
+            AmocaError::PolicyFrozen,
+            "Policy {} is frozen",
+            policy.key()
+        );
+        require_logged!(
+            !ctx.accounts.global_state.payouts_halted,
+            AmocaError::PayoutsHalted,
+            "Payouts are halted by the loss-ratio circuit breaker (limit {} bps)",
+            ctx.accounts.global_state.max_loss_ratio_bps
+        );
+
+        // Exhausted coverage leaves nothing to disburse
+        require_logged!(
+            policy.status != PolicyStatus::Claimed && policy.remaining_coverage() > 0,
+            AmocaError::NoPayoutDue,
+            "Policy {} has no coverage left: {} of {} paid out",
+            policy.key(),
+            policy.paid_out_so_far,
+            policy.coverage_amount
+        );
+
+        // Verify policy is triggered
+        require_logged!(
+            policy.status == PolicyStatus::Triggered,
+            AmocaError::TriggerNotMet,
+            "Policy {} is {:?}, expected Triggered",
+            policy.key(),
+            policy.status
+        );
+        // A trigger whose formula computed nothing reserved no claim
+        require_logged!(
+            policy.pending_claim > 0,
+            AmocaError::NoPayoutDue,
+            "Policy {} triggered at {} with a calculated payout of zero",
+            policy.key(),
+            policy.triggered_at
+        );
+        
+        // Triggers are observed from the start, but nothing pays out until
+        // the waiting period has passed
+        let current_time = Clock::get()?.unix_timestamp;
+        require_logged!(
+            current_time.saturating_sub(policy.start_timestamp) >= policy.waiting_period_seconds,
+            AmocaError::WaitingPeriodActive,
+            "Policy {} is in its waiting period until {}",
+            policy.key(),
+            policy.start_timestamp.saturating_add(policy.waiting_period_seconds)
+        );
+
+        // Missed installments block payouts until they are caught up
+        require_logged!(
+            !policy.has_overdue_installment(current_time),
+            AmocaError::PremiumInstallmentOverdue,
+            "Policy {} has paid {} of {} premium due",
+            policy.key(),
+            policy.premium_paid_so_far,
+            policy.premium_due_by(current_time)
+        );
+        
+        // Only a claim the policyholder filed is paid. Filings are clamped to
+        // the claim reserved at trigger time, which is already bounded by the
+        // formula, the per-payout cap and the coverage left.
+        require_logged!(
+            policy.filed_claim > 0,
+            AmocaError::ClaimNotFiled,
+            "Policy {} has a pending claim of {} but none filed",
+            policy.key(),
+            policy.pending_claim
+        );
+        let claim_amount = payout_amount.min(policy.filed_claim);
+        require_logged!(
+            claim_amount > 0,
+            AmocaError::InvalidPayoutAmount,
+            "Policy {}: requested {} against a filed claim of {}",
+            policy.key(),
+            payout_amount,
+            policy.filed_claim
+        );
+        // Whatever its coverage, no one policy may draw more than the
+        // protocol-wide ceiling from the pool over its life
+        let payout_ceiling = ctx.accounts.global_state.global_max_single_policy_payout;
+        require_logged!(
+            policy.transferred_so_far < payout_ceiling,
+            AmocaError::NoPayoutDue,
+            "Policy {} has been paid {}, the per-policy ceiling of {}",
+            policy.key(),
+            policy.transferred_so_far,
+            payout_ceiling
+        );
+        // The insured absorbs the deductible out of every payout, flooring at
+        // zero: a claim within it still settles the trigger, paying nothing.
+        // The deducted part is never paid, so it stays in remaining_coverage
+        // for later triggers.
+        let claim_amount = claim_amount.saturating_sub(policy.deductible_amount);
+
+        // Pay the beneficiary, or whoever holds the policy token if tokenized
+        let recipient = policy.payout_recipient(ctx.accounts.policy_token.as_ref())?;
+        let exposure_before = policy.committed_exposure()?;
+        if let Some(token_account) = &ctx.accounts.policyholder_token_account {
+            require_keys_eq!(token_account.owner, recipient, AmocaError::InvalidBeneficiary);
+        }
+        if let Some(beneficiary) = &ctx.accounts.beneficiary {
+            require_keys_eq!(beneficiary.key(), recipient, AmocaError::InvalidBeneficiary);
+        }
+
+        let pool = PoolTransfer {
+            mode: policy.payment_mode,
+            user: ctx.accounts.beneficiary.as_ref().map(|b| b.to_account_info()),
+            user_token_account: ctx.accounts.policyholder_token_account.as_ref(),
+            risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+        };
+
+        // When the pool can't cover every outstanding triggered claim, each is
+        // paid the same share; the rest is recorded as a shortfall on the policy
+        let haircut_bps = ctx.accounts.global_state.haircut_bps(policy.payment_mode, pool.balance()?);
+        let payout_amount = bps_of(claim_amount, BPS_DENOMINATOR as u16 - haircut_bps)?;
+
+        // Claims on denominated coverage are in the reference unit; the pool
+        // pays their value in the premium mint at the oracle's current rate
+        let transfer_amount = match policy.coverage_denomination {
+            None => payout_amount,
+            Some(denomination) => designated_fx_rate(
+                ctx.accounts.fx_rate.as_ref(),
+                policy.key(),
+                policy.fx_oracle,
+                denomination,
+                current_time,
+            )?
+            .convert(payout_amount)?,
+        };
+        // The ceiling counts what leaves the pool, so it binds after the FX
+        // conversion; a clamped transfer settles a proportionally smaller claim
+        let ceiling_allowance = payout_ceiling - policy.transferred_so_far;
+        let (claim_amount, payout_amount, transfer_amount) = if transfer_amount > ceiling_allowance {
+            (
+                mul_div(claim_amount, ceiling_allowance, transfer_amount)?,
+                mul_div(payout_amount, ceiling_allowance, transfer_amount)?,
+                ceiling_allowance,
+            )
+        } else {
+            (claim_amount, payout_amount, transfer_amount)
+        };
+        if transfer_amount > 0 {
+            pool.withdraw(transfer_amount)?;
+        }
+
+        // Only exhausted coverage is terminal; otherwise keep monitoring so the
+        // policy can re-trigger. The breach must persist afresh before it does.
+        policy.paid_out_so_far = policy.paid_out_so_far
+            .checked_add(payout_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        policy.transferred_so_far = policy.transferred_so_far
+            .checked_add(transfer_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        policy.haircut_bps = haircut_bps;
+        policy.haircut_shortfall = policy.haircut_shortfall
+            .checked_add(claim_amount - payout_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        let settled_claim = std::mem::take(&mut policy.pending_claim);
+        policy.filed_claim = 0;
+
+        // Keep an audit record of the payout and the breach behind it
+        let claim_log = &mut ctx.accounts.claim_log;
+        if claim_log.policy == Pubkey::default() {
+            claim_log.bump = ctx.bumps.claim_log;
+            claim_log.policy = policy.key();
+        }
+        claim_log.record(ClaimRecord {
+            timestamp: current_time,
+            amount: payout_amount,
+            claim_amount,
+            haircut_bps,
+            payout_formula: policy.payout_calculation,
+            severity: policy.last_severity,
+            breach_started_at: policy.breach_started_at,
+        })?;
+
+        policy.breach_started_at = None;
+        policy.last_payout_at = Some(current_time);
+        // Reaching the ceiling ends the policy too, freeing the coverage it can
+        // no longer draw on
+        let stranded_coverage = if policy.transferred_so_far >= payout_ceiling {
+            policy.remaining_coverage()
+        } else {
+            0
+        };
+        policy.status = if policy.remaining_coverage() == 0 || stranded_coverage > 0 {
+            PolicyStatus::Claimed
+        } else {
+            PolicyStatus::Monitoring
+        };
+
+        // Global counters move by the change in the committed value of the
+        // remaining coverage, so a policy releases exactly what it committed
+        let claimed_exposure = exposure_before - policy.committed_exposure()?;
+        let stranded_exposure = policy.committed_value(stranded_coverage)?;
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.settle_triggered_claim(policy.payment_mode, policy.committed_value(settled_claim)?)?;
+        global_state.release_coverage(policy.payment_mode, claimed_exposure)?;
+        global_state.release_coverage(policy.payment_mode, stranded_exposure)?;
+        let region_exposure = &mut ctx.accounts.region_exposure;
+        region_exposure.release(policy.payment_mode, claimed_exposure)?;
+        if policy.status == PolicyStatus::Claimed {
+            region_exposure.remove(policy.payment_mode, stranded_exposure)?;
+        }
+        global_state.record_payout(policy.payment_mode, transfer_amount)?;
+        ctx.accounts.epoch_stats.record_payout(ctx.bumps.epoch_stats, policy.payment_mode, transfer_amount)?;
+
+        // Trip the circuit breaker once today's payouts outrun today's
+        // premiums; this payout stands, later ones wait for resume_payouts
+        let max_loss_ratio_bps = ctx.accounts.global_state.max_loss_ratio_bps;
+        let loss_ratio_bps = ctx.accounts.epoch_stats.loss_ratio_bps(policy.payment_mode);
+        if max_loss_ratio_bps > 0 && loss_ratio_bps > max_loss_ratio_bps as u64 {
+            ctx.accounts.global_state.payouts_halted = true;
+            emit!(PayoutsHalted {
+                policy: policy.key(),
+                day: ctx.accounts.epoch_stats.day,
+                loss_ratio_bps,
+                max_loss_ratio_bps,
+            });
+            msg!(
+                "Payouts halted: loss ratio of {} bps exceeds {} bps",
+                loss_ratio_bps,
+                max_loss_ratio_bps
+            );
+        }
+
+        // Tip a registered keeper for cranking the payout; claimants are paid
+        // by the payout itself
+        if ctx.accounts.keeper.is_some() {
+            let cycle = policy.triggered_at;
+            let reward = pay_keeper_reward(
+                &mut policy.payout_reward_cycle,
+                cycle,
+                ctx.accounts.global_state.keeper_reward,
+                ctx.accounts.fee_vault.as_deref(),
+                ctx.accounts.keeper_token_account.as_deref(),
+                Some(&ctx.accounts.token_program),
+                ctx.accounts.global_state.fee_vault_bump,
+            )?;
+            if reward > 0 {
+                emit!(KeeperRewarded {
+                    keeper: ctx.accounts.executor.key(),
+                    policy: policy.key(),
+                    amount: reward,
+                    triggered_at: cycle,
+                });
+            }
+        }
+
+        emit!(PayoutExecuted {
+            policy: policy.key(),
+            beneficiary: recipient,
+            amount: transfer_amount,
+            payout_formula: policy.payout_calculation,
+            haircut_bps,
+        });
+
+        msg!(
+            "Climate payout executed: {} ({} of {} paid)",
+            payout_amount,
+            policy.paid_out_so_far,
+            policy.coverage_amount
+        );
+        Ok(())
+    }
+
+    /// File a claim against a triggered policy, asserting the insured's claim
+    /// before any payout is disbursed (owner, beneficiary or token holder only)
+    ///
+    /// The amount is clamped to the claim reserved at trigger time. Filing
+    /// again replaces the previous amount.
+    pub fn file_claim(ctx: Context<FileClaim>, _policy_id: u64, amount: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        require!(policy.status == PolicyStatus::Triggered, AmocaError::TriggerNotMet);
+        require!(
+            policy.is_claimant(ctx.accounts.claimant.key(), ctx.accounts.policy_token.as_ref())?,
+            AmocaError::Unauthorized
+        );
+
+        let claim_amount = amount.min(policy.pending_claim);
+        require!(claim_amount > 0, AmocaError::InvalidPayoutAmount);
+        policy.filed_claim = claim_amount;
+
+        emit!(ClaimFiled {
+            policy: policy.key(),
+            claimant: ctx.accounts.claimant.key(),
+            amount: claim_amount,
+            pending_claim: policy.pending_claim,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Claim filed for: {}", claim_amount);
+        Ok(())
+    }
+
+    /// Cancel a policy, refunding the unused share of the premium if active
+    pub fn cancel_policy(
+        ctx: Context<CancelPolicy>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
+
+        // Only policies that have not triggered or paid out can be cancelled
+        require!(
+            matches!(
+                policy.status,
+                PolicyStatus::Inactive
+                    | PolicyStatus::Active
+                    | PolicyStatus::Monitoring
+                    | PolicyStatus::Lapsed
+            ) && policy.paid_out_so_far == 0
+                && policy.haircut_shortfall == 0,
+            AmocaError::PolicyNotCancellable
+        );
+
+        // Unpaid policies hold no premium, so there is nothing to refund.
+        // The protocol fee is not refundable; only the pool's share is.
+        let refund_amount = if policy.status == PolicyStatus::Inactive {
+            0
+        } else {
+            prorata_amount(
+                policy.premium_paid_so_far
+                    .checked_sub(policy.protocol_fee_paid)
+                    .ok_or(AmocaError::MathOverflow)?,
+                policy.start_timestamp,
+                policy.end_timestamp,
+                current_time,
+            )?
+        };
+
+        if refund_amount > 0 {
+            let pool = PoolTransfer {
+                mode: policy.payment_mode,
+                user: Some(ctx.accounts.owner.to_account_info()),
+                user_token_account: ctx.accounts.owner_token_account.as_ref(),
+                risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+                risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+            };
+            pool.withdraw(refund_amount)?;
+
+            ctx.accounts.global_state.record_refund(policy.payment_mode, refund_amount)?;
+        }
+
+        if policy.status.is_in_force() {
+            ctx.accounts.global_state.release_coverage(policy.payment_mode, policy.committed_exposure()?)?;
+        }
+        // Regions count a policy from creation, paid or not
+        ctx.accounts.region_exposure.remove(policy.payment_mode, policy.committed_exposure()?)?;
+        policy.status = PolicyStatus::Expired;
+        ctx.accounts.policy_registry.remove(_policy_id);
+
+        emit!(PolicyCancelled {
+            policy: policy.key(),
+            owner: ctx.accounts.owner.key(),
+            refund_amount,
+        });
+
+        msg!("Policy cancelled, refunded: {}", refund_amount);
+        Ok(())
+    }
+
+    /// Move a lapsed policy to `Expired` (permissionless crank)
+    pub fn expire_policy(
+        ctx: Context<ExpirePolicy>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        require!(current_time > policy.end_timestamp, AmocaError::PolicyNotYetExpired);
+        require!(
+            !matches!(
+                policy.status,
+                PolicyStatus::Triggered | PolicyStatus::Claimed | PolicyStatus::Expired
+            ),
+            AmocaError::PolicyNotExpirable
+        );
+        // Paid policies that never received data are left for the holder's
+        // refund_on_no_data until the refund window closes
+        require!(
+            !policy.is_awaiting_no_data_refund(current_time),
+            AmocaError::NoDataRefundPending
+        );
+
+        if policy.status.is_in_force() {
+            ctx.accounts.global_state.release_coverage(policy.payment_mode, policy.committed_exposure()?)?;
+        }
+        // Regions count a policy from creation, paid or not
+        ctx.accounts.region_exposure.remove(policy.payment_mode, policy.committed_exposure()?)?;
+        policy.status = PolicyStatus::Expired;
+        ctx.accounts.policy_registry.remove(_policy_id);
+
+        emit!(PolicyLapsed {
+            policy: policy.key(),
+            owner: policy.owner,
+            end_timestamp: policy.end_timestamp,
+            timestamp: current_time,
+        });
+
+        msg!("Policy expired without a claim");
+        Ok(())
+    }
+
+    /// Expire a policy that never received qualifying oracle data, refunding
+    /// `no_data_refund_bps` of its premium (holder only)
+    ///
+    /// Only the pool's share of the premium is refundable, as on cancellation.
+    pub fn refund_on_no_data(
+        ctx: Context<RefundOnNoData>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
+
+        require!(current_time > policy.end_timestamp, AmocaError::PolicyNotYetExpired);
+        require!(
+            matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring)
+                && policy.paid_out_so_far == 0
+                && policy.haircut_shortfall == 0,
+            AmocaError::PolicyNotExpirable
+        );
+        require!(policy.qualifying_readings == 0, AmocaError::PolicyReceivedData);
+
+        let refund_amount = bps_of(
+            policy.premium_paid_so_far
+                .checked_sub(policy.protocol_fee_paid)
+                .ok_or(AmocaError::MathOverflow)?,
+            ctx.accounts.global_state.no_data_refund_bps,
+        )?;
+
+        if refund_amount > 0 {
+            let pool = PoolTransfer {
+                mode: policy.payment_mode,
+                user: Some(ctx.accounts.owner.to_account_info()),
+                user_token_account: ctx.accounts.owner_token_account.as_ref(),
+                risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+                risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+            };
+            pool.withdraw(refund_amount)?;
+
+            ctx.accounts.global_state.record_refund(policy.payment_mode, refund_amount)?;
+        }
+
+        ctx.accounts.global_state.release_coverage(policy.payment_mode, policy.committed_exposure()?)?;
+        ctx.accounts.region_exposure.remove(policy.payment_mode, policy.committed_exposure()?)?;
+        policy.status = PolicyStatus::Expired;
+        ctx.accounts.policy_registry.remove(_policy_id);
+
+        emit!(NoDataRefunded {
+            policy: policy.key(),
+            owner: ctx.accounts.owner.key(),
+            refund_amount,
+            end_timestamp: policy.end_timestamp,
+        });
+
+        msg!("Policy expired without oracle data, refunded: {}", refund_amount);
+        Ok(())
+    }
+
+    /// Close a policy whose premium was never paid by its deadline (permissionless crank)
+    ///
+    /// The policy and readings accounts are closed and their rent returned to the owner.
+    pub fn close_unpaid_policy(
+        ctx: Context<CloseUnpaidPolicy>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(policy.status == PolicyStatus::Inactive, AmocaError::PolicyAlreadyActive);
+        require!(
+            current_time > policy.premium_deadline,
+            AmocaError::PremiumDeadlineNotReached
+        );
+
+        ctx.accounts.policy_registry.remove(_policy_id);
+        ctx.accounts.region_exposure.remove(policy.payment_mode, policy.committed_exposure()?)?;
+
+        emit!(UnpaidPolicyClosed {
+            policy: policy.key(),
+            owner: policy.owner,
+            premium_deadline: policy.premium_deadline,
+            timestamp: current_time,
+        });
+
+        msg!("Unpaid policy closed, rent returned to owner");
+        Ok(())
+    }
+
+    /// Close a settled policy and reclaim its rent (holder only)
+    ///
+    /// Only `Claimed` or `Expired` policies with nothing still owed can be closed.
+    pub fn close_policy(
+        ctx: Context<ClosePolicy>,
+        _policy_id: u64,
+    ) -> Result<()> {
+        let policy = &ctx.accounts.policy;
+        policy.verify_holder(ctx.accounts.owner.key(), ctx.accounts.policy_token.as_ref())?;
+        require!(
+            matches!(policy.status, PolicyStatus::Claimed | PolicyStatus::Expired)
+                && policy.pending_claim == 0
+                && policy.haircut_shortfall == 0,
+            AmocaError::PolicyNotClosable
+        );
+
+        if let Some(registry) = ctx.accounts.policy_registry.as_mut() {
+            registry.remove(_policy_id);
+        }
+
+        emit!(PolicyClosed {
+            policy: policy.key(),
+            owner: policy.owner,
+            status: policy.status,
+        });
+
+        msg!("Policy closed, rent returned to owner");
+        Ok(())
+    }
+
+    /// Emit a policy's derived state for integrators (read-only)
+    pub fn get_policy_status(ctx: Context<GetPolicyStatus>, _policy_id: u64) -> Result<()> {
+        let policy = &ctx.accounts.policy;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        emit!(PolicyStatusReport {
+            policy: policy.key(),
+            status: policy.status,
+            premium_paid: policy.status != PolicyStatus::Inactive,
+            is_expired: current_time > policy.end_timestamp,
+            remaining_coverage: policy.remaining_coverage(),
+            seconds_until_expiry: policy.end_timestamp.saturating_sub(current_time).max(0),
+        });
+
+        Ok(())
+    }
+
+    /// Preview the payout an evaluation would reserve right now, without
+    /// touching state or funds (read-only)
+    ///
+    /// The policy is evaluated against its current readings and the formula
+    /// run on the resulting severity; the amount is emitted alongside its
+    /// inputs and returned for simulation.
+    pub fn dry_run_payout(ctx: Context<DryRunPayout>, _policy_id: u64) -> Result<u64> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut preview = ctx.accounts.policy.clone().into_inner();
+
+        let outcome = if preview.trigger_thresholds.is_in_season(current_time) {
+            let window = MeasurementWindow::trailing(current_time, preview.trigger_thresholds.measurement_period);
+            evaluate_trigger_conditions(
+                &mut preview,
+                &ctx.accounts.climate_readings,
+                ctx.accounts.global_state.reputation_floor,
+                window,
+            )?
+        } else {
+            TriggerOutcome { triggered: false, severity: SeverityBreakdown::default() }
+        };
+        let payout_amount = reserved_claim(
+            &preview,
+            &outcome.severity,
+            ctx.accounts.global_state.global_max_single_policy_payout,
+        )?;
+
+        emit!(PayoutPreviewed {
+            policy: ctx.accounts.policy.key(),
+            triggered: outcome.triggered,
+            payout_amount,
+            pending_claim: preview.pending_claim,
+            payout_formula: preview.payout_calculation,
+            risk_score: preview.risk_score,
+            severity: outcome.severity,
+            timestamp: current_time,
+        });
+
+        Ok(payout_amount)
+    }
+
+    /// Compute the policy's risk score from its current readings (read-only)
+    ///
+    /// Scores the readings exactly as `submit_climate_data` does and folds the
+    /// result into the stored score for the measurement period, so the number
+    /// doesn't depend on when data was last submitted.
+    pub fn compute_risk_score(ctx: Context<ComputeRiskScore>, _policy_id: u64) -> Result<u8> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut preview = ctx.accounts.policy.clone().into_inner();
+
+        let live_score = live_risk_score(
+            &preview,
+            &ctx.accounts.climate_readings,
+            ctx.accounts.global_state.reputation_floor,
+            current_time,
+        );
+        if let Some(score) = live_score {
+            preview.update_risk_score(score, current_time);
+        }
+
+        emit!(RiskScoreComputed {
+            policy: ctx.accounts.policy.key(),
+            risk_score: preview.risk_score,
+            live_score,
+            stored_risk_score: ctx.accounts.policy.risk_score,
+            timestamp: current_time,
+        });
+
+        Ok(preview.risk_score)
+    }
+
+    /// Backtest candidate trigger conditions against historical readings (read-only)
+    ///
+    /// The readings are replayed in time order into a scratch policy built from
+    /// `params`, evaluating once per distinct timestamp at most every monitoring
+    /// interval with the production trigger logic. Each trigger is treated as
+    /// paid in full at once, so later breaches must persist afresh. Returns the
+    /// total hypothetical payout.
+    pub fn simulate_triggers(
+        ctx: Context<SimulateTriggers>,
+        params: SimulationParams,
+        data_points: Vec<ClimateDataPoint>,
+    ) -> Result<u64> {
+        require!(params.coverage_amount > 0, AmocaError::InvalidCoverageAmount);
+        require!(params.geographic_bounds.is_valid(), AmocaError::InvalidGeographicBounds);
+        require!(
+            params.payout_formula != PayoutFormula::Exponential || params.decay_constant > 0,
+            AmocaError::InvalidDecayConstant
+        );
+        validate_trigger_conditions(params.payout_formula, &params.trigger_conditions)?;
+        let monitoring_frequency = params
+            .monitoring_frequency
+            .unwrap_or(DEFAULT_MONITORING_FREQUENCY_SECONDS);
+        require!(
+            is_valid_monitoring_frequency(monitoring_frequency),
+            AmocaError::InvalidMonitoringFrequency
+        );
+        require!(!data_points.is_empty(), AmocaError::InvalidOracleData);
+        require!(
+            data_points.iter().all(|data_point| data_point.unit == data_point.data_type.unit()),
+            AmocaError::InvalidMeasurementUnit
+        );
+
+        // Every oracle in the history is a source of the hypothetical policy
+        let mut oracle_sources: Vec<Pubkey> = Vec::new();
+        for data_point in &data_points {
+            if !oracle_sources.contains(&data_point.source_id) {
+                oracle_sources.push(data_point.source_id);
+            }
+        }
+        require!(oracle_sources.len() <= MAX_ORACLE_SOURCES, AmocaError::InvalidOracleSources);
+
+        let mut policy = ClimatePolicy {
+            policy_type: params.policy_type,
+            geographic_bounds: params.geographic_bounds,
+            trigger_thresholds: params.trigger_conditions,
+            oracle_sources,
+            min_oracle_consensus: params.min_oracle_consensus.unwrap_or(DEFAULT_MIN_ORACLE_CONSENSUS).max(1),
+            min_confidence: MIN_CONFIDENCE_LEVEL,
+            max_single_payout: params.coverage_amount,
+            monitoring_frequency,
+            payout_calculation: params.payout_formula,
+            decay_constant: params.decay_constant,
+            coverage_amount: params.coverage_amount,
+            status: PolicyStatus::Monitoring,
+            ..Default::default()
+        };
+        let mut climate_readings = ClimateReadings::default();
+        let reputation_floor = ctx.accounts.global_state.reputation_floor;
+
+        let mut data_points = data_points;
+        data_points.sort_by_key(|data_point| data_point.timestamp);
+        let mut evaluations: u32 = 0;
+        let mut triggers: u32 = 0;
+        let mut total_payout: u64 = 0;
+        let mut peak_severity_bps: u16 = 0;
+        let mut last_evaluation: Option<i64> = None;
+
+        let mut points = data_points.iter().peekable();
+        while let Some(data_point) = points.next() {
+            climate_readings.record(data_point.data_type, ClimateReading {
+                oracle: data_point.source_id,
+                location: data_point.location,
+                value: data_point.value,
+                timestamp: data_point.timestamp,
+                confidence_level: data_point.confidence_level,
+                reputation_score: 100,
+                oracle_type: OracleType::default(),
+            });
+
+            // Evaluate once every reading at this timestamp is in
+            let now = data_point.timestamp;
+            if points.peek().is_some_and(|next| next.timestamp == now)
+                || last_evaluation.is_some_and(|last| now - last < monitoring_frequency as i64)
+                || !policy.trigger_thresholds.is_in_season(now)
+            {
+                continue;
+            }
+            last_evaluation = Some(now);
+            evaluations += 1;
+
+            let window = MeasurementWindow::trailing(now, policy.trigger_thresholds.measurement_period);
+            let outcome = evaluate_trigger_conditions(&mut policy, &climate_readings, reputation_floor, window)?;
+            peak_severity_bps = peak_severity_bps.max(outcome.severity.peak_bps());
+            if !outcome.triggered {
+                continue;
+            }
+
+            let payout_amount = calculate_payout_amount(&policy, &outcome.severity)?
+                .min(policy.remaining_coverage());
+            triggers += 1;
+            total_payout = total_payout.checked_add(payout_amount).ok_or(AmocaError::MathOverflow)?;
+            policy.paid_out_so_far = total_payout;
+            policy.last_payout_at = Some(now);
+            policy.breach_started_at = None;
+            if policy.remaining_coverage() == 0 {
+                break;
+            }
+        }
+
+        emit!(TriggersSimulated {
+            policy_type: params.policy_type,
+            readings: data_points.len() as u32,
+            evaluations,
+            triggers,
+            total_payout,
+            peak_severity_bps,
+            first_timestamp: data_points[0].timestamp,
+            last_timestamp: data_points[data_points.len() - 1].timestamp,
+        });
+
+        Ok(total_payout)
+    }
+
+    /// Register an oracle provider (admin only)
+    pub fn register_oracle(
+        ctx: Context<RegisterOracle>,
+        params: OracleParams,
+    ) -> Result<()> {
+        require!(params.initial_reputation <= 100, AmocaError::InvalidReputationScore);
+        require!(
+            params.bond_amount >= ctx.accounts.global_state.min_oracle_bond,
+            AmocaError::InsufficientOracleBond
+        );
+        let supported_data_types = &params.supported_data_types;
+        require!(
+            !supported_data_types.is_empty()
+                && supported_data_types.len() <= CLIMATE_DATA_TYPE_COUNT
+                && supported_data_types.iter().enumerate().all(|(i, data_type)| !supported_data_types[..i].contains(data_type)),
+            AmocaError::InvalidSupportedDataTypes
+        );
+        require!(
+            params.min_submission_interval <= MAX_MONITORING_FREQUENCY_SECONDS,
+            AmocaError::InvalidSubmissionInterval
+        );
+
+        // Lock the provider's bond in its vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.provider_token_account.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, params.bond_amount)?;
+
+        let clock = Clock::get()?;
+        let oracle_bond = &mut ctx.accounts.oracle_bond;
+        oracle_bond.bump = ctx.bumps.oracle_bond;
+        oracle_bond.vault_bump = ctx.bumps.bond_vault;
+        oracle_bond.provider = ctx.accounts.provider.key();
+        oracle_bond.amount = params.bond_amount;
+        oracle_bond.bonded_at = clock.unix_timestamp;
+
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        oracle_data.bump = ctx.bumps.oracle_data;
+        oracle_data.provider = ctx.accounts.provider.key();
+        oracle_data.oracle_type = params.oracle_type;
+        oracle_data.supported_data_types = params.supported_data_types;
+        oracle_data.min_submission_interval = params.min_submission_interval;
+        oracle_data.reputation_score = params.initial_reputation;
+        oracle_data.last_update = clock.unix_timestamp;
+        oracle_data.reputation_decayed_at = clock.unix_timestamp;
+        oracle_data.is_active = true;
+        oracle_data.data_points_count = 0;
+        oracle_data.dispute_count = 0;
+        oracle_data.open_disputes = 0;
+
+        emit!(OracleRegistered {
+            provider: oracle_data.provider,
+            oracle_type: params.oracle_type,
+            reputation_score: params.initial_reputation,
+            bond_amount: params.bond_amount,
+        });
+
+        msg!("Oracle registered: {}", oracle_data.provider);
+        Ok(())
+    }
+
+    /// Decay the reputation of an oracle that has stopped reporting (permissionless crank)
+    ///
+    /// Each full `monitoring_frequency` interval of the given policy that passes
+    /// without a submission, beyond the first, costs `REPUTATION_DECAY_PER_INTERVAL`.
+    /// The policy must be in force and paid up, so a throwaway policy can't
+    /// set the pace, and the interval is never shorter than the oracle's own
+    /// `min_submission_interval`, which it couldn't report faster than.
+    pub fn decay_oracle_reputation(ctx: Context<DecayOracleReputation>) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        let interval = ctx.accounts.policy.monitoring_frequency
+            .max(oracle_data.min_submission_interval)
+            .max(1) as i64;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Intervals already charged are not charged again
+        let grace_end = oracle_data.last_update
+            .checked_add(interval)
+            .ok_or(AmocaError::MathOverflow)?;
+        let decay_from = grace_end.max(oracle_data.reputation_decayed_at);
+        let missed_intervals = current_time.saturating_sub(decay_from) / interval;
+        require!(missed_intervals > 0, AmocaError::OracleNotStale);
+
+        let penalty = u16::try_from(missed_intervals)
+            .unwrap_or(u16::MAX)
+            .saturating_mul(REPUTATION_DECAY_PER_INTERVAL);
+        oracle_data.reputation_score = oracle_data.reputation_score.saturating_sub(penalty);
+        oracle_data.reputation_decayed_at = decay_from + missed_intervals * interval;
+
+        if oracle_data.reputation_score < MIN_ORACLE_REPUTATION {
+            oracle_data.is_active = false;
+        }
+
+        emit!(OracleReputationDecayed {
+            provider: oracle_data.provider,
+            reputation_score: oracle_data.reputation_score,
+            is_active: oracle_data.is_active,
+        });
+
+        msg!(
+            "Oracle reputation decayed to {} after {} missed intervals",
+            oracle_data.reputation_score,
+            missed_intervals
+        );
+        Ok(())
+    }
+
+    /// Slash an oracle whose data was later proven wrong, recording the dispute
+    /// (admin only)
+    pub fn dispute_oracle_data(
+        ctx: Context<DisputeOracleData>,
+        params: DisputeParams,
+    ) -> Result<()> {
+        require!(
+            params.slash_amount > 0 || params.bond_slash_amount > 0,
+            AmocaError::InvalidSlashAmount
+        );
+
+        // Route the slashed part of the bond to the risk pool
+        let oracle_bond = &mut ctx.accounts.oracle_bond;
+        let bond_slashed = params.bond_slash_amount.min(oracle_bond.amount);
+        if bond_slashed > 0 {
+            let seeds = &[
+                b"oracle_bond".as_ref(),
+                oracle_bond.provider.as_ref(),
+                &[oracle_bond.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.risk_pool_token_account.to_account_info(),
+                authority: oracle_bond.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, bond_slashed)?;
+            oracle_bond.amount -= bond_slashed;
+        }
+
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        oracle_data.reputation_score = oracle_data.reputation_score.saturating_sub(params.slash_amount);
+        if oracle_data.reputation_score < MIN_ORACLE_REPUTATION {
+            oracle_data.is_active = false;
+        }
+        // Slashing resolves a raised dispute, if there is one
+        oracle_data.open_disputes = oracle_data.open_disputes.saturating_sub(1);
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.bump = ctx.bumps.dispute;
+        dispute.oracle = oracle_data.provider;
+        dispute.policy = params.policy;
+        dispute.authority = ctx.accounts.authority.key();
+        dispute.slash_amount = params.slash_amount;
+        dispute.bond_slashed = bond_slashed;
+        dispute.reputation_after = oracle_data.reputation_score;
+        dispute.evidence_hash = params.evidence_hash;
+        dispute.timestamp = Clock::get()?.unix_timestamp;
+
+        oracle_data.dispute_count = oracle_data.dispute_count
+            .checked_add(1)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        emit!(OracleDisputed {
+            provider: oracle_data.provider,
+            policy: params.policy,
+            slash_amount: params.slash_amount,
+            bond_slashed,
+            reputation_score: oracle_data.reputation_score,
+            is_active: oracle_data.is_active,
+        });
+
+        msg!("Oracle slashed by {} to {}", params.slash_amount, oracle_data.reputation_score);
+        Ok(())
+    }
+
+    /// Flag an oracle's data as under dispute, blocking it from unregistering (admin only)
+    pub fn raise_oracle_dispute(ctx: Context<OracleDisputeAction>) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        oracle_data.open_disputes = oracle_data.open_disputes
+            .checked_add(1)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        emit!(OracleDisputeRaised {
+            provider: oracle_data.provider,
+            open_disputes: oracle_data.open_disputes,
+        });
+
+        msg!("Dispute raised against oracle: {}", oracle_data.provider);
+        Ok(())
+    }
+
+    /// Close a raised dispute without slashing (admin only)
+    pub fn dismiss_oracle_dispute(ctx: Context<OracleDisputeAction>) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        require!(oracle_data.open_disputes > 0, AmocaError::NoOpenDispute);
+        oracle_data.open_disputes -= 1;
+
+        emit!(OracleDisputeDismissed {
+            provider: oracle_data.provider,
+            open_disputes: oracle_data.open_disputes,
+        });
+
+        msg!("Dispute dismissed for oracle: {}", oracle_data.provider);
+        Ok(())
+    }
+
+    /// Unregister an oracle and return its bond once the dispute window has passed
+    pub fn unregister_oracle(ctx: Context<UnregisterOracle>) -> Result<()> {
+        let oracle_data = &ctx.accounts.oracle_data;
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(oracle_data.open_disputes == 0, AmocaError::OracleDisputePending);
+        require!(
+            current_time >= oracle_data.last_update.saturating_add(ORACLE_UNBOND_COOLDOWN_SECONDS),
+            AmocaError::BondCooldownActive
+        );
+
+        let oracle_bond = &ctx.accounts.oracle_bond;
+        let seeds = &[
+            b"oracle_bond".as_ref(),
+            oracle_bond.provider.as_ref(),
+            &[oracle_bond.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let returned = ctx.accounts.bond_vault.amount;
+        if returned > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: oracle_bond.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, returned)?;
+        }
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.bond_vault.to_account_info(),
+            destination: ctx.accounts.provider.to_account_info(),
+            authority: oracle_bond.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::close_account(cpi_ctx)?;
+
+        emit!(OracleUnregistered {
+            provider: oracle_data.provider,
+            bond_returned: returned,
+        });
+
+        msg!("Oracle unregistered, bond returned: {}", returned);
+        Ok(())
+    }
+
+    /// Allow a keeper to execute payouts on any policy (admin only)
+    pub fn add_keeper(ctx: Context<AddKeeper>, keeper: Pubkey) -> Result<()> {
+        let keeper_account = &mut ctx.accounts.keeper_account;
+        keeper_account.bump = ctx.bumps.keeper_account;
+        keeper_account.keeper = keeper;
+        keeper_account.added_at = Clock::get()?.unix_timestamp;
+
+        emit!(KeeperAdded { keeper });
+
+        msg!("Keeper added: {}", keeper);
+        Ok(())
+    }
+
+    /// Revoke a keeper, returning its account's rent to the authority (admin only)
+    pub fn remove_keeper(ctx: Context<RemoveKeeper>) -> Result<()> {
+        let keeper = ctx.accounts.keeper_account.keeper;
+
+        emit!(KeeperRemoved { keeper });
+
+        msg!("Keeper removed: {}", keeper);
+        Ok(())
+    }
+
+    /// Deactivate a misbehaving oracle provider (admin only)
+    pub fn deactivate_oracle(ctx: Context<DeactivateOracle>) -> Result<()> {
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        oracle_data.is_active = false;
+
+        emit!(OracleDeactivated {
+            provider: oracle_data.provider,
+        });
+
+        msg!("Oracle deactivated: {}", oracle_data.provider);
+        Ok(())
+    }
+
+    /// Create the risk pool vault, the risk pool PDA's token account for the
+    /// premium mint (admin only)
+    pub fn init_risk_pool(ctx: Context<InitRiskPool>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.premium_mint = ctx.accounts.premium_mint.key();
+        global_state.risk_pool_vault = ctx.accounts.risk_pool_token_account.key();
+
+        emit!(RiskPoolInitialized {
+            premium_mint: global_state.premium_mint,
+            risk_pool_vault: global_state.risk_pool_vault,
+        });
+
+        msg!("Risk pool initialized: {}", global_state.risk_pool_vault);
+        Ok(())
+    }
+
+    /// Commit reinsurance capital to the risk pool
+    pub fn deposit_reinsurance(ctx: Context<DepositReinsurance>, amount: u64) -> Result<()> {
+        require!(amount > 0, AmocaError::InvalidReinsuranceAmount);
+
+        let pool = PoolTransfer {
+            mode: PaymentMode::SplToken,
+            user: Some(ctx.accounts.reinsurer.to_account_info()),
+            user_token_account: Some(&ctx.accounts.reinsurer_token_account),
+            risk_pool_token_account: Some(&ctx.accounts.risk_pool_token_account),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: ctx.accounts.global_state.risk_pool_bump,
+        };
+        pool.deposit(amount)?;
+
+        let position = &mut ctx.accounts.reinsurance_position;
+        if position.reinsurer == Pubkey::default() {
+            position.bump = ctx.bumps.reinsurance_position;
+            position.reinsurer = ctx.accounts.reinsurer.key();
+        }
+        position.amount = position.amount
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        position.last_deposit_at = Clock::get()?.unix_timestamp;
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_reinsurance = global_state.total_reinsurance
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        emit!(ReinsuranceDeposited {
+            reinsurer: position.reinsurer,
+            amount,
+            position_amount: position.amount,
+            total_reinsurance: global_state.total_reinsurance,
+        });
+
+        msg!("Reinsurance deposited: {}", amount);
+        Ok(())
+    }
+
+    /// Withdraw reinsurance capital not needed to keep the pool solvent
+    pub fn withdraw_reinsurance(ctx: Context<WithdrawReinsurance>, amount: u64) -> Result<()> {
+        require!(amount > 0, AmocaError::InvalidReinsuranceAmount);
+
+        let position = &mut ctx.accounts.reinsurance_position;
+        require!(amount <= position.amount, AmocaError::InsufficientReinsurance);
+
+        // Claims reserved by triggered policies can't be withdrawn, and
+        // what stays in the pool must still back all committed coverage
+        let global_state = &ctx.accounts.global_state;
+        require!(
+            amount <= global_state.unreserved_balance(
+                PaymentMode::SplToken,
+                ctx.accounts.risk_pool_token_account.amount,
+            ),
+            AmocaError::PoolFundsReserved
+        );
+        let pool_balance = ctx.accounts.risk_pool_token_account.amount
+            .checked_sub(amount)
+            .ok_or(AmocaError::PoolUndercollateralized)?;
+        global_state.check_solvency(global_state.total_coverage_committed, pool_balance)?;
+
+        let pool = PoolTransfer {
+            mode: PaymentMode::SplToken,
+            user: Some(ctx.accounts.reinsurer.to_account_info()),
+            user_token_account: Some(&ctx.accounts.reinsurer_token_account),
+            risk_pool_token_account: Some(&ctx.accounts.risk_pool_token_account),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: global_state.risk_pool_bump,
+        };
+        pool.withdraw(amount)?;
+
+        position.amount -= amount;
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_reinsurance = global_state.total_reinsurance
+            .checked_sub(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        emit!(ReinsuranceWithdrawn {
+            reinsurer: position.reinsurer,
+            amount,
+            position_amount: position.amount,
+            total_reinsurance: global_state.total_reinsurance,
+        });
+
+        msg!("Reinsurance withdrawn: {}", amount);
+        Ok(())
+    }
+
+    /// Set the reputation below which oracle readings are ignored (admin only)
+    pub fn set_reputation_floor(ctx: Context<AdminAction>, reputation_floor: u16) -> Result<()> {
+        require!(reputation_floor <= 100, AmocaError::InvalidReputationScore);
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.reputation_floor = reputation_floor;
+
+        msg!("Oracle reputation floor set to: {}", reputation_floor);
+        Ok(())
+    }
+
+    /// Set the longest coverage period a policy may run for (admin only)
+    pub fn set_max_policy_duration(
+        ctx: Context<AdminAction>,
+        max_policy_duration_seconds: i64,
+    ) -> Result<()> {
+        require!(max_policy_duration_seconds > 0, AmocaError::InvalidPolicyDuration);
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.max_policy_duration_seconds = max_policy_duration_seconds;
+
+        msg!("Maximum policy duration set to: {}s", max_policy_duration_seconds);
+        Ok(())
+    }
+
+    /// Set the largest coverage a single policy of a risk type may carry (admin only)
+    pub fn set_coverage_limit(
+        ctx: Context<AdminAction>,
+        policy_type: ClimateRiskType,
+        max_coverage: u64,
+    ) -> Result<()> {
+        require!(max_coverage > 0, AmocaError::InvalidCoverageAmount);
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.coverage_limits[policy_type.index()] = max_coverage;
+
+        emit!(CoverageLimitUpdated {
+            policy_type,
+            max_coverage,
+        });
+
+        msg!("Coverage limit for {:?} set to: {}", policy_type, max_coverage);
+        Ok(())
+    }
+
+    /// Set how long new policies of a risk type wait before they can pay out (admin only)
+    pub fn set_waiting_period(
+        ctx: Context<AdminAction>,
+        policy_type: ClimateRiskType,
+        waiting_period_seconds: i64,
+    ) -> Result<()> {
+        require!(waiting_period_seconds >= 0, AmocaError::InvalidPolicyDuration);
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.waiting_periods[policy_type.index()] = waiting_period_seconds;
+
+        emit!(WaitingPeriodUpdated {
+            policy_type,
+            waiting_period_seconds,
+        });
+
+        msg!("Waiting period for {:?} set to: {}s", policy_type, waiting_period_seconds);
+        Ok(())
+    }
+
+    /// Update any subset of the protocol parameters in one call (admin only)
+    pub fn set_config(ctx: Context<AdminAction>, params: ConfigParams) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+
+        if let Some(protocol_fee_bps) = params.protocol_fee_bps {
+            require!(
+                protocol_fee_bps as u128 <= BPS_DENOMINATOR,
+                AmocaError::InvalidProtocolFee
+            );
+            global_state.protocol_fee_bps = protocol_fee_bps;
+        }
+        if let Some(solvency_ratio_bps) = params.solvency_ratio_bps {
+            require!(solvency_ratio_bps > 0, AmocaError::InvalidSolvencyRatio);
+            global_state.solvency_ratio_bps = solvency_ratio_bps;
+        }
+        if let Some(reputation_floor) = params.reputation_floor {
+            require!(reputation_floor <= 100, AmocaError::InvalidReputationScore);
+            global_state.reputation_floor = reputation_floor;
+        }
+        if let Some(max_policy_duration_seconds) = params.max_policy_duration_seconds {
+            require!(max_policy_duration_seconds > 0, AmocaError::InvalidPolicyDuration);
+            global_state.max_policy_duration_seconds = max_policy_duration_seconds;
+        }
+        if let Some(min_oracle_bond) = params.min_oracle_bond {
+            require!(min_oracle_bond <= MAX_MIN_ORACLE_BOND, AmocaError::InvalidOracleBond);
+            global_state.min_oracle_bond = min_oracle_bond;
+        }
+        if let Some(max_region_exposure) = params.max_region_exposure {
+            require!(max_region_exposure > 0, AmocaError::InvalidCoverageAmount);
+            global_state.max_region_exposure = max_region_exposure;
+        }
+        if let Some(interval) = params.policy_creation_interval_seconds {
+            require!(interval >= 0, AmocaError::InvalidPolicyDuration);
+            global_state.policy_creation_interval_seconds = interval;
+        }
+        if let Some(no_data_refund_bps) = params.no_data_refund_bps {
+            require!(
+                no_data_refund_bps as u128 <= BPS_DENOMINATOR,
+                AmocaError::InvalidRefundFraction
+            );
+            global_state.no_data_refund_bps = no_data_refund_bps;
+        }
+        if let Some(smoothing_bps) = params.reputation_smoothing_bps {
+            require!(
+                smoothing_bps > 0 && smoothing_bps as u128 <= BPS_DENOMINATOR,
+                AmocaError::InvalidReputationSmoothing
+            );
+            global_state.reputation_smoothing_bps = smoothing_bps;
+        }
+        if let Some(min_premium_rate_bps) = params.min_premium_rate_bps {
+            require!(
+                min_premium_rate_bps as u128 <= BPS_DENOMINATOR,
+                AmocaError::InvalidPremiumRate
+            );
+            global_state.min_premium_rate_bps = min_premium_rate_bps;
+        }
+        if let Some(grace_period) = params.trigger_grace_period_seconds {
+            require!(grace_period > 0, AmocaError::InvalidTriggerGracePeriod);
+            global_state.trigger_grace_period_seconds = grace_period;
+        }
+        if let Some(max_stacked_coverage) = params.max_stacked_coverage {
+            require!(max_stacked_coverage > 0, AmocaError::InvalidCoverageAmount);
+            global_state.max_stacked_coverage = max_stacked_coverage;
+        }
+        if let Some(keeper_reward) = params.keeper_reward {
+            require!(keeper_reward <= MAX_KEEPER_REWARD, AmocaError::InvalidKeeperReward);
+            global_state.keeper_reward = keeper_reward;
+        }
+        if let Some(max_loss_ratio_bps) = params.max_loss_ratio_bps {
+            require!(max_loss_ratio_bps <= MAX_LOSS_RATIO_BPS, AmocaError::InvalidLossRatio);
+            global_state.max_loss_ratio_bps = max_loss_ratio_bps;
+        }
+        if let Some(payout_ceiling) = params.global_max_single_policy_payout {
+            require!(payout_ceiling > 0, AmocaError::InvalidCoverageAmount);
+            global_state.global_max_single_policy_payout = payout_ceiling;
+        }
+
+        emit!(ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            protocol_fee_bps: global_state.protocol_fee_bps,
+            solvency_ratio_bps: global_state.solvency_ratio_bps,
+            reputation_floor: global_state.reputation_floor,
+            max_policy_duration_seconds: global_state.max_policy_duration_seconds,
+            min_oracle_bond: global_state.min_oracle_bond,
+            max_region_exposure: global_state.max_region_exposure,
+            policy_creation_interval_seconds: global_state.policy_creation_interval_seconds,
+            no_data_refund_bps: global_state.no_data_refund_bps,
+            reputation_smoothing_bps: global_state.reputation_smoothing_bps,
+            min_premium_rate_bps: global_state.min_premium_rate_bps,
+            trigger_grace_period_seconds: global_state.trigger_grace_period_seconds,
+            max_stacked_coverage: global_state.max_stacked_coverage,
+            keeper_reward: global_state.keeper_reward,
+            max_loss_ratio_bps: global_state.max_loss_ratio_bps,
+            global_max_single_policy_payout: global_state.global_max_single_policy_payout,
+        });
+
+        msg!("Protocol configuration updated");
+        Ok(())
+    }
+
+    /// Create the protocol fee vault and set the fee taken from premiums (admin only)
+    pub fn initialize_fee_vault(
+        ctx: Context<InitializeFeeVault>,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            protocol_fee_bps as u128 <= BPS_DENOMINATOR,
+            AmocaError::InvalidProtocolFee
+        );
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.fee_vault_bump = ctx.bumps.fee_vault;
+        global_state.protocol_fee_bps = protocol_fee_bps;
+
+        msg!("Fee vault initialized, protocol fee: {} bps", protocol_fee_bps);
+        Ok(())
+    }
+
+    /// Withdraw accumulated protocol fees to a treasury account (admin only)
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, AmocaError::InvalidPayoutAmount);
+        require!(
+            amount <= ctx.accounts.fee_vault.amount,
+            AmocaError::InsufficientFees
+        );
+
+        let seeds = &[
+            b"fee_vault".as_ref(),
+            &[ctx.accounts.global_state.fee_vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.fee_vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(FeesWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            treasury: ctx.accounts.treasury_token_account.key(),
+            amount,
+        });
+
+        msg!("Protocol fees withdrawn: {}", amount);
+        Ok(())
+    }
+
+    /// Move risk pool funds to the authority during a wind-down (admin only, paused)
+    ///
+    /// Only possible once no coverage or triggered claim is outstanding in
+    /// either payment mode; reinsurers' token capital stays in the pool.
+    pub fn emergency_withdraw(
+        ctx: Context<EmergencyWithdraw>,
+        mode: PaymentMode,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, AmocaError::InvalidPayoutAmount);
+
+        let global_state = &ctx.accounts.global_state;
+        require!(
+            global_state.total_coverage_committed == 0
+                && global_state.sol_coverage_committed == 0
+                && global_state.triggered_claims == 0
+                && global_state.sol_triggered_claims == 0,
+            AmocaError::ObligationsOutstanding
+        );
+
+        let pool = PoolTransfer {
+            mode,
+            user: Some(ctx.accounts.authority.to_account_info()),
+            user_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            risk_pool_token_account: ctx.accounts.risk_pool_token_account.as_ref(),
+            risk_pool_pda: ctx.accounts.risk_pool_pda.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            risk_pool_bump: global_state.risk_pool_bump,
+        };
+        let withdrawable = match mode {
+            PaymentMode::SplToken => pool.balance()?.saturating_sub(global_state.total_reinsurance),
+            PaymentMode::NativeSol => pool.balance()?,
+        };
+        require!(amount <= withdrawable, AmocaError::PoolUndercollateralized);
+        pool.withdraw(amount)?;
+
+        let destination = match mode {
+            PaymentMode::SplToken => required(ctx.accounts.treasury_token_account.as_ref())?.key(),
+            PaymentMode::NativeSol => ctx.accounts.authority.key(),
+        };
+        emit!(EmergencyWithdrawal {
+            authority: ctx.accounts.authority.key(),
+            mode,
+            destination,
+            amount,
+        });
+
+        msg!("Emergency withdrawal of {} from the risk pool", amount);
+        Ok(())
+    }
+
+    /// Propose a new program authority; it takes effect once accepted (admin only)
+    pub fn propose_authority(ctx: Context<AdminAction>, new_authority: Pubkey) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.pending_authority = Some(new_authority);
+
+        emit!(AuthorityProposed {
+            authority: global_state.authority,
+            pending_authority: new_authority,
+        });
+
+        msg!("Authority transfer proposed to: {}", new_authority);
+        Ok(())
+    }
+
+    /// Require admin calls to arrive as a CPI from a multisig or governance
+    /// program, or clear the requirement with `None` (admin only)
+    ///
+    /// The new setting must already hold for this call, so an authority can't
+    /// lock itself out by naming a program it isn't routed through.
+    pub fn set_authority_program(
+        ctx: Context<AdminAction>,
+        authority_program: Option<Pubkey>,
+    ) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.authority_program = authority_program;
+        require!(
+            is_authorized_caller(global_state, ctx.accounts.instructions.as_ref()),
+            AmocaError::AuthorityCallerMismatch
+        );
+
+        emit!(AuthorityProgramUpdated {
+            authority: ctx.accounts.authority.key(),
+            authority_program,
+        });
+
+        msg!("Authority program updated");
+        Ok(())
+    }
+
+    /// Grow the global state to the current layout after a program upgrade and
+    /// default the fields it gained (admin only)
+    ///
+    /// Reads the raw account under the layout its version was written with,
+    /// which the current layout may not deserialize.
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        let account = ctx.accounts.global_state.to_account_info();
+        let mut global_state = GlobalState::from_legacy(&account.try_borrow_data()?)?;
+        require_keys_eq!(ctx.accounts.authority.key(), global_state.authority, AmocaError::Unauthorized);
+        require!(
+            is_authorized_caller(&global_state, ctx.accounts.instructions.as_ref()),
+            AmocaError::AuthorityCallerMismatch
+        );
+        require!(global_state.version < GLOBAL_STATE_VERSION, AmocaError::AccountAlreadyMigrated);
+
+        let from_version = global_state.version;
+        global_state.migrate();
+        grow_account(
+            &account,
+            8 + GlobalState::INIT_SPACE,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+        global_state.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: account.key(),
+            from_version,
+            to_version: GLOBAL_STATE_VERSION,
+        });
+
+        msg!("Global state migrated from version {} to {}", from_version, GLOBAL_STATE_VERSION);
+        Ok(())
+    }
+
+    /// Grow a policy to the current layout after a program upgrade and default
+    /// the fields it gained; the global state must be migrated first (admin only)
+    pub fn migrate_policy(
+        ctx: Context<MigratePolicy>,
+        policy_id: u64,
+        region: [u8; REGION_GEOHASH_PRECISION],
+    ) -> Result<()> {
+        let account = ctx.accounts.policy.to_account_info();
+        let mut policy = ClimatePolicy::from_legacy(&account.try_borrow_data()?)?;
+        require!(policy.version < CLIMATE_POLICY_VERSION, AmocaError::AccountAlreadyMigrated);
+        let policy_address = Pubkey::create_program_address(
+            &[b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes(), &[policy.bump]],
+            &crate::ID,
+        );
+        require!(policy_address == Ok(account.key()), AmocaError::PolicyIdMismatch);
+        require!(ctx.accounts.owner.key() == policy.owner, AmocaError::Unauthorized);
+        require!(region == region_geohash(&policy.geographic_bounds), AmocaError::RegionMismatch);
+
+        let from_version = policy.version;
+        // The baseline kept no registries or exposure counters, so its open
+        // policies join them here, as creation would have added them. The
+        // coverage is already written, so it isn't held to the solvency ratio
+        // or the region cap.
+        if from_version == 0 && !matches!(policy.status, PolicyStatus::Claimed | PolicyStatus::Expired) {
+            let registry = &mut ctx.accounts.policy_registry;
+            if registry.owner == Pubkey::default() {
+                registry.bump = ctx.bumps.policy_registry;
+                registry.owner = policy.owner;
+            }
+            require!(
+                registry.policy_ids.len() < MAX_POLICIES_PER_OWNER,
+                AmocaError::PolicyRegistryFull
+            );
+            require!(!registry.policy_ids.contains(&policy_id), AmocaError::PolicyIdTaken);
+            registry.policy_ids.push(policy_id);
+
+            let region_exposure = &mut ctx.accounts.region_exposure;
+            if region_exposure.geohash == [0; REGION_GEOHASH_PRECISION] {
+                region_exposure.bump = ctx.bumps.region_exposure;
+                region_exposure.geohash = region;
+            }
+            region_exposure.add(policy.payment_mode, policy.committed_exposure()?, u64::MAX)?;
+            if policy.status.is_in_force() {
+                ctx.accounts.global_state.adopt_coverage(policy.payment_mode, policy.committed_exposure()?)?;
+            }
+        }
+        policy.migrate();
+        grow_account(
+            &account,
+            8 + ClimatePolicy::INIT_SPACE,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+        policy.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: account.key(),
+            from_version,
+            to_version: CLIMATE_POLICY_VERSION,
+        });
+
+        msg!("Policy migrated from version {} to {}", from_version, CLIMATE_POLICY_VERSION);
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        let previous_authority = global_state.authority;
+        global_state.authority = ctx.accounts.pending_authority.key();
+        global_state.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: global_state.authority,
+        });
+
+        msg!("Authority transferred to: {}", global_state.authority);
+        Ok(())
+    }
+
+    /// Settle a policy as `Expired` or `Claimed` during an emergency wind-down
+    /// (admin only, while paused)
+    pub fn force_settle_policy(
+        ctx: Context<ForceSettlePolicy>,
+        _policy_id: u64,
+        status: PolicyStatus,
+    ) -> Result<()> {
+        require!(
+            matches!(status, PolicyStatus::Expired | PolicyStatus::Claimed),
+            AmocaError::InvalidSettlementStatus
+        );
+
+        let policy = &mut ctx.accounts.policy;
+        if policy.status.is_in_force() {
+            ctx.accounts.global_state.release_coverage(policy.payment_mode, policy.committed_exposure()?)?;
+        }
+        if !matches!(policy.status, PolicyStatus::Expired | PolicyStatus::Claimed) {
+            ctx.accounts.region_exposure.remove(policy.payment_mode, policy.committed_exposure()?)?;
+        }
+        let pending_claim = std::mem::take(&mut policy.pending_claim);
+        policy.filed_claim = 0;
+        ctx.accounts.global_state.settle_triggered_claim(policy.payment_mode, policy.committed_value(pending_claim)?)?;
+        let previous_status = policy.status;
+        policy.status = status;
+
+        emit!(PolicyForceSettled {
+            policy: policy.key(),
+            authority: ctx.accounts.authority.key(),
+            previous_status,
+            status,
+        });
+
+        msg!("Policy force settled by authority");
+        Ok(())
+    }
+
+    /// Freeze a single policy, blocking its trigger evaluation and payouts
+    /// while leaving cancellation, refunds and closing open (admin only)
+    pub fn freeze_policy(ctx: Context<FreezePolicy>, _policy_id: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        policy.is_frozen = true;
+        emit!(PolicyFreezeToggled {
+            policy: policy.key(),
+            authority: ctx.accounts.authority.key(),
+            is_frozen: true,
+        });
+        msg!("Policy {} frozen by authority", policy.key());
+        Ok(())
+    }
+
+    /// Lift a policy freeze (admin only)
+    pub fn unfreeze_policy(ctx: Context<FreezePolicy>, _policy_id: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        policy.is_frozen = false;
+        emit!(PolicyFreezeToggled {
+            policy: policy.key(),
+            authority: ctx.accounts.authority.key(),
+            is_frozen: false,
+        });
+        msg!("Policy {} unfrozen by authority", policy.key());
+        Ok(())
+    }
+
+    /// Pause the program (admin only)
+    pub fn pause_program(ctx: Context<AdminAction>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.is_paused = true;
+        emit!(ProgramPauseToggled {
+            authority: ctx.accounts.authority.key(),
+            is_paused: true,
+        });
+        msg!("Program paused by authority");
+        Ok(())
+    }
+
+    /// Unpause the program (admin only)
+    pub fn unpause_program(ctx: Context<AdminAction>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.is_paused = false;
+        emit!(ProgramPauseToggled {
+            authority: ctx.accounts.authority.key(),
+            is_paused: false,
+        });
+        msg!("Program unpaused by authority");
+        Ok(())
+    }
+
+    /// Clear a halt set by the loss-ratio circuit breaker (admin only)
+    pub fn resume_payouts(ctx: Context<AdminAction>) -> Result<()> {
+        let global_state = &mut ctx.accounts.global_state;
+        require!(global_state.payouts_halted, AmocaError::PayoutsNotHalted);
+        global_state.payouts_halted = false;
+        emit!(PayoutsResumed {
+            authority: ctx.accounts.authority.key(),
+        });
+        msg!("Payouts resumed by authority");
+        Ok(())
+    }
+}
+
+// Helper functions
+
+/// Whether an evaluation interval lies within the supported bounds
+fn is_valid_monitoring_frequency(monitoring_frequency: u32) -> bool {
+    (MIN_MONITORING_FREQUENCY_SECONDS..=MAX_MONITORING_FREQUENCY_SECONDS).contains(&monitoring_frequency)
+}
+
+/// Coarse geohash of a policy's centre, keying its `RegionExposure` cell
+///
+/// Uses the standard geohash bisection so clients can derive the cell with any
+/// geohash library.
+fn region_geohash(bounds: &GeoBounds) -> [u8; REGION_GEOHASH_PRECISION] {
+    let mut latitude_range: (f64, f64) = (-90.0, 90.0);
+    let mut longitude_range: (f64, f64) = (-180.0, 180.0);
+    let mut geohash = [0u8; REGION_GEOHASH_PRECISION];
+    let mut longitude_bit = true; // Bits alternate, starting with longitude
+
+    for character in geohash.iter_mut() {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (range, value) = if longitude_bit {
+                (&mut longitude_range, bounds.longitude)
+            } else {
+                (&mut latitude_range, bounds.latitude)
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if value > mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            longitude_bit = !longitude_bit;
+        }
+        *character = GEOHASH_ALPHABET[index];
+    }
+    geohash
+}
+
+/// Whether an admin call is routed through the configured authority program
+///
+/// A multisig or governance authority is a PDA that its program signs for with
+/// `invoke_signed`, so the call must be a CPI out of a top-level instruction
+/// belonging to that program. Without an authority program any caller passes.
+fn is_authorized_caller(global_state: &GlobalState, instructions: Option<&UncheckedAccount>) -> bool {
+    let Some(authority_program) = global_state.authority_program else {
+        return true;
+    };
+    let Some(instructions) = instructions else {
+        return false;
+    };
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return false;
+    }
+    load_current_index_checked(instructions)
+        .and_then(|index| load_instruction_at_checked(index as usize, instructions))
+        .is_ok_and(|instruction| instruction.program_id == authority_program)
+}
+
+/// Evaluate trigger conditions based on policy and persisted climate readings
+///
+/// Compares readings for each data type relevant to the policy's risk type
+/// against the populated thresholds, ignoring readings taken outside the
+/// policy's geographic bounds. A threshold only counts as breached when
+/// at least `min_oracle_consensus` of the policy's `oracle_sources` agree
+/// within `window`, normally the trailing measurement period. A breach only
+/// triggers once it has persisted for `minimum_duration` hours; the start of
+/// the current breach is tracked on the policy between evaluations.
+fn evaluate_trigger_conditions(
+    policy: &mut ClimatePolicy,
+    climate_readings: &ClimateReadings,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+) -> Result<TriggerOutcome> {
+    let checks = relevant_thresholds(policy.policy_type, &policy.trigger_thresholds);
+
+    let required_duration = policy.trigger_thresholds.minimum_duration as i64 * 3600;
+
+    // Consensus breach for each threshold, recording its severity. A threshold
+    // only fires once its readings have breached without interruption for
+    // `minimum_duration` hours.
+    let mut severity = SeverityBreakdown::default();
+    let mut breach_started_at: Option<i64> = None;
+    let mut persisted = Vec::with_capacity(checks.len());
+    for check in &checks {
+        let readings = climate_readings.readings_for(check.data_type);
+
+        // Once a run has crossed the trigger level it holds until readings
+        // recede past the reset level. Rolling totals have no per-reading
+        // runs; a breaching total's run spans the readings behind it.
+        let run_start = if check.data_type.is_cumulative() {
+            None
+        } else {
+            breach_run_start(check, readings, policy, reputation_floor, window)
+        };
+        let Some(breach) = consensus_breach(
+            check,
+            readings,
+            policy,
+            reputation_floor,
+            window,
+            run_start.is_some(),
+        ) else {
+            persisted.push(false);
+            continue;
+        };
+        severity.record(check.data_type, breach.severity_bps);
+        let run_start = run_start.or_else(|| total_run_start(readings, policy, reputation_floor, window));
+
+        if let Some(run_start) = run_start {
+            breach_started_at = Some(breach_started_at.map_or(run_start, |s| s.min(run_start)));
+        }
+        persisted.push(run_start.is_some_and(|start| breach.timestamp - start >= required_duration));
+    }
+
+    // Combine the individual checks per the policy's trigger logic
+    let conditions = &policy.trigger_thresholds;
+    let triggered = match conditions.trigger_logic {
+        TriggerLogic::AnyOf => persisted.iter().any(|&p| p),
+        TriggerLogic::AllOf => !persisted.is_empty() && persisted.iter().all(|&p| p),
+        TriggerLogic::WeightedThreshold { min_weight } => {
+            let weight: u16 = checks
+                .iter()
+                .zip(&persisted)
+                .filter(|(_, &p)| p)
+                .map(|(check, _)| conditions.composite_weights.weight_for(check.data_type) as u16)
+                .sum();
+            weight >= min_weight as u16
+        },
+        TriggerLogic::IndexBased => formula_claim(policy, &severity)? > 0,
+    };
+
+    policy.breach_started_at = breach_started_at;
+    Ok(TriggerOutcome { triggered, severity })
+}
+
+/// Lapse an active policy whose premium installments have fallen behind,
+/// returning whether it lapsed
+fn lapse_if_overdue(policy: &mut Account<ClimatePolicy>, current_time: i64) -> bool {
+    if !matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring)
+        || !policy.has_overdue_installment(current_time)
+    {
+        return false;
+    }
+    policy.status = PolicyStatus::Lapsed;
+
+    emit!(PremiumInstallmentMissed {
+        policy: policy.key(),
+        premium_due: policy.premium_due_by(current_time),
+        premium_paid: policy.premium_paid_so_far,
+        timestamp: current_time,
+    });
+    msg!("Policy {} lapsed on a missed premium installment", policy.key());
+    true
+}
+
+/// Evaluate a policy against its readings within `window` and record the
+/// outcome at `current_time`
+///
+/// Reserves the claim on a trigger; returns whether the policy triggered.
+fn apply_trigger_evaluation(
+    policy: &mut Account<ClimatePolicy>,
+    climate_readings: &ClimateReadings,
+    global_state: &mut GlobalState,
+    window: MeasurementWindow,
+    current_time: i64,
+) -> Result<bool> {
+    // Evaluate trigger conditions against the persisted readings; out of
+    // season the policy keeps monitoring without responding to breaches
+    let outcome = if policy.trigger_thresholds.is_in_season(window.end) {
+        evaluate_trigger_conditions(
+            policy,
+            climate_readings,
+            global_state.reputation_floor,
+            window,
+        )?
+    } else {
+        TriggerOutcome { triggered: false, severity: SeverityBreakdown::default() }
+    };
+    policy.last_severity = outcome.severity;
+    policy.index_value = outcome.severity.peak_bps();
+
+    // A lapsed trigger gives up its reserved claim before being judged afresh
+    if policy.status == PolicyStatus::Triggered {
+        let released_claim = std::mem::take(&mut policy.pending_claim);
+        policy.filed_claim = 0;
+        global_state.settle_triggered_claim(policy.payment_mode, policy.committed_value(released_claim)?)?;
+
+        emit!(TriggerLapsed {
+            policy: policy.key(),
+            released_claim,
+            triggered_at: policy.triggered_at,
+            timestamp: current_time,
+        });
+        msg!("Trigger lapsed for policy {} without a payout", policy.key());
+    }
+
+    if outcome.triggered {
+        // Reserve the claim so payouts can share any pool shortfall evenly
+        let claim = reserved_claim(
+            policy,
+            &policy.last_severity,
+            global_state.global_max_single_policy_payout,
+        )?;
+        policy.pending_claim = claim;
+        global_state.record_triggered_claim(policy.payment_mode, policy.committed_value(claim)?)?;
+
+        policy.status = PolicyStatus::Triggered;
+        policy.triggered_at = current_time;
+        msg!("Climate trigger conditions met for policy");
+    } else {
+        policy.status = PolicyStatus::Monitoring;
+    }
+
+    // Update last evaluation timestamp
+    policy.last_data_update = current_time;
+
+    emit!(TriggerEvaluated {
+        policy: policy.key(),
+        triggered: outcome.triggered,
+        status: policy.status,
+        severity_bps: outcome.severity.peak_bps(),
+        timestamp: current_time,
+    });
+
+    Ok(outcome.triggered)
+}
+
+/// Live risk score in `[0, 100]` from how close readings are to the thresholds
+///
+/// Each threshold's qualifying readings in the measurement period are scored
+/// by proximity and averaged, weighting recent readings more heavily; rolling
+/// totals are scored as a whole. The policy takes its most exposed threshold;
+/// `None` when nothing qualifies.
+fn live_risk_score(
+    policy: &ClimatePolicy,
+    climate_readings: &ClimateReadings,
+    reputation_floor: u16,
+    current_time: i64,
+) -> Option<u8> {
+    let window = MeasurementWindow::trailing(current_time, policy.trigger_thresholds.measurement_period);
+
+    relevant_thresholds(policy.policy_type, &policy.trigger_thresholds)
+        .iter()
+        .filter_map(|check| {
+            let readings = climate_readings.readings_for(check.data_type);
+            if check.data_type.is_cumulative() {
+                let totals = oracle_aggregates(check.data_type, readings, policy, reputation_floor, window);
+                let total = Fixed::weighted_mean(
+                    totals.iter().map(|(total, latest)| (*total, latest.reputation_score)),
+                )?;
+                return Some(check.proximity_score(total));
+            }
+
+            let (weighted, total_weight) = readings
+                .iter()
+                .filter(|r| is_qualifying_reading(r, policy, reputation_floor, window))
+                .fold((0u128, 0u128), |(weighted, total), r| {
+                    let weight = (r.timestamp - window.start).max(0) as u128 + 1;
+                    let score = check.proximity_score(Fixed::from_f64(r.value)) as u128;
+                    (weighted + score * weight, total + weight)
+                });
+            (total_weight > 0).then(|| ((weighted + total_weight / 2) / total_weight) as u8)
+        })
+        .max()
+}
+
+/// Whether any qualifying reading for the policy's thresholds is recent enough to act on
+///
+/// Readings older than two monitoring intervals are stale.
+fn has_fresh_reading(
+    policy: &ClimatePolicy,
+    climate_readings: &ClimateReadings,
+    reputation_floor: u16,
+    current_time: i64,
+) -> bool {
+    let recent = MeasurementWindow {
+        start: current_time.saturating_sub(policy.monitoring_frequency as i64 * 2),
+        end: current_time,
+    };
+    relevant_thresholds(policy.policy_type, &policy.trigger_thresholds)
+        .iter()
+        .any(|check| {
+            climate_readings
+                .readings_for(check.data_type)
+                .iter()
+                .any(|r| is_qualifying_reading(r, policy, reputation_floor, recent))
+        })
+}
+
+/// Whether a stored reading may count toward a policy's trigger evaluation
+fn is_qualifying_reading(
+    reading: &ClimateReading,
+    policy: &ClimatePolicy,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+) -> bool {
+    policy.oracle_sources.contains(&reading.oracle)
+        && reading.confidence_level >= policy.min_confidence
+        && reading.reputation_score >= reputation_floor
+        && window.contains(reading.timestamp)
+        && is_within_bounds(&policy.geographic_bounds, &reading.location)
+        && policy.admits_altitude(reading.location.altitude)
+}
+
+/// Timestamp of the first reading in the current uninterrupted breach
+///
+/// Any qualifying reading past the reset level, or a payout, ends a run, so
+/// a transient spike that recovers never accumulates duration while one that
+/// only dips into the hysteresis band keeps running.
+fn breach_run_start(
+    check: &ThresholdCheck,
+    readings: &[ClimateReading],
+    policy: &ClimatePolicy,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+) -> Option<i64> {
+    let qualifying = || {
+        readings
+            .iter()
+            .filter(|r| is_qualifying_reading(r, policy, reputation_floor, window))
+    };
+
+    let last_clear = qualifying()
+        .filter(|r| check.is_cleared(Fixed::from_f64(r.value)))
+        .map(|r| r.timestamp)
+        .chain(policy.last_payout_at)
+        .max();
+
+    qualifying()
+        .filter(|r| {
+            check.is_breached(Fixed::from_f64(r.value)) && r.timestamp > last_clear.unwrap_or(i64::MIN)
+        })
+        .map(|r| r.timestamp)
+        .min()
+}
+
+/// Start of a breaching rolling total's run: its earliest qualifying reading
+/// since the last payout
+fn total_run_start(
+    readings: &[ClimateReading],
+    policy: &ClimatePolicy,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+) -> Option<i64> {
+    readings
+        .iter()
+        .filter(|r| is_qualifying_reading(r, policy, reputation_floor, window))
+        .map(|r| r.timestamp)
+        .filter(|&timestamp| timestamp > policy.last_payout_at.unwrap_or(i64::MIN))
+        .min()
+}
+
+/// Each authorized oracle's aggregate of its qualifying readings inside the
+/// window, paired with its latest reading
+///
+/// Cumulative data types are totalled over the window. Otherwise readings more
+/// than `OUTLIER_STDDEV_LIMIT` standard deviations from the window's mean are
+/// discarded and each oracle contributes the median of the rest; totals skip
+/// that filter, since a storm day legitimately sits far from the mean.
+fn oracle_aggregates<'a>(
+    data_type: ClimateDataType,
+    readings: &'a [ClimateReading],
+    policy: &ClimatePolicy,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+) -> Vec<(Fixed, &'a ClimateReading)> {
+    let in_window: Vec<&ClimateReading> = readings
+        .iter()
+        .filter(|r| is_qualifying_reading(r, policy, reputation_floor, window))
+        .collect();
+    let values: Vec<Fixed> = in_window.iter().map(|r| Fixed::from_f64(r.value)).collect();
+    let inliers = if data_type.is_cumulative() {
+        vec![true; values.len()]
+    } else {
+        Fixed::within_deviations(&values, OUTLIER_STDDEV_LIMIT)
+    };
+
+    // Group the surviving values by oracle, tracking each oracle's latest reading
+    let mut by_oracle: Vec<(Vec<Fixed>, &ClimateReading)> = Vec::new();
+    for ((reading, value), _) in in_window.iter().zip(values).zip(inliers).filter(|(_, keep)| *keep) {
+        match by_oracle.iter_mut().find(|(_, latest)| latest.oracle == reading.oracle) {
+            Some((values, latest)) => {
+                values.push(value);
+                if reading.timestamp > latest.timestamp {
+                    *latest = reading;
+                }
+            }
+            None => by_oracle.push((vec![value], reading)),
+        }
+    }
+    by_oracle
+        .into_iter()
+        .filter_map(|(mut values, latest)| {
+            let aggregate = if data_type.is_cumulative() {
+                values.iter().fold(Fixed::ZERO, |total, &value| total + value)
+            } else {
+                Fixed::median(&mut values)?
+            };
+            Some((aggregate, latest))
+        })
+        .collect()
+}
+
+/// Check whether enough distinct oracles agree that a threshold is breached
+///
+/// Each authorized oracle at or above the reputation floor contributes its
+/// window aggregate (see `oracle_aggregates`), weighted by its latest
+/// reputation. At least `min_oracle_consensus` of those aggregates must
+/// breach, and so must their weighted average; the severity is taken from
+/// that average. While `held` by an ongoing run, readings inside the
+/// hysteresis band still count as breaching. Policies that require cross
+/// validation also need the breaching oracles to span two `OracleType`s.
+fn consensus_breach(
+    check: &ThresholdCheck,
+    readings: &[ClimateReading],
+    policy: &ClimatePolicy,
+    reputation_floor: u16,
+    window: MeasurementWindow,
+    held: bool,
+) -> Option<ThresholdBreach> {
+    let aggregates = oracle_aggregates(check.data_type, readings, policy, reputation_floor, window);
+
+    let breaching: Vec<&(Fixed, &ClimateReading)> = aggregates
+        .iter()
+        .filter(|(aggregate, _)| check.counts_as_breach(*aggregate, held))
+        .collect();
+
+    let required = policy.min_oracle_consensus.max(1) as usize;
+    if breaching.len() < required {
+        return None;
+    }
+    if policy.require_cross_validation
+        && breaching.iter().all(|(_, latest)| latest.oracle_type == breaching[0].1.oracle_type)
+    {
+        return None;
+    }
+
+    let weighted_value = Fixed::weighted_mean(
+        aggregates.iter().map(|(aggregate, latest)| (*aggregate, latest.reputation_score)),
+    )?;
+    if !check.counts_as_breach(weighted_value, held) {
+        return None;
+    }
+
+    Some(ThresholdBreach {
+        severity_bps: check.severity_bps(weighted_value),
+        timestamp: breaching.iter().map(|(_, latest)| latest.timestamp).max()?,
+    })
+}
+
+/// Require that enough of a policy's oracle sources declare each data type its thresholds need
+///
+/// `oracle_accounts` must be the sources' `OracleData`, in `sources` order.
+/// With `require_cross_validation`, each data type's capable sources must
+/// also span two `OracleType`s, or the policy could never trigger.
+fn verify_oracle_capabilities<'info>(
+    policy_type: ClimateRiskType,
+    conditions: &TriggerConditions,
+    sources: &[Pubkey],
+    min_oracle_consensus: u8,
+    require_cross_validation: bool,
+    oracle_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(oracle_accounts.len() == sources.len(), AmocaError::OracleCapabilityMismatch);
+    let mut oracles = Vec::with_capacity(sources.len());
+    for (source, account) in sources.iter().zip(oracle_accounts) {
+        let oracle_data = Account::<OracleData>::try_from(account)?;
+        require_keys_eq!(oracle_data.provider, *source, AmocaError::OracleCapabilityMismatch);
+        oracles.push(oracle_data);
+    }
+
+    for check in relevant_thresholds(policy_type, conditions) {
+        let capable: Vec<_> = oracles
+            .iter()
+            .filter(|oracle_data| oracle_data.supported_data_types.contains(&check.data_type))
+            .collect();
+        require_logged!(
+            capable.len() >= min_oracle_consensus as usize,
+            AmocaError::OracleCapabilityMismatch,
+            "Only {} of the policy's oracles report {:?}, {} required",
+            capable.len(),
+            check.data_type,
+            min_oracle_consensus
+        );
+        require_logged!(
+            !require_cross_validation
+                || capable.iter().any(|oracle_data| oracle_data.oracle_type != capable[0].oracle_type),
+            AmocaError::OracleCapabilityMismatch,
+            "Cross validation needs {:?} from two oracle types",
+            check.data_type
+        );
+    }
+    Ok(())
+}
+
+/// Committed value of the remaining coverage the owner holds across the
+/// registry policies `stacks` selects
+///
+/// `policy_accounts` must be the policies of `registry_ids`, in order, so
+/// none can be left out. Policies transferred in are seeded by their creator.
+fn stacked_coverage<'info>(
+    owner: Pubkey,
+    registry_ids: &[u64],
+    policy_accounts: &'info [AccountInfo<'info>],
+    stacks: impl Fn(&ClimatePolicy) -> bool,
+) -> Result<u64> {
+    require!(policy_accounts.len() == registry_ids.len(), AmocaError::InvalidRegistryPolicies);
+    let mut stacked: u64 = 0;
+    for (policy_id, account) in registry_ids.iter().zip(policy_accounts) {
+        let policy = Account::<ClimatePolicy>::try_from(account)?;
+        let expected = Pubkey::create_program_address(
+            &[b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes(), &[policy.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| AmocaError::InvalidRegistryPolicies)?;
+        require_keys_eq!(account.key(), expected, AmocaError::InvalidRegistryPolicies);
+        require_keys_eq!(policy.owner, owner, AmocaError::InvalidRegistryPolicies);
+
+        if stacks(&policy) {
+            stacked = stacked
+                .checked_add(policy.committed_exposure()?)
+                .ok_or(AmocaError::MathOverflow)?;
+        }
+    }
+    Ok(stacked)
+}
+
+/// Thresholds that apply to a policy's risk type, skipping unset ones
+fn relevant_thresholds(
+    policy_type: ClimateRiskType,
+    conditions: &TriggerConditions,
+) -> Vec<ThresholdCheck> {
+    use BreachDirection::*;
+    use ClimateDataType::*;
+
+    let candidates = match policy_type {
+        ClimateRiskType::DroughtProtection => vec![
+            (Rainfall, conditions.rainfall_threshold, Below),
+        ],
+        ClimateRiskType::FloodInsurance => vec![
+            (Rainfall, conditions.rainfall_threshold, Above),
+            (WaterLevel, conditions.water_level_threshold, Above),
+        ],
+        ClimateRiskType::HurricaneCoverage => vec![
+            (WindSpeed, conditions.wind_speed_threshold, Above),
+        ],
+        ClimateRiskType::AgriculturalClimate => vec![
+            (Rainfall, conditions.rainfall_threshold, Below),
+            (Temperature, conditions.temperature_threshold, Above),
+            (Temperature, conditions.cold_temperature_threshold, Below),
+            (WindSpeed, conditions.wind_speed_threshold, Above),
+        ],
+        ClimateRiskType::WildfireProtection => vec![
+            (FireDetection, conditions.fire_proximity_threshold, Below),
+        ],
+        ClimateRiskType::SeaLevelRise => vec![
+            (WaterLevel, conditions.water_level_threshold, Above),
+        ],
+        ClimateRiskType::ExtremeTemperature => vec![
+            (Temperature, conditions.temperature_threshold, Above),
+            (Temperature, conditions.cold_temperature_threshold, Below),
+        ],
+    };
+
+    candidates
+        .into_iter()
+        .filter_map(|(data_type, threshold, direction)| {
+            threshold.map(|threshold| {
+                ThresholdCheck::new(data_type, Fixed::from_f64(threshold), direction, conditions.reset_band_bps)
+            })
+        })
+        .collect()
+}
+
+/// Calculate payout amount based on parametric formula
+///
+/// `severity` records how far past its threshold each breaching reading was,
+/// as of the last trigger evaluation. Fractional payouts round to the nearest
+/// base unit, halves up, so small policies aren't shortchanged by truncation.
+fn calculate_payout_amount(policy: &ClimatePolicy, severity: &SeverityBreakdown) -> Result<u64> {
+    match policy.payout_calculation {
+        PayoutFormula::LinearScale => {
+            // Ramps from nothing at the threshold to full coverage at maximum severity
+            let payout = (policy.coverage_amount as u128)
+                .checked_mul(severity.peak_bps() as u128)
+                .and_then(|v| div_round_half_up(v, MAX_SEVERITY_BPS as u128))
+                .ok_or(AmocaError::MathOverflow)?;
+            Ok(payout.min(policy.coverage_amount as u128) as u64)
+        },
+        PayoutFormula::StepFunction => {
+            // Step function payout
+            if policy.risk_score > 90 {
+                Ok(policy.coverage_amount)
+            } else if policy.risk_score > 70 {
+                let half = div_round_half_up(policy.coverage_amount as u128, 2)
+                    .ok_or(AmocaError::MathOverflow)?;
+                Ok(half as u64)
+            } else {
+                Ok(0)
+            }
+        },
+        PayoutFormula::Exponential => {
+            // coverage * (1 - e^(-k * severity)), with severity normalised to [0, 1]
+            let peak_severity = severity.peak_bps() as u128;
+            let exponent = (policy.decay_constant as u128)
+                .checked_mul(peak_severity)
+                .and_then(|v| v.checked_mul(FIXED_POINT_ONE))
+                .and_then(|v| v.checked_div(DECAY_CONSTANT_SCALE * MAX_SEVERITY_BPS as u128))
+                .ok_or(AmocaError::MathOverflow)?;
+            let payout_fraction = FIXED_POINT_ONE
+                .checked_sub(exp_neg_fixed(exponent))
+                .ok_or(AmocaError::MathOverflow)?;
+
+            let payout = (policy.coverage_amount as u128)
+                .checked_mul(payout_fraction)
+                .and_then(|v| div_round_half_up(v, FIXED_POINT_ONE))
+                .ok_or(AmocaError::MathOverflow)?;
+            Ok(payout.min(policy.coverage_amount as u128) as u64)
+        },
+        PayoutFormula::Composite => {
+            // Weighted sum of the rainfall, temperature and wind breaches, kept
+            // in percent-bps until the final rounding
+            let weights = &policy.trigger_thresholds.composite_weights;
+            let weighted_severity = [
+                (weights.rainfall, severity.rainfall_bps),
+                (weights.temperature, severity.temperature_bps),
+                (weights.wind_speed, severity.wind_speed_bps),
+            ]
+            .iter()
+            .try_fold(0u128, |total, &(weight, bps)| {
+                (weight as u128)
+                    .checked_mul(bps as u128)
+                    .and_then(|v| total.checked_add(v))
+            })
+            .ok_or(AmocaError::MathOverflow)?;
+
+            let payout = (policy.coverage_amount as u128)
+                .checked_mul(weighted_severity)
+                .and_then(|v| div_round_half_up(v, 100 * MAX_SEVERITY_BPS as u128))
+                .ok_or(AmocaError::MathOverflow)?;
+            Ok(payout.min(policy.coverage_amount as u128) as u64)
+        },
+    }
+}
+
+/// The formula's payout for `severity`, bounded by `max_single_payout` and
+/// the coverage left
+///
+/// Index products pay as their index deepens, so earlier payouts (and shortfalls
+/// withheld from them) are deducted from what the formula gives for the
+/// current index; an index that hasn't deepened owes nothing.
+fn formula_claim(policy: &ClimatePolicy, severity: &SeverityBreakdown) -> Result<u64> {
+    let mut payout = calculate_payout_amount(policy, severity)?;
+    if policy.trigger_thresholds.trigger_logic == TriggerLogic::IndexBased {
+        payout = payout.saturating_sub(policy.coverage_amount - policy.remaining_coverage());
+    }
+    Ok(payout.min(policy.max_single_payout).min(policy.remaining_coverage()))
+}
+
+/// Claim a trigger reserves: the formula's claim, also bounded by what the
+/// policy may still draw under `payout_ceiling`, valued at its commitment rate
+fn reserved_claim(policy: &ClimatePolicy, severity: &SeverityBreakdown, payout_ceiling: u64) -> Result<u64> {
+    let allowance = payout_ceiling.saturating_sub(policy.transferred_so_far);
+    Ok(formula_claim(policy, severity)?.min(policy.coverage_value(allowance)))
+}
+
+/// `value / divisor`, rounded to nearest with halves rounding up
+fn div_round_half_up(value: u128, divisor: u128) -> Option<u128> {
+    value.checked_add(divisor / 2)?.checked_div(divisor)
+}
+
+/// Whether a coordinate lies within the coverage radius of a policy's bounds
+fn is_within_bounds(bounds: &GeoBounds, coord: &GeographicCoordinate) -> bool {
+    if !coord.latitude.is_finite() || !coord.longitude.is_finite() {
+        return false;
+    }
+    if coord.latitude.abs() > 90.0 || coord.longitude.abs() > 180.0 {
+        return false;
+    }
+
+    let distance = haversine_distance(
+        Fixed::from_f64(bounds.latitude),
+        Fixed::from_f64(bounds.longitude),
+        Fixed::from_f64(coord.latitude),
+        Fixed::from_f64(coord.longitude),
+    );
+    distance <= Fixed::from_f64(bounds.radius)
+}
+
+/// Seconds elapsed since 1 January 00:00 UTC of the year containing `timestamp`
+fn seconds_of_year(timestamp: i64) -> i64 {
+    let days = timestamp.div_euclid(86_400);
+    let year = civil_year_from_days(days);
+    timestamp - days_from_civil_year_start(year) * 86_400
+}
+
+/// Gregorian year containing a day count since 1970-01-01
+///
+/// Follows Howard Hinnant's `civil_from_days`, with years starting in March.
+fn civil_year_from_days(days: i64) -> i64 {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // 0 = March
+    let year = year_of_era + era * 400;
+    if month_index >= 10 { year + 1 } else { year }
+}
+
+/// Day count since 1970-01-01 of 1 January of `year`
+fn days_from_civil_year_start(year: i64) -> i64 {
+    // 1 January falls in the previous March-based year
+    let y = year - 1;
+    let era = y.div_euclid(400);
+    let year_of_era = y.rem_euclid(400);
+    let day_of_year = 306; // 1 January, counted from 1 March
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Installments must be positive with strictly later due dates, the first
+/// by the premium deadline and the last by the policy's end, and sum to the
+/// premium; an empty schedule pays the premium in one deposit
+fn validate_premium_schedule(
+    schedule: &[PremiumInstallment],
+    premium_amount: u64,
+    premium_deadline: i64,
+    end_timestamp: i64,
+) -> Result<()> {
+    let (Some(first), Some(last)) = (schedule.first(), schedule.last()) else {
+        return Ok(());
+    };
+    require!(
+        schedule.len() <= MAX_PREMIUM_INSTALLMENTS
+            && first.due_timestamp <= premium_deadline
+            && last.due_timestamp <= end_timestamp
+            && schedule.iter().all(|installment| installment.amount > 0)
+            && schedule.windows(2).all(|pair| pair[0].due_timestamp < pair[1].due_timestamp),
+        AmocaError::InvalidPremiumSchedule
+    );
+    let total = schedule
+        .iter()
+        .try_fold(0u64, |total, installment| total.checked_add(installment.amount))
+        .ok_or(AmocaError::MathOverflow)?;
+    require!(total == premium_amount, AmocaError::InvalidPremiumSchedule);
+    Ok(())
+}
+
+/// Validation shared by policy creation and trigger condition updates
+fn validate_trigger_conditions(
+    payout_formula: PayoutFormula,
+    conditions: &TriggerConditions,
+) -> Result<()> {
+    if let Some(window) = conditions.active_window {
+        require!(window.is_valid(), AmocaError::InvalidSeasonWindow);
+    }
+    require!(
+        conditions.reset_band_bps as u128 <= BPS_DENOMINATOR,
+        AmocaError::InvalidResetBand
+    );
+    // Heat and cold bounds must leave a normal band between them, and a
+    // reading can't breach both at once so AllOf could never trigger
+    if let (Some(hot), Some(cold)) = (conditions.temperature_threshold, conditions.cold_temperature_threshold) {
+        require!(
+            cold < hot && conditions.trigger_logic != TriggerLogic::AllOf,
+            AmocaError::InvalidTemperatureBounds
+        );
+    }
+    let weighted = payout_formula == PayoutFormula::Composite
+        || matches!(conditions.trigger_logic, TriggerLogic::WeightedThreshold { .. });
+    require!(
+        !weighted || conditions.composite_weights.total() == 100,
+        AmocaError::InvalidCompositeWeights
+    );
+    if let TriggerLogic::WeightedThreshold { min_weight } = conditions.trigger_logic {
+        require!(
+            (1..=100).contains(&min_weight),
+            AmocaError::InvalidTriggerLogic
+        );
+    }
+    Ok(())
+}
+
+/// Mean confidence of a non-empty batch, summed wide so full batches can't overflow
+fn average_confidence(data_points: &[ClimateDataPoint]) -> u8 {
+    let total: u32 = data_points.iter().map(|dp| dp.confidence_level as u32).sum();
+    (total / data_points.len().max(1) as u32) as u8
+}
+
+/// Exponential moving average of an oracle's reputation toward a batch's
+/// average confidence, weighting the batch by `smoothing_bps` and rounding to
+/// the nearest point in `[0, 100]`
+fn smoothed_reputation(reputation: u16, avg_confidence: u8, smoothing_bps: u16) -> Result<u16> {
+    let alpha = smoothing_bps as u128;
+    let keep = BPS_DENOMINATOR.checked_sub(alpha).ok_or(AmocaError::MathOverflow)?;
+    let weighted = (reputation.min(100) as u128)
+        .checked_mul(keep)
+        .and_then(|old| old.checked_add((avg_confidence.min(100) as u128).checked_mul(alpha)?))
+        .and_then(|sum| sum.checked_add(BPS_DENOMINATOR / 2))
+        .ok_or(AmocaError::MathOverflow)?;
+    Ok((weighted / BPS_DENOMINATOR).min(100) as u16)
+}
+
+/// Fail like Anchor's own deserialization unless `data` starts with `discriminator`
+fn check_discriminator(data: &[u8], discriminator: &[u8]) -> Result<()> {
+    require!(data.len() >= discriminator.len(), ErrorCode::AccountDiscriminatorNotFound);
+    require!(
+        data[..discriminator.len()] == *discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(())
+}
+
+/// Deserialize the next field of a legacy account layout
+fn read_legacy<T: AnchorDeserialize>(fields: &mut &[u8]) -> Result<T> {
+    T::deserialize(fields).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Zero-extend a program account to `new_len`, topping its rent up from `payer`
+///
+/// Accounts already at least `new_len` long are left as they are.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let top_up = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if top_up > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, top_up)?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Accounts that move premiums, payouts and refunds in and out of the risk
+/// pool, in either payment mode
+///
+/// SPL policies move tokens between `user_token_account` and the pool vault;
+/// native SOL policies move lamports between `user` and the risk pool PDA.
+struct PoolTransfer<'a, 'info> {
+    mode: PaymentMode,
+    user: Option<AccountInfo<'info>>,
+    user_token_account: Option<&'a Account<'info, TokenAccount>>,
+    risk_pool_token_account: Option<&'a Account<'info, TokenAccount>>,
+    risk_pool_pda: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    risk_pool_bump: u8,
+}
+
+impl<'info> PoolTransfer<'_, 'info> {
+    /// Take a premium from the user, routing the protocol fee on SPL premiums
+    /// to the fee vault. Returns the fee and the pool balance afterwards.
+    fn collect_premium(
+        &self,
+        fee_vault: Option<&Account<'info, TokenAccount>>,
+        amount: u64,
+        protocol_fee_bps: u16,
+    ) -> Result<(u64, u64)> {
+        let protocol_fee = match self.mode {
+            PaymentMode::SplToken => bps_of(amount, protocol_fee_bps)?,
+            PaymentMode::NativeSol => 0,
+        };
+
+        if protocol_fee > 0 {
+            let cpi_accounts = Transfer {
+                from: required(self.user_token_account)?.to_account_info(),
+                to: required(fee_vault)?.to_account_info(),
+                authority: required(self.user.clone())?,
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.clone(), cpi_accounts);
+            token::transfer(cpi_ctx, protocol_fee)?;
+        }
+
+        let pool_amount = amount
+            .checked_sub(protocol_fee)
+            .ok_or(AmocaError::MathOverflow)?;
+        let pool_balance = self.deposit(pool_amount)?;
+        Ok((protocol_fee, pool_balance))
+    }
+
+    /// Funds currently held by the pool for this payment mode
+    fn balance(&self) -> Result<u64> {
+        match self.mode {
+            PaymentMode::SplToken => Ok(required(self.risk_pool_token_account)?.amount),
+            PaymentMode::NativeSol => Ok(self.risk_pool_pda.lamports()),
+        }
+    }
+
+    /// Move `amount` from the user into the pool, returning the new pool balance
+    fn deposit(&self, amount: u64) -> Result<u64> {
+        let user = required(self.user.clone())?;
+        match self.mode {
+            PaymentMode::SplToken => {
+                let risk_pool_token_account = required(self.risk_pool_token_account)?;
+                let cpi_accounts = Transfer {
+                    from: required(self.user_token_account)?.to_account_info(),
+                    to: risk_pool_token_account.to_account_info(),
+                    authority: user,
+                };
+                let cpi_ctx = CpiContext::new(self.token_program.clone(), cpi_accounts);
+                token::transfer(cpi_ctx, amount)?;
+
+                risk_pool_token_account.amount
+                    .checked_add(amount)
+                    .ok_or(AmocaError::MathOverflow.into())
+            },
+            PaymentMode::NativeSol => {
+                let cpi_accounts = system_program::Transfer {
+                    from: user,
+                    to: self.risk_pool_pda.clone(),
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.clone(), cpi_accounts);
+                system_program::transfer(cpi_ctx, amount)?;
+
+                Ok(self.risk_pool_pda.lamports())
+            },
+        }
+    }
+
+    /// Move `amount` out of the pool to the user, signed by the risk pool PDA
+    fn withdraw(&self, amount: u64) -> Result<()> {
+        let seeds = &[
+            b"risk_pool".as_ref(),
+            &[self.risk_pool_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        match self.mode {
+            PaymentMode::SplToken => {
+                let cpi_accounts = Transfer {
+                    from: required(self.risk_pool_token_account)?.to_account_info(),
+                    to: required(self.user_token_account)?.to_account_info(),
+                    authority: self.risk_pool_pda.clone(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, amount)
+            },
+            PaymentMode::NativeSol => {
+                let cpi_accounts = system_program::Transfer {
+                    from: self.risk_pool_pda.clone(),
+                    to: required(self.user.clone())?,
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                system_program::transfer(cpi_ctx, amount)
+            },
+        }
+    }
+}
+
+/// Unwrap an optional account the policy's payment mode needs
+fn required<T>(account: Option<T>) -> Result<T> {
+    account.ok_or_else(|| error!(AmocaError::MissingPaymentAccount))
+}
+
+/// Pay `keeper_reward` from the fee vault into a keeper's token account,
+/// once per trigger cycle, returning the amount paid
+///
+/// `rewarded_cycle` is the `triggered_at` this crank was last rewarded for.
+/// Without the reward accounts or a configured reward nothing is paid, and a
+/// drained vault pays what it holds rather than failing the crank.
+fn pay_keeper_reward<'info>(
+    rewarded_cycle: &mut i64,
+    cycle: i64,
+    keeper_reward: u64,
+    fee_vault: Option<&Account<'info, TokenAccount>>,
+    keeper_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    fee_vault_bump: u8,
+) -> Result<u64> {
+    let (Some(fee_vault), Some(keeper_token_account), Some(token_program)) =
+        (fee_vault, keeper_token_account, token_program)
+    else {
+        return Ok(0);
+    };
+    if keeper_reward == 0 || *rewarded_cycle == cycle {
+        return Ok(0);
+    }
+    require_keys_eq!(keeper_token_account.mint, fee_vault.mint, AmocaError::InvalidTokenAccount);
+
+    let amount = keeper_reward.min(fee_vault.amount);
+    if amount > 0 {
+        let seeds = &[b"fee_vault".as_ref(), &[fee_vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: fee_vault.to_account_info(),
+            to: keeper_token_account.to_account_info(),
+            authority: fee_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+        *rewarded_cycle = cycle;
+    }
+    Ok(amount)
+}
+
+/// `amount * bps / 10_000`, rounded down
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(AmocaError::MathOverflow)?
+        / BPS_DENOMINATOR;
+    u64::try_from(share).map_err(|_| AmocaError::MathOverflow.into())
+}
+
+/// `amount * numerator / denominator`, rounded down
+fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let scaled = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(AmocaError::MathOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(AmocaError::MathOverflow)?;
+    u64::try_from(scaled).map_err(|_| AmocaError::MathOverflow.into())
+}
+
+/// `amount` reference units in premium mint base units at `rate` (scaled by
+/// `FX_RATE_SCALE`), rounded up
+fn convert_rounding_up(amount: u64, rate: u64) -> Result<u64> {
+    let converted = (amount as u128)
+        .checked_mul(rate as u128)
+        .ok_or(AmocaError::MathOverflow)?
+        .div_ceil(FX_RATE_SCALE);
+    u64::try_from(converted).map_err(|_| AmocaError::MathOverflow.into())
+}
+
+/// The policy's designated FX rate for `denomination`, published by
+/// `fx_oracle` within `FX_RATE_MAX_AGE_SECONDS` of `now`
+fn designated_fx_rate<'a>(
+    fx_rate: Option<&'a Account<'_, FxRate>>,
+    policy: Pubkey,
+    fx_oracle: Option<Pubkey>,
+    denomination: [u8; 3],
+    now: i64,
+) -> Result<&'a FxRate> {
+    let fx_rate = fx_rate.ok_or(AmocaError::InvalidFxRate)?;
+    require_logged!(
+        Some(fx_rate.provider) == fx_oracle && fx_rate.denomination == denomination,
+        AmocaError::InvalidFxRate,
+        "FX rate {} is not policy {}'s designated rate",
+        fx_rate.key(),
+        policy
+    );
+    require_logged!(
+        now.saturating_sub(fx_rate.updated_at) <= FX_RATE_MAX_AGE_SECONDS,
+        AmocaError::StaleFxRate,
+        "FX rate {} was last updated at {}",
+        fx_rate.key(),
+        fx_rate.updated_at
+    );
+    Ok(fx_rate)
+}
+
+/// Share of `amount` attributable to the time left between `now` and `end`
+fn prorata_amount(amount: u64, start: i64, end: i64, now: i64) -> Result<u64> {
+    if now >= end {
+        return Ok(0);
+    }
+    let total_duration = end.checked_sub(start).ok_or(AmocaError::MathOverflow)?;
+    if total_duration <= 0 {
+        return Ok(0);
+    }
+    let remaining = (end - now).min(total_duration) as u128;
+
+    let prorated = (amount as u128)
+        .checked_mul(remaining)
+        .ok_or(AmocaError::MathOverflow)?
+        / total_duration as u128;
+    Ok(prorated as u64)
+}
+
+// Account validation structs
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalState::INIT_SPACE,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: PolicyParams)]
+pub struct CreateClimatePolicy<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ClimatePolicy::INIT_SPACE,
+        seeds = [b"policy", owner.key().as_ref(), &params.policy_id.to_le_bytes()],
+        bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ClimateReadings::INIT_SPACE,
+        seeds = [b"readings", policy.key().as_ref()],
+        bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PolicyRegistry::INIT_SPACE,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump
+    )]
+    pub policy_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RegionExposure::INIT_SPACE,
+        seeds = [b"region", region_geohash(&params.geographic_bounds).as_ref()],
+        bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// FX rate of `params.fx_oracle` for the coverage denomination, required for denominated coverage
+    #[account(
+        seeds = [b"fx_rate", fx_rate.provider.as_ref(), fx_rate.denomination.as_ref()],
+        bump = fx_rate.bump
+    )]
+    pub fx_rate: Option<Box<Account<'info, FxRate>>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct DepositPremium<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        constraint = user_token_account.owner == owner.key() @ AmocaError::Unauthorized,
+        constraint = user_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA, authority of the pool vault and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    /// Required for SPL token policies when a protocol fee is set
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::current_day()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct RenewPolicy<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        constraint = user_token_account.owner == owner.key() @ AmocaError::Unauthorized,
+        constraint = user_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA, authority of the pool vault and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    /// Required for SPL token policies when a protocol fee is set
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::current_day()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct IncreaseCoverage<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    /// The policy owner's registry, listing the policies the stacked coverage check reads
+    #[account(
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Box<Account<'info, PolicyRegistry>>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        constraint = user_token_account.owner == owner.key() @ AmocaError::Unauthorized,
+        constraint = user_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.mint == global_state.premium_mint @ AmocaError::InvalidMint
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA, authority of the pool vault and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    /// Required for SPL token policies when a protocol fee is set
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::current_day()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct UpdateTriggerConditions<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct UpdateBeneficiary<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump,
+        constraint = policy.owner == owner.key() @ AmocaError::Unauthorized
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct OfferPolicyTransfer<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump,
+        constraint = policy.owner == owner.key() @ AmocaError::Unauthorized
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct AcceptPolicyTransfer<'info> {
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump,
+        constraint = policy.pending_owner == Some(new_owner.key()) @ AmocaError::Unauthorized
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = owner_registry.bump
+    )]
+    pub owner_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = 8 + PolicyRegistry::INIT_SPACE,
+        seeds = [b"registry", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct SetMonitoringFrequency<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct InitPolicyNft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump,
+        constraint = policy.owner == owner.key() @ AmocaError::Unauthorized
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"policy_mint", policy.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = policy
+    )]
+    pub policy_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = policy_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_policy_token: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitClimateData<'info> {
+    #[account(mut)]
+    pub oracle_provider: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_provider.key().as_ref()],
+        bump = oracle_data.bump,
+        constraint = oracle_data.provider == oracle_provider.key() @ AmocaError::Unauthorized
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(mut)]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        mut,
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(denomination: [u8; 3])]
+pub struct SubmitFxRate<'info> {
+    #[account(mut)]
+    pub oracle_provider: Signer<'info>,
+    
+    #[account(
+        seeds = [b"oracle", oracle_provider.key().as_ref()],
+        bump = oracle_data.bump,
+        constraint = oracle_data.provider == oracle_provider.key() @ AmocaError::Unauthorized
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        init_if_needed,
+        payer = oracle_provider,
+        space = 8 + FxRate::INIT_SPACE,
+        seeds = [b"fx_rate", oracle_provider.key().as_ref(), denomination.as_ref()],
+        bump
+    )]
+    pub fx_rate: Account<'info, FxRate>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct EvaluateClimateTrigger<'info> {
+    pub evaluator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Source of the keeper reward; omit to forgo it
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Evaluator's token account receiving the keeper reward
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == evaluator.key() @ AmocaError::InvalidTokenAccount
+    )]
+    pub keeper_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct BatchEvaluateTriggers<'info> {
+    pub evaluator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct FileClaim<'info> {
+    pub claimant: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Claimant's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct ExecuteClimatePayout<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + ClaimLog::INIT_SPACE,
+        seeds = [b"claims", policy.key().as_ref()],
+        bump
+    )]
+    pub claim_log: Box<Account<'info, ClaimLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// Executor's keeper registration; not needed when a claimant executes
+    #[account(
+        seeds = [b"keeper", executor.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+    
+    /// Holder's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies; must be owned by the payout recipient
+    #[account(mut)]
+    pub policyholder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Policy's designated FX rate, required for denominated coverage
+    #[account(
+        seeds = [b"fx_rate", fx_rate.provider.as_ref(), fx_rate.denomination.as_ref()],
+        bump = fx_rate.bump
+    )]
+    pub fx_rate: Option<Account<'info, FxRate>>,
+    
+    /// CHECK: Payout recipient's wallet, required for native SOL policies
+    #[account(mut)]
+    pub beneficiary: Option<UncheckedAccount<'info>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA signer and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + EpochStats::INIT_SPACE,
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::current_day()?.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Source of a registered keeper's reward; omit to forgo it
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Keeper's token account receiving the reward
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == executor.key() @ AmocaError::InvalidTokenAccount
+    )]
+    pub keeper_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct CancelPolicy<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ AmocaError::Unauthorized
+    )]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA signer and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct RefundOnNoData<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ AmocaError::Unauthorized
+    )]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token policies
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount,
+        constraint = risk_pool_token_account.owner == risk_pool_pda.key() @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Risk pool PDA signer and holder of SOL premiums
+    #[account(
+        mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct ExpirePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct CloseUnpaidPolicy<'info> {
+    pub closer: Signer<'info>,
+    
+    /// CHECK: Receives the reclaimed rent; must be the policy owner
+    #[account(mut, address = policy.owner @ AmocaError::Unauthorized)]
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Account<'info, PolicyRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Account<'info, RegionExposure>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct ClosePolicy<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    /// Caller's policy token account, required once the policy is tokenized
+    pub policy_token: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    /// Policy owner's registry, if one exists
+    #[account(
+        mut,
+        seeds = [b"registry", policy.owner.as_ref()],
+        bump = policy_registry.bump
+    )]
+    pub policy_registry: Option<Account<'info, PolicyRegistry>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct GetPolicyStatus<'info> {
+    #[account(
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct DryRunPayout<'info> {
+    #[account(
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct ComputeRiskScore<'info> {
+    #[account(
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
+    )]
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateTriggers<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterOracle<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Oracle provider being registered, co-signing to post its bond
+    pub provider: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleData::INIT_SPACE,
+        seeds = [b"oracle", provider.key().as_ref()],
+        bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleBond::INIT_SPACE,
+        seeds = [b"oracle_bond", provider.key().as_ref()],
+        bump
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"bond_vault", provider.key().as_ref()],
+        bump,
+        token::mint = premium_mint,
+        token::authority = oracle_bond
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key() @ AmocaError::Unauthorized
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    
+    #[account(address = global_state.premium_mint @ AmocaError::InvalidTokenAccount)]
+    pub premium_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnregisterOracle<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = provider,
+        seeds = [b"oracle", provider.key().as_ref()],
+        bump = oracle_data.bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        mut,
+        close = provider,
+        seeds = [b"oracle_bond", provider.key().as_ref()],
+        bump = oracle_bond.bump
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", provider.key().as_ref()],
+        bump = oracle_bond.vault_bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key() @ AmocaError::Unauthorized
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OracleDisputeAction<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_data.provider.as_ref()],
+        bump = oracle_data.bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct DecayOracleReputation<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_data.provider.as_ref()],
+        bump = oracle_data.bump,
+        constraint = oracle_data.is_active @ AmocaError::OracleNotAuthorized
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        constraint = policy.oracle_sources.contains(&oracle_data.provider) @ AmocaError::OracleNotAuthorized,
+        constraint = matches!(policy.status, PolicyStatus::Active | PolicyStatus::Monitoring)
+            && policy.premium_paid_so_far >= policy.premium_amount @ AmocaError::PolicyNotActive
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
 }
 
-/// Calculate payout amount based on parametric formula
-fn calculate_payout_amount(policy: &ClimatePolicy) -> Result<u64> {
-    match policy.payout_calculation {
-        PayoutFormula::LinearScale => {
-            // Linear payout based on risk score
-            let payout_percentage = if policy.risk_score > 80 {
-                std::cmp::min(100, policy.risk_score as u64)
-            } else {
-                0
-            };
-            Ok((policy.coverage_amount * payout_percentage) / 100)
-        },
-        PayoutFormula::StepFunction => {
-            // Step function payout
-            if policy.risk_score > 90 {
-                Ok(policy.coverage_amount)
-            } else if policy.risk_score > 70 {
-                Ok(policy.coverage_amount / 2)
-            } else {
-                Ok(0)
-            }
-        },
-        _ => Ok(0), // Other formulas not implemented
-    }
+#[derive(Accounts)]
+pub struct DepositReinsurance<'info> {
+    #[account(mut)]
+    pub reinsurer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = reinsurer,
+        space = 8 + ReinsurancePosition::INIT_SPACE,
+        seeds = [b"reinsurance", reinsurer.key().as_ref()],
+        bump
+    )]
+    pub reinsurance_position: Account<'info, ReinsurancePosition>,
+    
+    #[account(
+        mut,
+        constraint = reinsurer_token_account.owner == reinsurer.key() @ AmocaError::Unauthorized
+    )]
+    pub reinsurer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Risk pool PDA
+    #[account(
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-// Account validation structs
-
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+pub struct WithdrawReinsurance<'info> {
     #[account(mut)]
+    pub reinsurer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reinsurance", reinsurer.key().as_ref()],
+        bump = reinsurance_position.bump
+    )]
+    pub reinsurance_position: Account<'info, ReinsurancePosition>,
+    
+    #[account(
+        mut,
+        constraint = reinsurer_token_account.owner == reinsurer.key() @ AmocaError::Unauthorized
+    )]
+    pub reinsurer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Risk pool PDA signer
+    #[account(
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeOracleData<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_data.provider.as_ref()],
+        bump = oracle_data.bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleDispute::INIT_SPACE,
+        seeds = [
+            b"dispute",
+            oracle_data.provider.as_ref(),
+            &oracle_data.dispute_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, OracleDispute>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle_bond", oracle_data.provider.as_ref()],
+        bump = oracle_bond.bump
+    )]
+    pub oracle_bond: Account<'info, OracleBond>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", oracle_data.provider.as_ref()],
+        bump = oracle_bond.vault_bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(keeper: Pubkey)]
+pub struct AddKeeper<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Keeper::INIT_SPACE,
+        seeds = [b"keeper", keeper.as_ref()],
+        bump
+    )]
+    pub keeper_account: Account<'info, Keeper>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveKeeper<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"keeper", keeper_account.keeper.as_ref()],
+        bump = keeper_account.bump
+    )]
+    pub keeper_account: Account<'info, Keeper>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateOracle<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_data.provider.as_ref()],
+        bump = oracle_data.bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct InitRiskPool<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Risk pool PDA, authority of the pool vault
+    #[account(
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    pub premium_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = premium_mint,
+        associated_token::authority = risk_pool_pda
+    )]
+    pub risk_pool_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub fee_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"fee_vault"],
+        bump,
+        token::mint = fee_mint,
+        token::authority = fee_vault
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
     pub authority: Signer<'info>,
     
     #[account(
-        init,
-        payer = authority,
-        space = 8 + GlobalState::INIT_SPACE,
+        mut,
+        seeds = [b"fee_vault"],
+        bump = global_state.fee_vault_bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == fee_vault.mint @ AmocaError::InvalidTokenAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
         seeds = [b"global_state"],
-        bump
+        bump = global_state.bump
     )]
     pub global_state: Account<'info, GlobalState>,
     
-    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-#[instruction(params: PolicyParams)]
-pub struct CreateClimatePolicy<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
+pub struct EmergencyWithdraw<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
     
+    /// Authority's token account; required for SPL token withdrawals
     #[account(
-        init,
-        payer = owner,
-        space = 8 + ClimatePolicy::INIT_SPACE,
-        seeds = [b"policy", owner.key().as_ref(), &params.policy_id.to_le_bytes()],
-        bump
+        mut,
+        constraint = treasury_token_account.owner == authority.key() @ AmocaError::Unauthorized,
+        constraint = treasury_token_account.mint == global_state.premium_mint @ AmocaError::InvalidTokenAccount
     )]
-    pub policy: Account<'info, ClimatePolicy>,
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for SPL token withdrawals
+    #[account(
+        mut,
+        address = global_state.risk_pool_vault @ AmocaError::InvalidRiskPoolAccount
+    )]
+    pub risk_pool_token_account: Option<Account<'info, TokenAccount>>,
     
+    /// CHECK: Risk pool PDA signer and holder of SOL premiums
     #[account(
         mut,
+        seeds = [b"risk_pool"],
+        bump = global_state.risk_pool_bump
+    )]
+    pub risk_pool_pda: AccountInfo<'info>,
+    
+    #[account(
         seeds = [b"global_state"],
         bump = global_state.bump,
-        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+        constraint = global_state.is_paused @ AmocaError::ProgramNotPaused
     )]
     pub global_state: Account<'info, GlobalState>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(policy_id: u64)]
-pub struct DepositPremium<'info> {
+pub struct MigrateGlobalState<'info> {
     #[account(mut)]
-    pub owner: Signer<'info>,
-    
+    pub authority: Signer<'info>,
+
+    /// CHECK: May predate the current layout; checked and deserialized by the handler before resizing
     #[account(
         mut,
-        seeds = [b"policy", owner.key().as_ref(), &policy_id.to_le_bytes()],
-        bump = policy.bump,
-        constraint = policy.owner == owner.key() @ AmocaError::Unauthorized
+        seeds = [b"global_state"],
+        bump,
+        owner = crate::ID
     )]
-    pub policy: Account<'info, ClimatePolicy>,
-    
+    pub global_state: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed once an authority program is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64, region: [u8; REGION_GEOHASH_PRECISION])]
+pub struct MigratePolicy<'info> {
     #[account(
         mut,
-        constraint = user_token_account.owner == owner.key() @ AmocaError::Unauthorized
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized,
+        constraint = is_authorized_caller(&global_state, instructions.as_ref()) @ AmocaError::AuthorityCallerMismatch
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub risk_pool_token_account: Account<'info, TokenAccount>,
-    
+    pub authority: Signer<'info>,
+
     #[account(
         mut,
         seeds = [b"global_state"],
-        bump = global_state.bump,
-        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+        bump = global_state.bump
     )]
     pub global_state: Account<'info, GlobalState>,
-    
-    pub token_program: Program<'info, Token>,
+
+    /// CHECK: May predate the current layout; the handler checks its discriminator before resizing
+    #[account(mut, owner = crate::ID)]
+    pub policy: UncheckedAccount<'info>,
+
+    /// CHECK: The policy's owner, seeding its registry; the handler checks it against the policy
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PolicyRegistry::INIT_SPACE,
+        seeds = [b"registry", owner.key().as_ref()],
+        bump
+    )]
+    pub policy_registry: Box<Account<'info, PolicyRegistry>>,
+
+    /// The policy's region cell; the handler checks `region` against its bounds
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RegionExposure::INIT_SPACE,
+        seeds = [b"region", region.as_ref()],
+        bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
+
+    /// CHECK: Instructions sysvar, needed once an authority program is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitClimateData<'info> {
-    #[account(mut)]
-    pub oracle_provider: Signer<'info>,
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"oracle", oracle_provider.key().as_ref()],
-        bump = oracle_data.bump,
-        constraint = oracle_data.provider == oracle_provider.key() @ AmocaError::Unauthorized
-    )]
-    pub oracle_data: Account<'info, OracleData>,
-    
-    #[account(
         seeds = [b"global_state"],
         bump = global_state.bump,
-        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+        constraint = global_state.pending_authority == Some(pending_authority.key()) @ AmocaError::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
 }
 
 #[derive(Accounts)]
 #[instruction(policy_id: u64)]
-pub struct EvaluateClimateTrigger<'info> {
-    pub evaluator: Signer<'info>,
+pub struct ForceSettlePolicy<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"policy", policy.owner.as_ref(), &policy_id.to_le_bytes()],
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
         bump = policy.bump
     )]
     pub policy: Account<'info, ClimatePolicy>,
     
-    /// CHECK: Oracle data account for trigger evaluation
-    pub oracle_data: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"region", region_geohash(&policy.geographic_bounds).as_ref()],
+        bump = region_exposure.bump
+    )]
+    pub region_exposure: Box<Account<'info, RegionExposure>>,
     
     #[account(
+        mut,
         seeds = [b"global_state"],
         bump = global_state.bump,
-        constraint = !global_state.is_paused @ AmocaError::ProgramPaused
+        constraint = global_state.is_paused @ AmocaError::ProgramNotPaused
     )]
     pub global_state: Account<'info, GlobalState>,
 }
 
 #[derive(Accounts)]
 #[instruction(policy_id: u64)]
-pub struct ExecuteClimatePayout<'info> {
-    pub executor: Signer<'info>,
-    
+pub struct EvaluateHistoricalWindow<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized,
+        constraint = is_authorized_caller(&global_state, instructions.as_ref()) @ AmocaError::AuthorityCallerMismatch
+    )]
+    pub authority: Signer<'info>,
+
     #[account(
         mut,
-        seeds = [b"policy", policy.owner.as_ref(), &policy_id.to_le_bytes()],
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
         bump = policy.bump
     )]
     pub policy: Account<'info, ClimatePolicy>,
-    
-    #[account(mut)]
-    pub policyholder_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub risk_pool_token_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: Risk pool PDA signer
+
     #[account(
-        seeds = [b"risk_pool"],
-        bump = global_state.bump
+        seeds = [b"readings", policy.key().as_ref()],
+        bump = climate_readings.bump
     )]
-    pub risk_pool_pda: AccountInfo<'info>,
-    
+    pub climate_readings: Box<Account<'info, ClimateReadings>>,
+
     #[account(
         mut,
         seeds = [b"global_state"],
@@ -461,14 +5966,44 @@ pub struct ExecuteClimatePayout<'info> {
         constraint = !global_state.is_paused @ AmocaError::ProgramPaused
     )]
     pub global_state: Account<'info, GlobalState>,
-    
-    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Instructions sysvar, needed once an authority program is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(policy_id: u64)]
+pub struct FreezePolicy<'info> {
+    #[account(
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized,
+        constraint = is_authorized_caller(&global_state, instructions.as_ref()) @ AmocaError::AuthorityCallerMismatch
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"policy", policy.creator.as_ref(), &policy_id.to_le_bytes()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, ClimatePolicy>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Instructions sysvar, needed once an authority program is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct AdminAction<'info> {
     #[account(
-        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized
+        constraint = authority.key() == global_state.authority @ AmocaError::Unauthorized,
+        constraint = is_authorized_caller(&global_state, instructions.as_ref()) @ AmocaError::AuthorityCallerMismatch
     )]
     pub authority: Signer<'info>,
     
@@ -478,40 +6013,919 @@ pub struct AdminAction<'info> {
         bump = global_state.bump
     )]
     pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: Instructions sysvar, needed once an authority program is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+}
+
+// Data structures
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct GlobalState {
+    pub bump: u8,
+    pub risk_pool_bump: u8, // Bump of the risk pool PDA that signs pool transfers
+    pub premium_mint: Pubkey, // Token premiums and payouts are denominated in
+    pub risk_pool_vault: Pubkey, // Risk pool PDA's associated token account
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>, // Set by propose_authority until accepted
+    pub total_policies: u64,
+    pub total_premiums_collected: u64,
+    pub total_payouts: u64,
+    pub total_coverage_committed: u64, // Coverage of policies currently in force
+    pub solvency_ratio_bps: u16, // Minimum pool balance as a share of committed coverage
+    pub reputation_floor: u16, // Readings from oracles below this reputation are discarded
+    pub protocol_fee_bps: u16, // Share of each premium routed to the fee vault
+    pub total_fees_collected: u64,
+    pub fee_vault_bump: u8,
+    pub sol_coverage_committed: u64, // Native SOL counterparts of the token totals, in lamports
+    pub sol_premiums_collected: u64,
+    pub sol_payouts: u64,
+    pub max_policy_duration_seconds: i64, // Upper bound on end_timestamp - now at creation and renewal
+    pub total_reinsurance: u64, // External capital backing the token pool, tracked per ReinsurancePosition
+    pub triggered_claims: u64, // Claims reserved by triggered policies awaiting payout
+    pub sol_triggered_claims: u64,
+    pub min_oracle_bond: u64, // Bond each oracle must post at registration, in the premium mint
+    pub coverage_limits: [u64; CLIMATE_RISK_TYPE_COUNT], // Max coverage per policy, indexed by ClimateRiskType
+    pub waiting_periods: [i64; CLIMATE_RISK_TYPE_COUNT], // Seconds new policies wait before paying out, indexed by ClimateRiskType
+    pub authority_program: Option<Pubkey>, // Multisig or governance program AdminAction calls must be routed through
+    pub max_region_exposure: u64, // Cap on coverage written per RegionExposure cell, in each payment mode's units
+    pub policy_creation_interval_seconds: i64, // Minimum spacing between one owner's policy creations; 0 disables
+    pub no_data_refund_bps: u16, // Share of the pool's premium refunded when a term saw no qualifying readings
+    pub reputation_smoothing_bps: u16, // Weight of each batch's average confidence in oracle reputation
+    pub min_premium_rate_bps: u16, // Floor on premium / coverage at creation; 0 disables
+    pub trigger_grace_period_seconds: i64, // How long a trigger awaits its payout before lapsing back to Monitoring
+    pub is_paused: bool,
+    pub version: u8, // Schema generation; fields added later go after it, read by migrate_global_state only as far as the version wrote them
+    pub max_stacked_coverage: u64, // Cap on one owner's overlapping coverage of a peril; u64::MAX disables the check (version 2)
+    pub keeper_reward: u64, // Fee vault tip per rewarded trigger or payout crank; 0 disables (version 3)
+    pub max_loss_ratio_bps: u32, // Daily payouts / premiums above which payouts halt; 0 disables (version 4)
+    pub payouts_halted: bool, // Set by the loss-ratio circuit breaker, cleared by resume_payouts (version 4)
+    pub global_max_single_policy_payout: u64, // Ceiling on any one policy's lifetime payouts; u64::MAX disables (version 5)
+}
+
+impl GlobalState {
+    /// Upgrade an account written under an older `version`, defaulting the
+    /// fields each later generation appended
+    pub fn migrate(&mut self) {
+        // Version 1 only introduced `version` itself
+        if self.version < 2 {
+            self.max_stacked_coverage = u64::MAX;
+        }
+        if self.version < 3 {
+            self.keeper_reward = 0;
+        }
+        if self.version < 4 {
+            self.max_loss_ratio_bps = 0;
+            self.payouts_halted = false;
+        }
+        if self.version < 5 {
+            self.global_max_single_policy_payout = u64::MAX;
+        }
+        self.version = GLOBAL_STATE_VERSION;
+    }
+
+    /// Read an account written under any earlier layout: the unversioned
+    /// baseline, recognised by its size, or a versioned one whose appended
+    /// fields are read only as far as its `version` wrote them. Bytes past
+    /// those are slack that may hold stale data, so they are never read.
+    pub fn from_legacy(data: &[u8]) -> Result<Self> {
+        check_discriminator(data, GlobalState::DISCRIMINATOR)?;
+        let mut fields = &data[GlobalState::DISCRIMINATOR.len()..];
+        if data.len() == 8 + GlobalStateV0::INIT_SPACE {
+            return Ok(read_legacy::<GlobalStateV0>(&mut fields)?.into());
+        }
+
+        let mut global_state: GlobalState = read_legacy::<GlobalStateV1>(&mut fields)?.into();
+        if global_state.version >= 2 {
+            global_state.max_stacked_coverage = read_legacy(&mut fields)?;
+        }
+        if global_state.version >= 3 {
+            global_state.keeper_reward = read_legacy(&mut fields)?;
+        }
+        if global_state.version >= 4 {
+            global_state.max_loss_ratio_bps = read_legacy(&mut fields)?;
+            global_state.payouts_halted = read_legacy(&mut fields)?;
+        }
+        if global_state.version >= 5 {
+            global_state.global_max_single_policy_payout = read_legacy(&mut fields)?;
+        }
+        Ok(global_state)
+    }
+
+    /// Largest coverage a single policy of `policy_type` may carry
+    pub fn coverage_limit(&self, policy_type: ClimateRiskType) -> u64 {
+        self.coverage_limits[policy_type.index()]
+    }
+
+    /// Whether `premium_amount` meets the minimum rate for `coverage_amount`
+    pub fn is_adequate_premium(&self, premium_amount: u64, coverage_amount: u64) -> bool {
+        premium_amount as u128 * BPS_DENOMINATOR
+            >= coverage_amount as u128 * self.min_premium_rate_bps as u128
+    }
+
+    /// Waiting period a new policy of `policy_type` starts with
+    pub fn waiting_period(&self, policy_type: ClimateRiskType) -> i64 {
+        self.waiting_periods[policy_type.index()]
+    }
+
+    /// Commit coverage for a newly active policy, enforcing the solvency ratio
+    /// against the pool balance of the policy's payment mode
+    pub fn commit_coverage(
+        &mut self,
+        mode: PaymentMode,
+        coverage_amount: u64,
+        pool_balance: u64,
+    ) -> Result<()> {
+        let committed_total = match mode {
+            PaymentMode::SplToken => self.total_coverage_committed,
+            PaymentMode::NativeSol => self.sol_coverage_committed,
+        };
+        let committed = committed_total
+            .checked_add(coverage_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+
+        self.check_solvency(committed, pool_balance)?;
+
+        match mode {
+            PaymentMode::SplToken => self.total_coverage_committed = committed,
+            PaymentMode::NativeSol => self.sol_coverage_committed = committed,
+        }
+        Ok(())
+    }
+
+    /// Count coverage written before the counters tracked it, which unlike new
+    /// coverage isn't held to the solvency ratio
+    pub fn adopt_coverage(&mut self, mode: PaymentMode, coverage_amount: u64) -> Result<()> {
+        let committed_total = match mode {
+            PaymentMode::SplToken => &mut self.total_coverage_committed,
+            PaymentMode::NativeSol => &mut self.sol_coverage_committed,
+        };
+        *committed_total = committed_total
+            .checked_add(coverage_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Fail unless `pool_balance` meets the solvency ratio for `committed` coverage
+    pub fn check_solvency(&self, committed: u64, pool_balance: u64) -> Result<()> {
+        let required_capital = committed as u128 * self.solvency_ratio_bps as u128;
+        require!(
+            pool_balance as u128 * BPS_DENOMINATOR >= required_capital,
+            AmocaError::PoolUndercollateralized
+        );
+        Ok(())
+    }
+
+    /// Record a collected premium and the protocol fee taken from it
+    pub fn record_premium(&mut self, mode: PaymentMode, amount: u64, protocol_fee: u64) -> Result<()> {
+        let premiums_collected = match mode {
+            PaymentMode::SplToken => &mut self.total_premiums_collected,
+            PaymentMode::NativeSol => &mut self.sol_premiums_collected,
+        };
+        *premiums_collected = premiums_collected
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a premium refunded on cancellation
+    pub fn record_refund(&mut self, mode: PaymentMode, amount: u64) -> Result<()> {
+        let premiums_collected = match mode {
+            PaymentMode::SplToken => &mut self.total_premiums_collected,
+            PaymentMode::NativeSol => &mut self.sol_premiums_collected,
+        };
+        *premiums_collected = premiums_collected
+            .checked_sub(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Reserve a claim for a policy that has just triggered
+    pub fn record_triggered_claim(&mut self, mode: PaymentMode, amount: u64) -> Result<()> {
+        let claims = match mode {
+            PaymentMode::SplToken => &mut self.triggered_claims,
+            PaymentMode::NativeSol => &mut self.sol_triggered_claims,
+        };
+        *claims = claims
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Drop a reserved claim once its policy is paid or settled
+    pub fn settle_triggered_claim(&mut self, mode: PaymentMode, amount: u64) -> Result<()> {
+        let claims = match mode {
+            PaymentMode::SplToken => &mut self.triggered_claims,
+            PaymentMode::NativeSol => &mut self.sol_triggered_claims,
+        };
+        *claims = claims
+            .checked_sub(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Claims reserved by triggered policies awaiting payout in `mode`
+    pub fn reserved_claims(&self, mode: PaymentMode) -> u64 {
+        match mode {
+            PaymentMode::SplToken => self.triggered_claims,
+            PaymentMode::NativeSol => self.sol_triggered_claims,
+        }
+    }
+
+    /// Pool balance not earmarked for triggered claims
+    pub fn unreserved_balance(&self, mode: PaymentMode, pool_balance: u64) -> u64 {
+        pool_balance.saturating_sub(self.reserved_claims(mode))
+    }
+
+    /// Share of each claim to withhold when `pool_balance` can't cover all
+    /// outstanding triggered claims; 0 when the pool is sufficient
+    pub fn haircut_bps(&self, mode: PaymentMode, pool_balance: u64) -> u16 {
+        let claims = self.reserved_claims(mode);
+        if claims <= pool_balance {
+            return 0;
+        }
+        let covered_bps = pool_balance as u128 * BPS_DENOMINATOR / claims as u128;
+        (BPS_DENOMINATOR - covered_bps) as u16
+    }
+
+    /// Record a payout made from the pool
+    pub fn record_payout(&mut self, mode: PaymentMode, amount: u64) -> Result<()> {
+        let payouts = match mode {
+            PaymentMode::SplToken => &mut self.total_payouts,
+            PaymentMode::NativeSol => &mut self.sol_payouts,
+        };
+        *payouts = payouts
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Release coverage once a policy is no longer in force
+    pub fn release_coverage(&mut self, mode: PaymentMode, coverage_amount: u64) -> Result<()> {
+        let committed_total = match mode {
+            PaymentMode::SplToken => &mut self.total_coverage_committed,
+            PaymentMode::NativeSol => &mut self.sol_coverage_committed,
+        };
+        *committed_total = committed_total
+            .checked_sub(coverage_amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// `GlobalState` as the unversioned baseline program laid it out
+#[derive(AnchorDeserialize, InitSpace)]
+struct GlobalStateV0 {
+    bump: u8,
+    authority: Pubkey,
+    total_policies: u64,
+    total_premiums_collected: u64,
+    total_payouts: u64,
+    is_paused: bool,
+}
+
+impl From<GlobalStateV0> for GlobalState {
+    fn from(legacy: GlobalStateV0) -> Self {
+        // Fields the baseline lacked start as initialize sets them
+        Self {
+            bump: legacy.bump,
+            risk_pool_bump: Pubkey::find_program_address(&[b"risk_pool"], &crate::ID).1,
+            authority: legacy.authority,
+            total_policies: legacy.total_policies,
+            total_premiums_collected: legacy.total_premiums_collected,
+            total_payouts: legacy.total_payouts,
+            solvency_ratio_bps: DEFAULT_SOLVENCY_RATIO_BPS,
+            reputation_floor: DEFAULT_REPUTATION_FLOOR,
+            max_policy_duration_seconds: DEFAULT_MAX_POLICY_DURATION_SECONDS,
+            coverage_limits: [u64::MAX; CLIMATE_RISK_TYPE_COUNT],
+            waiting_periods: DEFAULT_WAITING_PERIODS_SECONDS,
+            max_region_exposure: u64::MAX,
+            no_data_refund_bps: BPS_DENOMINATOR as u16,
+            reputation_smoothing_bps: DEFAULT_REPUTATION_SMOOTHING_BPS,
+            trigger_grace_period_seconds: DEFAULT_TRIGGER_GRACE_PERIOD_SECONDS,
+            is_paused: legacy.is_paused,
+            version: 0,
+            ..Default::default()
+        }
+    }
+}
+
+/// Fields every versioned `GlobalState` shares, up to and including `version`;
+/// later versions append theirs after it
+#[derive(AnchorDeserialize)]
+struct GlobalStateV1 {
+    bump: u8,
+    risk_pool_bump: u8,
+    premium_mint: Pubkey,
+    risk_pool_vault: Pubkey,
+    authority: Pubkey,
+    pending_authority: Option<Pubkey>,
+    total_policies: u64,
+    total_premiums_collected: u64,
+    total_payouts: u64,
+    total_coverage_committed: u64,
+    solvency_ratio_bps: u16,
+    reputation_floor: u16,
+    protocol_fee_bps: u16,
+    total_fees_collected: u64,
+    fee_vault_bump: u8,
+    sol_coverage_committed: u64,
+    sol_premiums_collected: u64,
+    sol_payouts: u64,
+    max_policy_duration_seconds: i64,
+    total_reinsurance: u64,
+    triggered_claims: u64,
+    sol_triggered_claims: u64,
+    min_oracle_bond: u64,
+    coverage_limits: [u64; CLIMATE_RISK_TYPE_COUNT],
+    waiting_periods: [i64; CLIMATE_RISK_TYPE_COUNT],
+    authority_program: Option<Pubkey>,
+    max_region_exposure: u64,
+    policy_creation_interval_seconds: i64,
+    no_data_refund_bps: u16,
+    reputation_smoothing_bps: u16,
+    min_premium_rate_bps: u16,
+    trigger_grace_period_seconds: i64,
+    is_paused: bool,
+    version: u8,
+}
+
+impl From<GlobalStateV1> for GlobalState {
+    fn from(legacy: GlobalStateV1) -> Self {
+        Self {
+            bump: legacy.bump,
+            risk_pool_bump: legacy.risk_pool_bump,
+            premium_mint: legacy.premium_mint,
+            risk_pool_vault: legacy.risk_pool_vault,
+            authority: legacy.authority,
+            pending_authority: legacy.pending_authority,
+            total_policies: legacy.total_policies,
+            total_premiums_collected: legacy.total_premiums_collected,
+            total_payouts: legacy.total_payouts,
+            total_coverage_committed: legacy.total_coverage_committed,
+            solvency_ratio_bps: legacy.solvency_ratio_bps,
+            reputation_floor: legacy.reputation_floor,
+            protocol_fee_bps: legacy.protocol_fee_bps,
+            total_fees_collected: legacy.total_fees_collected,
+            fee_vault_bump: legacy.fee_vault_bump,
+            sol_coverage_committed: legacy.sol_coverage_committed,
+            sol_premiums_collected: legacy.sol_premiums_collected,
+            sol_payouts: legacy.sol_payouts,
+            max_policy_duration_seconds: legacy.max_policy_duration_seconds,
+            total_reinsurance: legacy.total_reinsurance,
+            triggered_claims: legacy.triggered_claims,
+            sol_triggered_claims: legacy.sol_triggered_claims,
+            min_oracle_bond: legacy.min_oracle_bond,
+            coverage_limits: legacy.coverage_limits,
+            waiting_periods: legacy.waiting_periods,
+            authority_program: legacy.authority_program,
+            max_region_exposure: legacy.max_region_exposure,
+            policy_creation_interval_seconds: legacy.policy_creation_interval_seconds,
+            no_data_refund_bps: legacy.no_data_refund_bps,
+            reputation_smoothing_bps: legacy.reputation_smoothing_bps,
+            min_premium_rate_bps: legacy.min_premium_rate_bps,
+            trigger_grace_period_seconds: legacy.trigger_grace_period_seconds,
+            is_paused: legacy.is_paused,
+            version: legacy.version,
+            ..Default::default()
+        }
+    }
+}
+
+/// Per-owner index of live policy ids, for discovery without scanning
+#[account]
+#[derive(InitSpace)]
+pub struct PolicyRegistry {
+    pub bump: u8,
+    pub owner: Pubkey,
+    #[max_len(MAX_POLICIES_PER_OWNER)]
+    pub policy_ids: Vec<u64>,
+    pub last_policy_created: i64, // Spaces creations policy_creation_interval_seconds apart
 }
 
-// Data structures
+impl PolicyRegistry {
+    pub fn remove(&mut self, policy_id: u64) {
+        self.policy_ids.retain(|&id| id != policy_id);
+    }
+}
 
+/// Coverage written across all open policies centred in one geohash cell
 #[account]
 #[derive(InitSpace)]
-pub struct GlobalState {
+pub struct RegionExposure {
     pub bump: u8,
-    pub authority: Pubkey,
-    pub total_policies: u64,
-    pub total_premiums_collected: u64,
-    pub total_payouts: u64,
-    pub is_paused: bool,
+    pub geohash: [u8; REGION_GEOHASH_PRECISION], // ASCII geohash of the cell
+    pub total_coverage: u64,
+    pub sol_total_coverage: u64, // Native SOL counterpart, in lamports
+    pub policy_count: u32,
+}
+
+impl RegionExposure {
+    /// Add a new policy's coverage, enforcing the per-cell cap
+    pub fn add(&mut self, mode: PaymentMode, coverage_amount: u64, max_exposure: u64) -> Result<()> {
+        self.extend(mode, coverage_amount, max_exposure)?;
+        self.policy_count = self.policy_count.checked_add(1).ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Add coverage to a policy the cell already counts, enforcing the per-cell cap
+    pub fn extend(&mut self, mode: PaymentMode, coverage_amount: u64, max_exposure: u64) -> Result<()> {
+        let total = match mode {
+            PaymentMode::SplToken => &mut self.total_coverage,
+            PaymentMode::NativeSol => &mut self.sol_total_coverage,
+        };
+        *total = total.checked_add(coverage_amount).ok_or(AmocaError::MathOverflow)?;
+        require!(*total <= max_exposure, AmocaError::RegionExposureExceeded);
+        Ok(())
+    }
+
+    /// Remove coverage a policy paid out or gave up while staying open
+    pub fn release(&mut self, mode: PaymentMode, coverage_amount: u64) -> Result<()> {
+        let total = match mode {
+            PaymentMode::SplToken => &mut self.total_coverage,
+            PaymentMode::NativeSol => &mut self.sol_total_coverage,
+        };
+        *total = total.checked_sub(coverage_amount).ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Remove a policy that settled or closed, with the coverage it still held
+    pub fn remove(&mut self, mode: PaymentMode, remaining_coverage: u64) -> Result<()> {
+        self.release(mode, remaining_coverage)?;
+        self.policy_count = self.policy_count.saturating_sub(1);
+        Ok(())
+    }
 }
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct ClimatePolicy {
     pub bump: u8,
     pub owner: Pubkey,
+    pub creator: Pubkey, // Seeds the policy PDA; unlike owner it never changes
+    pub pending_owner: Option<Pubkey>, // Offered by offer_policy_transfer until accepted
     pub status: PolicyStatus,
     pub policy_type: ClimateRiskType,
     pub geographic_bounds: GeoBounds,
     pub trigger_thresholds: TriggerConditions,
-    #[max_len(5)]
+    #[max_len(MAX_ORACLE_SOURCES)]
     pub oracle_sources: Vec<Pubkey>,
+    pub min_oracle_consensus: u8, // Distinct oracles required to agree on a breach
+    pub min_confidence: u8, // Readings below this confidence are ignored during evaluation
+    pub altitude_range: Option<AltitudeRange>, // Elevation band readings must be taken in; None accepts any
+    pub max_single_payout: u64, // Upper bound on any one execute_climate_payout
+    pub beneficiary: Pubkey, // Owner of the token account payouts are sent to
+    pub payment_mode: PaymentMode, // Whether premiums and payouts move SPL tokens or SOL
+    pub coverage_denomination: Option<[u8; 3]>, // Reference currency coverage and claims are counted in; None means the premium mint
+    pub fx_oracle: Option<Pubkey>, // Oracle whose FxRate converts denominated payouts
     pub monitoring_frequency: u32,
-    pub last_data_update: i64,
+    pub last_data_update: i64, // Last trigger evaluation; evaluations are spaced monitoring_frequency apart
     pub risk_score: u8,
+    pub risk_period_start: i64, // risk_score only rises until this measurement period ends
     pub payout_calculation: PayoutFormula,
+    pub decay_constant: u32, // Exponential curve steepness, in thousandths
+    pub last_severity: SeverityBreakdown, // Breach severities from the last evaluation
     pub coverage_amount: u64,
     pub premium_amount: u64,
+    pub protocol_fee_paid: u64, // Non-refundable share of the premium kept by the protocol
+    pub paid_out_so_far: u64, // Cumulative payouts; the policy is Claimed once this reaches coverage
     pub start_timestamp: i64,
     pub end_timestamp: i64,
+    pub waiting_period_seconds: i64, // Payouts are blocked until this long after start_timestamp
+    pub premium_deadline: i64, // Unpaid policies can be closed after this
+    pub breach_started_at: Option<i64>, // Timestamp of the first reading in the current breach
+    pub last_payout_at: Option<i64>, // Breaches must persist afresh after each payout
+    pub pending_claim: u64, // Claim reserved while Triggered, paid by execute_climate_payout
+    pub triggered_at: i64, // When the policy last became Triggered
+    pub filed_claim: u64, // Amount the policyholder claimed via file_claim; payouts need one filed
+    pub qualifying_readings: u32, // Readings this term that passed the policy's filters when submitted
+    pub haircut_bps: u16, // Haircut applied to the most recent payout
+    pub haircut_shortfall: u64, // Claimed amounts withheld by haircuts, owed if the pool recapitalizes
+    pub policy_mint: Option<Pubkey>, // 1-of-1 token whose holder owns the policy, once tokenized
+    pub version: u8, // Schema generation; fields added later go after it, read by migrate_policy only as far as the version wrote them
+    pub require_cross_validation: bool, // Breaches must be confirmed by oracles of two OracleTypes (version 2)
+    pub is_frozen: bool, // Set by freeze_policy; blocks evaluation and payouts (version 3)
+    #[max_len(MAX_PREMIUM_INSTALLMENTS)]
+    pub premium_schedule: Vec<PremiumInstallment>, // Installments summing to premium_amount before any coverage increase; empty when paid in full (version 4)
+    pub premium_paid_so_far: u64, // Premium deposited so far, protocol fee included (version 4)
+    pub trigger_reward_cycle: i64, // triggered_at of the last trigger whose evaluator was rewarded (version 5)
+    pub payout_reward_cycle: i64, // triggered_at of the last trigger whose payout keeper was rewarded (version 5)
+    pub deductible_amount: u64, // Subtracted from every payout and absorbed by the insured (version 6)
+    pub index_value: u16, // Peak consensus severity in bps at the last evaluation (version 7)
+    pub commitment_fx_rate: u64, // Premium mint base units per coverage unit, scaled by FX_RATE_SCALE, that pool and region counters hold the coverage at (version 8)
+    pub transferred_so_far: u64, // Premium mint base units paid out over the policy's life, bounded by global_max_single_policy_payout (version 9)
+    pub full_term_premium: u64, // Premium a full term of coverage_amount costs, pricing coverage increases (version 10)
+}
+
+impl ClimatePolicy {
+    /// Upgrade an account written under an older `version`, defaulting the
+    /// fields each later generation appended
+    pub fn migrate(&mut self) {
+        // Version 1 only introduced `version` itself; version 2 added cross
+        // validation, which existing policies never opted into
+        if self.version < 2 {
+            self.require_cross_validation = false;
+        }
+        if self.version < 3 {
+            self.is_frozen = false;
+        }
+        // Policies before installment plans paid their premium in one deposit
+        if self.version < 4 {
+            self.premium_schedule = Vec::new();
+            self.premium_paid_so_far = if self.status == PolicyStatus::Inactive {
+                0
+            } else {
+                self.premium_amount
+            };
+        }
+        if self.version < 5 {
+            self.trigger_reward_cycle = 0;
+            self.payout_reward_cycle = 0;
+        }
+        if self.version < 6 {
+            self.deductible_amount = 0;
+        }
+        if self.version < 7 {
+            self.index_value = self.last_severity.peak_bps();
+        }
+        // Earlier policies committed coverage at face value, denominated or not
+        if self.version < 8 {
+            self.commitment_fx_rate = FX_RATE_SCALE as u64;
+        }
+        // Earlier payouts are counted at face value, as the ceiling then did
+        if self.version < 9 {
+            self.transferred_so_far = self.paid_out_so_far;
+        }
+        // Earlier policies priced increases from premium_amount
+        if self.version < 10 {
+            self.full_term_premium = self.premium_amount;
+        }
+        self.version = CLIMATE_POLICY_VERSION;
+    }
+
+    /// Read an account written under any earlier layout, like
+    /// `GlobalState::from_legacy`
+    pub fn from_legacy(data: &[u8]) -> Result<Self> {
+        check_discriminator(data, ClimatePolicy::DISCRIMINATOR)?;
+        let mut fields = &data[ClimatePolicy::DISCRIMINATOR.len()..];
+        if data.len() == 8 + ClimatePolicyV0::INIT_SPACE {
+            return Ok(read_legacy::<ClimatePolicyV0>(&mut fields)?.into());
+        }
+
+        let mut policy: ClimatePolicy = read_legacy::<ClimatePolicyV1>(&mut fields)?.into();
+        if policy.version >= 2 {
+            policy.require_cross_validation = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 3 {
+            policy.is_frozen = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 4 {
+            policy.premium_schedule = read_legacy(&mut fields)?;
+            policy.premium_paid_so_far = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 5 {
+            policy.trigger_reward_cycle = read_legacy(&mut fields)?;
+            policy.payout_reward_cycle = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 6 {
+            policy.deductible_amount = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 7 {
+            policy.index_value = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 8 {
+            policy.commitment_fx_rate = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 9 {
+            policy.transferred_so_far = read_legacy(&mut fields)?;
+        }
+        if policy.version >= 10 {
+            policy.full_term_premium = read_legacy(&mut fields)?;
+        }
+        Ok(policy)
+    }
+
+    /// Coverage not yet paid out or withheld as a haircut shortfall
+    pub fn remaining_coverage(&self) -> u64 {
+        self.coverage_amount
+            .saturating_sub(self.paid_out_so_far)
+            .saturating_sub(self.haircut_shortfall)
+    }
+
+    /// `amount` of coverage or claims in premium mint base units, at the rate
+    /// the policy committed its coverage against the pool at
+    pub fn committed_value(&self, amount: u64) -> Result<u64> {
+        convert_rounding_up(amount, self.commitment_fx_rate)
+    }
+
+    /// Remaining coverage as held in the pool and region counters
+    pub fn committed_exposure(&self) -> Result<u64> {
+        self.committed_value(self.remaining_coverage())
+    }
+
+    /// Whether this policy's coverage stacks with coverage of `policy_type`
+    /// over `bounds`: the same peril, payment mode and denomination in an
+    /// overlapping area
+    pub fn stacks_with(
+        &self,
+        policy_type: ClimateRiskType,
+        payment_mode: PaymentMode,
+        coverage_denomination: Option<[u8; 3]>,
+        bounds: &GeoBounds,
+    ) -> bool {
+        self.policy_type == policy_type
+            && self.payment_mode == payment_mode
+            && self.coverage_denomination == coverage_denomination
+            && self.geographic_bounds.overlaps(bounds)
+    }
+
+    /// `amount` premium mint base units in coverage units at the commitment
+    /// rate, rounded down and saturating
+    pub fn coverage_value(&self, amount: u64) -> u64 {
+        let value = amount as u128 * FX_RATE_SCALE / self.commitment_fx_rate.max(1) as u128;
+        u64::try_from(value).unwrap_or(u64::MAX)
+    }
+
+    /// Premium that should have been deposited by `now`: the installments
+    /// already due, or the whole premium without a schedule
+    pub fn premium_due_by(&self, now: i64) -> u64 {
+        if self.premium_schedule.is_empty() {
+            return self.premium_amount;
+        }
+        self.premium_schedule
+            .iter()
+            .filter(|installment| installment.due_timestamp <= now)
+            .map(|installment| installment.amount)
+            .sum()
+    }
+
+    /// Whether an installment due by `now` is still unpaid
+    pub fn has_overdue_installment(&self, now: i64) -> bool {
+        self.premium_paid_so_far < self.premium_due_by(now)
+    }
+
+    /// Whether the policy is Triggered but its payout hasn't landed within the grace period
+    pub fn has_trigger_lapsed(&self, grace_period: i64, now: i64) -> bool {
+        self.status == PolicyStatus::Triggered
+            && now >= self.triggered_at.saturating_add(grace_period)
+    }
+
+    /// Whether `evaluate_climate_trigger` would accept the policy at `now`
+    pub fn is_due_for_evaluation(&self, grace_period: i64, now: i64) -> bool {
+        (matches!(self.status, PolicyStatus::Active | PolicyStatus::Monitoring)
+            || self.has_trigger_lapsed(grace_period, now))
+            && !self.is_frozen
+            && now <= self.end_timestamp
+            && now.saturating_sub(self.last_data_update) >= self.monitoring_frequency as i64
+    }
+
+    /// Fail unless `holder` owns the policy: its creator, or the policy token's holder once tokenized
+    pub fn verify_holder(
+        &self,
+        holder: Pubkey,
+        policy_token: Option<&Account<TokenAccount>>,
+    ) -> Result<()> {
+        match self.policy_mint {
+            None => require_keys_eq!(holder, self.owner, AmocaError::Unauthorized),
+            Some(_) => require_keys_eq!(self.token_holder(policy_token)?, holder, AmocaError::NotPolicyHolder),
+        }
+        Ok(())
+    }
+
+    /// Who payouts go to: the policy token's holder once tokenized, otherwise `beneficiary`
+    pub fn payout_recipient(&self, policy_token: Option<&Account<TokenAccount>>) -> Result<Pubkey> {
+        match self.policy_mint {
+            None => Ok(self.beneficiary),
+            Some(_) => self.token_holder(policy_token),
+        }
+    }
+
+    /// Whether a lapsed, paid policy that never saw qualifying data is still
+    /// inside the window reserved for the holder's no-data refund
+    pub fn is_awaiting_no_data_refund(&self, now: i64) -> bool {
+        matches!(self.status, PolicyStatus::Active | PolicyStatus::Monitoring)
+            && self.qualifying_readings == 0
+            && self.paid_out_so_far == 0
+            && self.haircut_shortfall == 0
+            && now <= self.end_timestamp.saturating_add(NO_DATA_REFUND_WINDOW_SECONDS)
+    }
+
+    /// Whether a reading taken at `altitude` falls in the policy's elevation band
+    pub fn admits_altitude(&self, altitude: Option<f64>) -> bool {
+        match &self.altitude_range {
+            Some(range) => range.contains(altitude),
+            None => true,
+        }
+    }
+
+    /// Whether `claimant` may file claims: the token holder once tokenized,
+    /// otherwise the owner or beneficiary
+    pub fn is_claimant(&self, claimant: Pubkey, policy_token: Option<&Account<TokenAccount>>) -> Result<bool> {
+        match self.policy_mint {
+            None => Ok(claimant == self.owner || claimant == self.beneficiary),
+            Some(_) => Ok(self.token_holder(policy_token)? == claimant),
+        }
+    }
+
+    fn token_holder(&self, policy_token: Option<&Account<TokenAccount>>) -> Result<Pubkey> {
+        let policy_token = policy_token.ok_or(AmocaError::NotPolicyHolder)?;
+        require!(
+            Some(policy_token.mint) == self.policy_mint && policy_token.amount == 1,
+            AmocaError::NotPolicyHolder
+        );
+        Ok(policy_token.owner)
+    }
+
+    /// Raise `risk_score` within a measurement period, resetting it once the period ends
+    pub fn update_risk_score(&mut self, score: u8, current_time: i64) {
+        let period = self.trigger_thresholds.measurement_period as i64 * 86_400;
+        if current_time >= self.risk_period_start.saturating_add(period) {
+            self.risk_period_start = current_time;
+            self.risk_score = score.min(100);
+        } else {
+            self.risk_score = self.risk_score.max(score).min(100);
+        }
+    }
+}
+
+/// `ClimatePolicy` as the unversioned baseline program laid it out
+#[derive(AnchorDeserialize, InitSpace)]
+struct ClimatePolicyV0 {
+    bump: u8,
+    owner: Pubkey,
+    status: PolicyStatus,
+    policy_type: ClimateRiskType,
+    geographic_bounds: GeoBounds,
+    trigger_thresholds: TriggerConditionsV0,
+    #[max_len(5)]
+    oracle_sources: Vec<Pubkey>,
+    monitoring_frequency: u32,
+    last_data_update: i64,
+    risk_score: u8,
+    payout_calculation: PayoutFormula,
+    coverage_amount: u64,
+    premium_amount: u64,
+    start_timestamp: i64,
+    end_timestamp: i64,
+}
+
+/// `TriggerConditions` as the baseline laid them out, before cold snaps,
+/// composite weights, trigger logic, hysteresis and seasons
+#[derive(AnchorDeserialize, InitSpace)]
+struct TriggerConditionsV0 {
+    rainfall_threshold: Option<f64>,
+    temperature_threshold: Option<f64>,
+    wind_speed_threshold: Option<f64>,
+    water_level_threshold: Option<f64>,
+    fire_proximity_threshold: Option<f64>,
+    measurement_period: u32,
+    minimum_duration: u32,
+}
+
+impl From<ClimatePolicyV0> for ClimatePolicy {
+    fn from(legacy: ClimatePolicyV0) -> Self {
+        // The baseline seeded policies by owner, paid them out to the owner
+        // and had no per-payout cap; other fields start as creation sets them
+        Self {
+            bump: legacy.bump,
+            owner: legacy.owner,
+            creator: legacy.owner,
+            status: legacy.status,
+            policy_type: legacy.policy_type,
+            geographic_bounds: legacy.geographic_bounds,
+            trigger_thresholds: TriggerConditions {
+                rainfall_threshold: legacy.trigger_thresholds.rainfall_threshold,
+                temperature_threshold: legacy.trigger_thresholds.temperature_threshold,
+                wind_speed_threshold: legacy.trigger_thresholds.wind_speed_threshold,
+                water_level_threshold: legacy.trigger_thresholds.water_level_threshold,
+                fire_proximity_threshold: legacy.trigger_thresholds.fire_proximity_threshold,
+                measurement_period: legacy.trigger_thresholds.measurement_period,
+                minimum_duration: legacy.trigger_thresholds.minimum_duration,
+                ..Default::default()
+            },
+            oracle_sources: legacy.oracle_sources,
+            min_oracle_consensus: DEFAULT_MIN_ORACLE_CONSENSUS,
+            min_confidence: MIN_CONFIDENCE_LEVEL,
+            max_single_payout: legacy.coverage_amount,
+            beneficiary: legacy.owner,
+            monitoring_frequency: legacy.monitoring_frequency,
+            last_data_update: legacy.last_data_update,
+            risk_score: legacy.risk_score,
+            risk_period_start: legacy.start_timestamp,
+            payout_calculation: legacy.payout_calculation,
+            decay_constant: DECAY_CONSTANT_SCALE as u32,
+            coverage_amount: legacy.coverage_amount,
+            premium_amount: legacy.premium_amount,
+            start_timestamp: legacy.start_timestamp,
+            end_timestamp: legacy.end_timestamp,
+            premium_deadline: legacy.start_timestamp + PREMIUM_GRACE_PERIOD_SECONDS,
+            version: 0,
+            ..Default::default()
+        }
+    }
+}
+
+/// Fields every versioned `ClimatePolicy` shares, up to and including
+/// `version`; later versions append theirs after it
+#[derive(AnchorDeserialize)]
+struct ClimatePolicyV1 {
+    bump: u8,
+    owner: Pubkey,
+    creator: Pubkey,
+    pending_owner: Option<Pubkey>,
+    status: PolicyStatus,
+    policy_type: ClimateRiskType,
+    geographic_bounds: GeoBounds,
+    trigger_thresholds: TriggerConditions,
+    oracle_sources: Vec<Pubkey>,
+    min_oracle_consensus: u8,
+    min_confidence: u8,
+    altitude_range: Option<AltitudeRange>,
+    max_single_payout: u64,
+    beneficiary: Pubkey,
+    payment_mode: PaymentMode,
+    coverage_denomination: Option<[u8; 3]>,
+    fx_oracle: Option<Pubkey>,
+    monitoring_frequency: u32,
+    last_data_update: i64,
+    risk_score: u8,
+    risk_period_start: i64,
+    payout_calculation: PayoutFormula,
+    decay_constant: u32,
+    last_severity: SeverityBreakdown,
+    coverage_amount: u64,
+    premium_amount: u64,
+    protocol_fee_paid: u64,
+    paid_out_so_far: u64,
+    start_timestamp: i64,
+    end_timestamp: i64,
+    waiting_period_seconds: i64,
+    premium_deadline: i64,
+    breach_started_at: Option<i64>,
+    last_payout_at: Option<i64>,
+    pending_claim: u64,
+    triggered_at: i64,
+    filed_claim: u64,
+    qualifying_readings: u32,
+    haircut_bps: u16,
+    haircut_shortfall: u64,
+    policy_mint: Option<Pubkey>,
+    version: u8,
+}
+
+impl From<ClimatePolicyV1> for ClimatePolicy {
+    fn from(legacy: ClimatePolicyV1) -> Self {
+        Self {
+            bump: legacy.bump,
+            owner: legacy.owner,
+            creator: legacy.creator,
+            pending_owner: legacy.pending_owner,
+            status: legacy.status,
+            policy_type: legacy.policy_type,
+            geographic_bounds: legacy.geographic_bounds,
+            trigger_thresholds: legacy.trigger_thresholds,
+            oracle_sources: legacy.oracle_sources,
+            min_oracle_consensus: legacy.min_oracle_consensus,
+            min_confidence: legacy.min_confidence,
+            altitude_range: legacy.altitude_range,
+            max_single_payout: legacy.max_single_payout,
+            beneficiary: legacy.beneficiary,
+            payment_mode: legacy.payment_mode,
+            coverage_denomination: legacy.coverage_denomination,
+            fx_oracle: legacy.fx_oracle,
+            monitoring_frequency: legacy.monitoring_frequency,
+            last_data_update: legacy.last_data_update,
+            risk_score: legacy.risk_score,
+            risk_period_start: legacy.risk_period_start,
+            payout_calculation: legacy.payout_calculation,
+            decay_constant: legacy.decay_constant,
+            last_severity: legacy.last_severity,
+            coverage_amount: legacy.coverage_amount,
+            premium_amount: legacy.premium_amount,
+            protocol_fee_paid: legacy.protocol_fee_paid,
+            paid_out_so_far: legacy.paid_out_so_far,
+            start_timestamp: legacy.start_timestamp,
+            end_timestamp: legacy.end_timestamp,
+            waiting_period_seconds: legacy.waiting_period_seconds,
+            premium_deadline: legacy.premium_deadline,
+            breach_started_at: legacy.breach_started_at,
+            last_payout_at: legacy.last_payout_at,
+            pending_claim: legacy.pending_claim,
+            triggered_at: legacy.triggered_at,
+            filed_claim: legacy.filed_claim,
+            qualifying_readings: legacy.qualifying_readings,
+            haircut_bps: legacy.haircut_bps,
+            haircut_shortfall: legacy.haircut_shortfall,
+            policy_mint: legacy.policy_mint,
+            version: legacy.version,
+            ..Default::default()
+        }
+    }
 }
 
 #[account]
@@ -520,10 +6934,250 @@ pub struct OracleData {
     pub bump: u8,
     pub provider: Pubkey,
     pub oracle_type: OracleType,
+    #[max_len(CLIMATE_DATA_TYPE_COUNT)]
+    pub supported_data_types: Vec<ClimateDataType>, // Data types the oracle declared it can report
+    pub min_submission_interval: u32, // Seconds required between submissions, from the feed's cadence
     pub reputation_score: u16,
-    pub last_update: i64,
+    pub last_update: i64, // Last submission, or registration before the first
+    pub reputation_decayed_at: i64, // End of the last missed interval charged by decay
     pub is_active: bool,
     pub data_points_count: u32,
+    pub dispute_count: u32, // Also the index of the next OracleDispute PDA
+    pub open_disputes: u16, // Raised disputes not yet slashed or dismissed; block unregistering
+}
+
+/// Token bond an oracle posts at registration, held in its bond vault
+#[account]
+#[derive(InitSpace)]
+pub struct OracleBond {
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub provider: Pubkey,
+    pub amount: u64, // Bond remaining after slashing, in the premium mint
+    pub bonded_at: i64,
+}
+
+/// Reinsurance capital a single reinsurer has committed to the risk pool
+#[account]
+#[derive(InitSpace)]
+pub struct ReinsurancePosition {
+    pub bump: u8,
+    pub reinsurer: Pubkey,
+    pub amount: u64, // Capital currently committed, in the premium mint
+    pub last_deposit_at: i64,
+}
+
+/// Oracle-published exchange rate from a reference currency to the premium mint
+#[account]
+#[derive(InitSpace)]
+pub struct FxRate {
+    pub bump: u8,
+    pub provider: Pubkey, // Oracle that publishes the rate
+    pub denomination: [u8; 3], // ISO 4217 code of the reference currency, e.g. b"KES"
+    pub rate: u64, // Premium mint base units per reference unit, scaled by FX_RATE_SCALE
+    pub updated_at: i64,
+}
+
+impl FxRate {
+    /// Value of `amount` reference units in premium mint base units
+    pub fn convert(&self, amount: u64) -> Result<u64> {
+        let converted = (amount as u128)
+            .checked_mul(self.rate as u128)
+            .ok_or(AmocaError::MathOverflow)?
+            / FX_RATE_SCALE;
+        u64::try_from(converted).map_err(|_| AmocaError::MathOverflow.into())
+    }
+
+    /// Rate, with `FX_COMMITMENT_MARGIN_BPS` on top, at which new coverage
+    /// in this denomination is committed against the pool
+    pub fn commitment_rate(&self) -> Result<u64> {
+        let rate = (self.rate as u128)
+            .checked_mul(BPS_DENOMINATOR + FX_COMMITMENT_MARGIN_BPS as u128)
+            .ok_or(AmocaError::MathOverflow)?
+            .div_ceil(BPS_DENOMINATOR);
+        u64::try_from(rate).map_err(|_| AmocaError::MathOverflow.into())
+    }
+}
+
+/// Allowlist entry letting a keeper execute payouts
+#[account]
+#[derive(InitSpace)]
+pub struct Keeper {
+    pub bump: u8,
+    pub keeper: Pubkey,
+    pub added_at: i64,
+}
+
+/// Record of a dispute that slashed an oracle's reputation
+#[account]
+#[derive(InitSpace)]
+pub struct OracleDispute {
+    pub bump: u8,
+    pub oracle: Pubkey,
+    pub policy: Pubkey, // Policy affected by the disputed data
+    pub authority: Pubkey,
+    pub slash_amount: u16,
+    pub bond_slashed: u64, // Bond moved to the risk pool
+    pub reputation_after: u16,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct ClimateReadings {
+    pub bump: u8,
+    pub policy: Pubkey,
+    #[max_len(8)]
+    pub buffers: Vec<ReadingBuffer>, // One ring buffer per reported data type
+}
+
+impl ClimateReadings {
+    /// Append a reading, overwriting the oldest slot once the buffer is full
+    pub fn record(&mut self, data_type: ClimateDataType, reading: ClimateReading) {
+        let index = match self.buffers.iter().position(|b| b.data_type == data_type) {
+            Some(index) => index,
+            None => {
+                self.buffers.push(ReadingBuffer {
+                    data_type,
+                    next_index: 0,
+                    readings: Vec::new(),
+                });
+                self.buffers.len() - 1
+            }
+        };
+        let buffer = &mut self.buffers[index];
+
+        if buffer.readings.len() < MAX_READINGS_PER_TYPE {
+            buffer.readings.push(reading);
+        } else {
+            buffer.readings[buffer.next_index as usize] = reading;
+        }
+        buffer.next_index = ((buffer.next_index as usize + 1) % MAX_READINGS_PER_TYPE) as u8;
+    }
+
+    /// Stored readings for a data type, in no particular order
+    pub fn readings_for(&self, data_type: ClimateDataType) -> &[ClimateReading] {
+        self.buffers
+            .iter()
+            .find(|b| b.data_type == data_type)
+            .map(|b| b.readings.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Premiums and payouts collected within one `STATS_BUCKET_SECONDS` bucket,
+/// for off-chain loss-ratio and reserve analysis
+#[account]
+#[derive(InitSpace)]
+pub struct EpochStats {
+    pub bump: u8,
+    pub day: i64, // Bucket index, unix_timestamp / STATS_BUCKET_SECONDS
+    pub premiums_collected: u64,
+    pub payouts: u64,
+    pub sol_premiums_collected: u64, // Native SOL counterparts, in lamports
+    pub sol_payouts: u64,
+    pub policies_activated: u32, // Premium deposits and renewals in the bucket
+    pub payout_count: u32,
+}
+
+impl EpochStats {
+    /// Index of the bucket the current time falls in
+    pub fn current_day() -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp / STATS_BUCKET_SECONDS)
+    }
+
+    /// Record a premium collected by `deposit_premium` or `renew_policy`
+    pub fn record_premium(&mut self, bump: u8, mode: PaymentMode, amount: u64) -> Result<()> {
+        self.open(bump)?;
+        let premiums_collected = match mode {
+            PaymentMode::SplToken => &mut self.premiums_collected,
+            PaymentMode::NativeSol => &mut self.sol_premiums_collected,
+        };
+        *premiums_collected = premiums_collected
+            .checked_add(amount)
+            .ok_or(AmocaError::MathOverflow)?;
+        self.policies_activated = self.policies_activated
+            .checked_add(1)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a payout disbursed by `execute_climate_payout`
+    pub fn record_payout(&mut self, bump: u8, mode: PaymentMode, amount: u64) -> Result<()> {
+        self.open(bump)?;
+        let payouts = match mode {
+            PaymentMode::SplToken => &mut self.payouts,
+            PaymentMode::NativeSol => &mut self.sol_payouts,
+        };
+        *payouts = payouts.checked_add(amount).ok_or(AmocaError::MathOverflow)?;
+        self.payout_count = self.payout_count
+            .checked_add(1)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Payouts as a share of premiums collected in the bucket, in basis
+    /// points; saturates when payouts arrive with no premiums
+    pub fn loss_ratio_bps(&self, mode: PaymentMode) -> u64 {
+        let (premiums, payouts) = match mode {
+            PaymentMode::SplToken => (self.premiums_collected, self.payouts),
+            PaymentMode::NativeSol => (self.sol_premiums_collected, self.sol_payouts),
+        };
+        if premiums == 0 {
+            return if payouts == 0 { 0 } else { u64::MAX };
+        }
+        let ratio = payouts as u128 * BPS_DENOMINATOR / premiums as u128;
+        ratio.min(u64::MAX as u128) as u64
+    }
+
+    /// Stamp a freshly created bucket with its bump and index
+    fn open(&mut self, bump: u8) -> Result<()> {
+        if self.day == 0 {
+            self.bump = bump;
+            self.day = Self::current_day()?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-policy audit trail of payouts
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimLog {
+    pub bump: u8,
+    pub policy: Pubkey,
+    pub total_claims: u32, // Payouts ever recorded, including ones overwritten
+    pub next_index: u8, // Slot overwritten by the next record once full
+    #[max_len(MAX_CLAIM_RECORDS)]
+    pub records: Vec<ClaimRecord>,
+}
+
+impl ClaimLog {
+    /// Append a record, overwriting the oldest slot once the log is full
+    pub fn record(&mut self, record: ClaimRecord) -> Result<()> {
+        if self.records.len() < MAX_CLAIM_RECORDS {
+            self.records.push(record);
+        } else {
+            self.records[self.next_index as usize] = record;
+        }
+        self.next_index = ((self.next_index as usize + 1) % MAX_CLAIM_RECORDS) as u8;
+        self.total_claims = self.total_claims
+            .checked_add(1)
+            .ok_or(AmocaError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ClaimRecord {
+    pub timestamp: i64,
+    pub amount: u64, // Paid to the recipient
+    pub claim_amount: u64, // Claimed before any haircut
+    pub haircut_bps: u16,
+    pub payout_formula: PayoutFormula,
+    pub severity: SeverityBreakdown, // Breach severities the payout was calculated from
+    pub breach_started_at: Option<i64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -532,31 +7186,107 @@ pub struct PolicyParams {
     pub policy_type: ClimateRiskType,
     pub geographic_bounds: GeoBounds,
     pub trigger_conditions: TriggerConditions,
-    #[max_len(5)]
+    #[max_len(MAX_ORACLE_SOURCES)]
     pub oracle_sources: Vec<Pubkey>,
     pub coverage_amount: u64,
     pub premium_amount: u64,
     pub end_timestamp: i64,
+    pub payout_formula: PayoutFormula,
+    pub decay_constant: u32,
+    pub min_oracle_consensus: Option<u8>, // Defaults to DEFAULT_MIN_ORACLE_CONSENSUS
+    pub min_confidence: Option<u8>, // MIN_CONFIDENCE_LEVEL-100, defaults to MIN_CONFIDENCE_LEVEL
+    pub monitoring_frequency: Option<u32>, // Seconds between evaluations, defaults to DEFAULT_MONITORING_FREQUENCY_SECONDS
+    pub altitude_range: Option<AltitudeRange>, // Only readings taken in this band count; None accepts any altitude
+    pub max_single_payout: Option<u64>, // Defaults to coverage_amount
+    pub deductible_amount: u64, // Taken off every payout; must be below coverage_amount, 0 for none
+    pub beneficiary: Option<Pubkey>, // Defaults to the policy owner
+    pub payment_mode: PaymentMode,
+    pub coverage_denomination: Option<[u8; 3]>, // ISO 4217 code; SPL token policies only
+    pub fx_oracle: Option<Pubkey>, // Required with coverage_denomination
+    pub require_cross_validation: bool, // Require breaches confirmed by two oracle types, against basis risk
+    #[max_len(MAX_PREMIUM_INSTALLMENTS)]
+    pub premium_schedule: Vec<PremiumInstallment>, // Empty to pay premium_amount in one deposit
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+/// One due date of a policy's premium installment plan
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct PremiumInstallment {
+    pub due_timestamp: i64,
+    pub amount: u64,
+}
+
+/// Hypothetical product for `simulate_triggers`; options default as in `PolicyParams`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SimulationParams {
+    pub policy_type: ClimateRiskType,
+    pub geographic_bounds: GeoBounds,
+    pub trigger_conditions: TriggerConditions,
+    pub coverage_amount: u64,
+    pub payout_formula: PayoutFormula,
+    pub decay_constant: u32,
+    pub min_oracle_consensus: Option<u8>,
+    pub monitoring_frequency: Option<u32>, // Spacing between replayed evaluations
+}
+
+/// Protocol parameters for `set_config`; `None` leaves a parameter unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigParams {
+    pub protocol_fee_bps: Option<u16>,
+    pub solvency_ratio_bps: Option<u16>,
+    pub reputation_floor: Option<u16>,
+    pub max_policy_duration_seconds: Option<i64>,
+    pub min_oracle_bond: Option<u64>,
+    pub max_region_exposure: Option<u64>,
+    pub policy_creation_interval_seconds: Option<i64>,
+    pub no_data_refund_bps: Option<u16>,
+    pub reputation_smoothing_bps: Option<u16>,
+    pub min_premium_rate_bps: Option<u16>,
+    pub trigger_grace_period_seconds: Option<i64>,
+    pub max_stacked_coverage: Option<u64>,
+    pub keeper_reward: Option<u64>,
+    pub max_loss_ratio_bps: Option<u32>,
+    pub global_max_single_policy_payout: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisputeParams {
+    pub policy: Pubkey,
+    pub slash_amount: u16,
+    pub bond_slash_amount: u64, // Capped at the remaining bond
+    pub evidence_hash: [u8; 32], // Hash of off-chain evidence the data was wrong
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleParams {
+    pub oracle_type: OracleType,
+    pub supported_data_types: Vec<ClimateDataType>, // Data types the oracle can report, without duplicates
+    pub min_submission_interval: u32, // Expected feed cadence in seconds, up to MAX_MONITORING_FREQUENCY_SECONDS; 0 for no limit
+    pub initial_reputation: u16, // 0-100
+    pub bond_amount: u64, // At least GlobalState.min_oracle_bond
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug, Default)]
 pub enum PolicyStatus {
+    #[default]
     Inactive,
     Active,
     Monitoring,
     Triggered,
     Claimed,
     Expired,
+    Lapsed, // An installment is overdue; not evaluated or paid out until caught up
 }
 
-impl Default for PolicyStatus {
-    fn default() -> Self {
-        Self::Inactive
+impl PolicyStatus {
+    /// Whether the policy's coverage is committed against the risk pool
+    pub fn is_in_force(&self) -> bool {
+        matches!(self, Self::Active | Self::Monitoring | Self::Triggered | Self::Lapsed)
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug, Default)]
 pub enum ClimateRiskType {
+    #[default]
     DroughtProtection,
     FloodInsurance,
     HurricaneCoverage,
@@ -566,9 +7296,10 @@ pub enum ClimateRiskType {
     ExtremeTemperature,
 }
 
-impl Default for ClimateRiskType {
-    fn default() -> Self {
-        Self::DroughtProtection
+impl ClimateRiskType {
+    /// Position in declaration order, indexing per-type tables
+    pub fn index(self) -> usize {
+        self as usize
     }
 }
 
@@ -589,15 +7320,54 @@ impl Default for GeoBounds {
     }
 }
 
+impl GeoBounds {
+    /// Finite coordinates in range and a radius in `(0, MAX_COVERAGE_RADIUS_KM]`
+    pub fn is_valid(&self) -> bool {
+        self.latitude.is_finite()
+            && self.longitude.is_finite()
+            && self.radius.is_finite()
+            && (-90.0..=90.0).contains(&self.latitude)
+            && (-180.0..=180.0).contains(&self.longitude)
+            && self.radius > 0.0
+            && self.radius <= MAX_COVERAGE_RADIUS_KM
+    }
+
+    /// Whether the two coverage areas share any ground
+    pub fn overlaps(&self, other: &GeoBounds) -> bool {
+        let distance = haversine_distance(
+            Fixed::from_f64(self.latitude),
+            Fixed::from_f64(self.longitude),
+            Fixed::from_f64(other.latitude),
+            Fixed::from_f64(other.longitude),
+        );
+        distance < Fixed::from_f64(self.radius + other.radius)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct TriggerConditions {
     pub rainfall_threshold: Option<f64>, // mm per measurement period
-    pub temperature_threshold: Option<f64>, // degrees Celsius
+    pub temperature_threshold: Option<f64>, // degrees Celsius; breaches at or above
+    pub cold_temperature_threshold: Option<f64>, // degrees Celsius; breaches at or below, e.g. -10.0 for a cold snap
     pub wind_speed_threshold: Option<f64>, // mph
     pub water_level_threshold: Option<f64>, // meters above normal
     pub fire_proximity_threshold: Option<f64>, // kilometers
     pub measurement_period: u32, // days
     pub minimum_duration: u32, // hours the condition must persist
+    pub composite_weights: CompositeWeights, // Used by the Composite payout formula and WeightedThreshold logic
+    pub trigger_logic: TriggerLogic, // How individual threshold breaches combine into a trigger
+    pub reset_band_bps: u16, // Hysteresis: reset levels sit this share of each threshold's magnitude back
+    pub active_window: Option<SeasonWindow>, // Season triggers respond in; None is year-round
+}
+
+/// How a policy's individual threshold checks combine into a trigger
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum TriggerLogic {
+    #[default]
+    AnyOf, // Any one persisted breach triggers
+    AllOf, // Every set threshold must have a persisted breach
+    WeightedThreshold { min_weight: u8 }, // composite_weights of persisted breaches must reach min_weight
+    IndexBased, // Pays the formula on the consensus severity index as it deepens, without waiting for persistence
 }
 
 impl Default for TriggerConditions {
@@ -605,29 +7375,158 @@ impl Default for TriggerConditions {
         Self {
             rainfall_threshold: None,
             temperature_threshold: None,
+            cold_temperature_threshold: None,
             wind_speed_threshold: None,
             water_level_threshold: None,
             fire_proximity_threshold: None,
             measurement_period: 7,
             minimum_duration: 24,
+            composite_weights: CompositeWeights::default(),
+            trigger_logic: TriggerLogic::default(),
+            reset_band_bps: 0,
+            active_window: None,
+        }
+    }
+}
+
+impl TriggerConditions {
+    /// Whether triggers respond at `now`, given the policy's active window
+    pub fn is_in_season(&self, now: i64) -> bool {
+        match self.active_window {
+            Some(window) => window.contains(seconds_of_year(now)),
+            None => true,
+        }
+    }
+}
+
+/// A range of the year, in seconds since 1 January 00:00 UTC
+///
+/// A window whose `start` is after its `end` wraps over the new year, e.g.
+/// November to February.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct SeasonWindow {
+    pub start: i64,
+    pub end: i64, // Exclusive
+}
+
+impl SeasonWindow {
+    pub fn is_valid(&self) -> bool {
+        let in_year = |s: i64| (0..SECONDS_PER_LEAP_YEAR).contains(&s);
+        in_year(self.start) && in_year(self.end) && self.start != self.end
+    }
+
+    pub fn contains(&self, second_of_year: i64) -> bool {
+        if self.start < self.end {
+            (self.start..self.end).contains(&second_of_year)
+        } else {
+            second_of_year >= self.start || second_of_year < self.end
+        }
+    }
+}
+
+/// Percentage each dimension contributes to a Composite payout or WeightedThreshold trigger; must total 100
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct CompositeWeights {
+    pub rainfall: u8,
+    pub temperature: u8,
+    pub wind_speed: u8,
+}
+
+impl CompositeWeights {
+    pub fn total(&self) -> u16 {
+        self.rainfall as u16 + self.temperature as u16 + self.wind_speed as u16
+    }
+
+    /// Weight of a data type; dimensions without a weight contribute nothing
+    pub fn weight_for(&self, data_type: ClimateDataType) -> u8 {
+        match data_type {
+            ClimateDataType::Rainfall => self.rainfall,
+            ClimateDataType::Temperature => self.temperature,
+            ClimateDataType::WindSpeed => self.wind_speed,
+            _ => 0,
         }
     }
 }
 
+/// Per-dimension breach severity in basis points, capped at `MAX_SEVERITY_BPS`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SeverityBreakdown {
+    pub rainfall_bps: u16,
+    pub temperature_bps: u16,
+    pub wind_speed_bps: u16,
+    pub water_level_bps: u16,
+    pub fire_proximity_bps: u16,
+}
+
+impl SeverityBreakdown {
+    pub fn record(&mut self, data_type: ClimateDataType, severity_bps: u16) {
+        let slot = match data_type {
+            ClimateDataType::Rainfall => &mut self.rainfall_bps,
+            ClimateDataType::Temperature => &mut self.temperature_bps,
+            ClimateDataType::WindSpeed => &mut self.wind_speed_bps,
+            ClimateDataType::WaterLevel => &mut self.water_level_bps,
+            ClimateDataType::FireDetection => &mut self.fire_proximity_bps,
+            _ => return,
+        };
+        *slot = (*slot).max(severity_bps.min(MAX_SEVERITY_BPS));
+    }
+
+    /// Largest severity across all dimensions
+    pub fn peak_bps(&self) -> u16 {
+        self.rainfall_bps
+            .max(self.temperature_bps)
+            .max(self.wind_speed_bps)
+            .max(self.water_level_bps)
+            .max(self.fire_proximity_bps)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct ClimateDataPoint {
     pub data_type: ClimateDataType,
     pub location: GeographicCoordinate,
     pub value: f64,
+    pub unit: MeasurementUnit, // Must be the data type's unit, see ClimateDataType::unit
     pub timestamp: i64,
     pub confidence_level: u8, // 0-100 data quality score
-    pub source_id: Pubkey, // Oracle provider identifier
+    pub source_id: Pubkey, // Oracle provider identifier; must be the submitting oracle
     #[max_len(32)]
-    pub verification_hash: Vec<u8>, // Cryptographic proof
+    pub verification_hash: Vec<u8>, // sha256 of the canonical reading, see canonical_hash
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+impl ClimateDataPoint {
+    /// sha256 of the Borsh-encoded `(data_type, location, value, timestamp, source_id)`
+    pub fn canonical_hash(&self) -> Result<[u8; 32]> {
+        let mut preimage = Vec::new();
+        (self.data_type, self.location, self.value, self.timestamp, self.source_id)
+            .serialize(&mut preimage)
+            .map_err(|_| AmocaError::InvalidOracleData)?;
+        Ok(hash(&preimage).to_bytes())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ReadingBuffer {
+    pub data_type: ClimateDataType,
+    pub next_index: u8, // Slot overwritten by the next reading once full
+    #[max_len(MAX_READINGS_PER_TYPE)]
+    pub readings: Vec<ClimateReading>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ClimateReading {
+    pub oracle: Pubkey, // Oracle provider that reported the value
+    pub location: GeographicCoordinate,
+    pub value: f64,
+    pub timestamp: i64,
+    pub confidence_level: u8,
+    pub reputation_score: u16, // Reporter's reputation at submission, used as its weight
+    pub oracle_type: OracleType, // Reporter's category at submission, for cross validation
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug, Default)]
 pub enum ClimateDataType {
+    #[default]
     Temperature,
     Rainfall,
     WindSpeed,
@@ -638,12 +7537,50 @@ pub enum ClimateDataType {
     AtmosphericPressure,
 }
 
-impl Default for ClimateDataType {
-    fn default() -> Self {
-        Self::Temperature
+impl ClimateDataType {
+    /// Whether each reading reports an amount accrued since the oracle's previous
+    /// one, so the measurement period is judged on the window total (rainfall in
+    /// mm) rather than on a typical reading
+    pub fn is_cumulative(self) -> bool {
+        matches!(self, Self::Rainfall)
+    }
+
+    /// Unit readings of this type are reported in, and the policy thresholds
+    /// they're compared against are set in
+    pub fn unit(self) -> MeasurementUnit {
+        match self {
+            Self::Temperature => MeasurementUnit::Celsius,
+            Self::Rainfall => MeasurementUnit::Millimeters,
+            Self::WindSpeed => MeasurementUnit::MilesPerHour,
+            Self::Humidity => MeasurementUnit::Percent,
+            Self::WaterLevel => MeasurementUnit::Meters,
+            Self::FireDetection => MeasurementUnit::Kilometers,
+            Self::VegetationIndex => MeasurementUnit::Index,
+            Self::AtmosphericPressure => MeasurementUnit::Hectopascals,
+        }
     }
 }
 
+/// Unit a reading's value is expressed in
+///
+/// Each `ClimateDataType` has exactly one accepted unit. The others exist so a
+/// feed declaring, say, wind in m/s is rejected instead of read as mph.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum MeasurementUnit {
+    Celsius,
+    Fahrenheit,
+    Millimeters,
+    Inches,
+    MilesPerHour,
+    MetersPerSecond,
+    KilometersPerHour,
+    Percent,
+    Meters,
+    Kilometers,
+    Index, // Dimensionless, e.g. NDVI
+    Hectopascals,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
 pub struct GeographicCoordinate {
     pub latitude: f64,
@@ -661,8 +7598,35 @@ impl Default for GeographicCoordinate {
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+/// Elevation band, in metres, that a policy's readings must be taken in
+///
+/// Keeps e.g. a mountain gauge's water level out of a coastal sea-level policy.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub struct AltitudeRange {
+    pub min: f64,
+    pub max: f64, // Inclusive
+    pub include_unknown: bool, // Whether readings that report no altitude count
+}
+
+impl AltitudeRange {
+    pub fn is_valid(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite() && self.min <= self.max
+    }
+
+    pub fn contains(&self, altitude: Option<f64>) -> bool {
+        match altitude {
+            Some(altitude) => {
+                let altitude = Fixed::from_f64(altitude);
+                Fixed::from_f64(self.min) <= altitude && altitude <= Fixed::from_f64(self.max)
+            }
+            None => self.include_unknown,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
 pub enum OracleType {
+    #[default]
     ChainlinkWeather,
     PythSatellite,
     NasaModis,
@@ -671,26 +7635,567 @@ pub enum OracleType {
     SwitchboardNetwork,
 }
 
-impl Default for OracleType {
-    fn default() -> Self {
-        Self::ChainlinkWeather
-    }
+/// Asset a policy's premiums and payouts are paid in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
+pub enum PaymentMode {
+    #[default]
+    SplToken, // The premium mint, through the risk pool vault
+    NativeSol, // Lamports held directly by the risk pool PDA
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Default)]
 pub enum PayoutFormula {
+    #[default]
     LinearScale,
     StepFunction,
     Exponential,
     Composite,
 }
 
-impl Default for PayoutFormula {
-    fn default() -> Self {
-        Self::LinearScale
+/// Direction a reading has to move past its threshold to count as a breach
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BreachDirection {
+    Above,
+    Below,
+}
+
+/// Span of reading timestamps an evaluation considers, inclusive at both ends
+#[derive(Clone, Copy)]
+pub struct MeasurementWindow {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl MeasurementWindow {
+    /// The rolling `[now - days * 86400, now]` window of a measurement period
+    pub fn trailing(now: i64, days: u32) -> Self {
+        Self { start: now.saturating_sub(days as i64 * 86_400), end: now }
+    }
+
+    pub fn contains(&self, timestamp: i64) -> bool {
+        (self.start..=self.end).contains(&timestamp)
+    }
+}
+
+/// A single threshold comparison derived from a policy's trigger conditions
+#[derive(Clone, Copy)]
+pub struct ThresholdCheck {
+    pub data_type: ClimateDataType,
+    pub threshold: Fixed, // Trigger level
+    pub reset: Fixed, // Level readings must recede past to end a breach
+    pub direction: BreachDirection,
+}
+
+impl ThresholdCheck {
+    /// Check with its reset level `reset_band_bps` of the threshold magnitude back from it
+    pub fn new(
+        data_type: ClimateDataType,
+        threshold: Fixed,
+        direction: BreachDirection,
+        reset_band_bps: u16,
+    ) -> Self {
+        let mut check = Self { data_type, threshold, reset: threshold, direction };
+        let band = check.magnitude().scale_bps(reset_band_bps);
+        check.reset = match direction {
+            BreachDirection::Above => threshold - band,
+            BreachDirection::Below => threshold + band,
+        };
+        check
+    }
+
+    /// Whether a value has receded past the reset level, ending a breach
+    pub fn is_cleared(&self, value: Fixed) -> bool {
+        match self.direction {
+            BreachDirection::Above => value < self.reset,
+            BreachDirection::Below => value > self.reset,
+        }
+    }
+
+    /// A breach at the trigger level, or anything short of clearing while `held`
+    pub fn counts_as_breach(&self, value: Fixed, held: bool) -> bool {
+        if held { !self.is_cleared(value) } else { self.is_breached(value) }
+    }
+
+    pub fn is_breached(&self, value: Fixed) -> bool {
+        match self.direction {
+            BreachDirection::Above => value >= self.threshold,
+            BreachDirection::Below => value <= self.threshold,
+        }
+    }
+
+    /// How far past the threshold a value is, relative to the threshold magnitude
+    pub fn severity_bps(&self, value: Fixed) -> u16 {
+        let excess = match self.direction {
+            BreachDirection::Above => value - self.threshold,
+            BreachDirection::Below => self.threshold - value,
+        };
+        if excess <= Fixed::ZERO {
+            return 0;
+        }
+        excess
+            .ratio(self.magnitude(), MAX_SEVERITY_BPS as i128)
+            .min(MAX_SEVERITY_BPS as i128) as u16
+    }
+
+    /// 100 at or past the threshold, falling linearly to 0 a full threshold magnitude away
+    pub fn proximity_score(&self, value: Fixed) -> u8 {
+        if self.is_breached(value) {
+            return 100;
+        }
+        let magnitude = self.magnitude();
+        let closeness = magnitude - (value - self.threshold).abs();
+        closeness.max(Fixed::ZERO).ratio(magnitude, 100).min(99) as u8
+    }
+
+    /// Threshold magnitude used to normalise distances, at least 1
+    fn magnitude(&self) -> Fixed {
+        self.threshold.abs().max(Fixed::ONE)
     }
 }
 
+/// A threshold breach agreed on by enough oracles, at the weighted severity
+pub struct ThresholdBreach {
+    pub severity_bps: u16,
+    pub timestamp: i64, // Latest breaching reading
+}
+
+/// Result of evaluating a policy's trigger conditions
+pub struct TriggerOutcome {
+    pub triggered: bool,
+    pub severity: SeverityBreakdown,
+}
+
+// Events
+
+#[event]
+pub struct PolicyCreated {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub policy_type: ClimateRiskType,
+    pub coverage_amount: u64,
+    pub premium_amount: u64,
+    pub end_timestamp: i64,
+}
+
+#[event]
+pub struct PremiumDeposited {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct ClimateDataSubmitted {
+    pub oracle: Pubkey,
+    pub policy: Pubkey,
+    pub data_points: u8,
+    pub reputation_score: u16,
+    pub risk_score: u8,
+}
+
+#[event]
+pub struct TriggerEvaluated {
+    pub policy: Pubkey,
+    pub triggered: bool,
+    pub status: PolicyStatus,
+    pub severity_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct PremiumInstallmentMissed {
+    pub policy: Pubkey,
+    pub premium_due: u64,
+    pub premium_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TriggerLapsed {
+    pub policy: Pubkey,
+    pub released_claim: u64,
+    pub triggered_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchTriggersEvaluated {
+    pub evaluated: u32,
+    pub triggered: u32,
+    pub skipped: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimFiled {
+    pub policy: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub pending_claim: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperRewarded {
+    pub keeper: Pubkey,
+    pub policy: Pubkey,
+    pub amount: u64,
+    pub triggered_at: i64, // Trigger cycle the crank was rewarded for
+}
+
+#[event]
+pub struct PayoutExecuted {
+    pub policy: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub payout_formula: PayoutFormula,
+    pub haircut_bps: u16, // Share of the claim withheld because the pool was short
+}
+
+#[event]
+pub struct PolicyCancelled {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub refund_amount: u64,
+}
+
+#[event]
+pub struct NoDataRefunded {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub refund_amount: u64,
+    pub end_timestamp: i64,
+}
+
+#[event]
+pub struct OracleRegistered {
+    pub provider: Pubkey,
+    pub oracle_type: OracleType,
+    pub reputation_score: u16,
+    pub bond_amount: u64,
+}
+
+#[event]
+pub struct OracleDeactivated {
+    pub provider: Pubkey,
+}
+
+#[event]
+pub struct KeeperAdded {
+    pub keeper: Pubkey,
+}
+
+#[event]
+pub struct KeeperRemoved {
+    pub keeper: Pubkey,
+}
+
+#[event]
+pub struct ProgramPauseToggled {
+    pub authority: Pubkey,
+    pub is_paused: bool,
+}
+
+#[event]
+pub struct HistoricalWindowEvaluated {
+    pub policy: Pubkey,
+    pub authority: Pubkey,
+    pub window_start: i64,
+    pub window_end: i64,
+    pub triggered: bool,
+}
+
+#[event]
+pub struct PayoutsHalted {
+    pub policy: Pubkey, // Policy whose payout tripped the breaker
+    pub day: i64, // EpochStats bucket the loss ratio was measured over
+    pub loss_ratio_bps: u64,
+    pub max_loss_ratio_bps: u32,
+}
+
+#[event]
+pub struct PayoutsResumed {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PolicyLapsed {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyClosed {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub status: PolicyStatus,
+}
+
+#[event]
+pub struct UnpaidPolicyClosed {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub premium_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityProgramUpdated {
+    pub authority: Pubkey,
+    pub authority_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct OracleReputationDecayed {
+    pub provider: Pubkey,
+    pub reputation_score: u16,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct CoverageIncreased {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub additional_coverage: u64,
+    pub coverage_amount: u64, // Coverage after the increase
+    pub premium_amount: u64, // Pro-rated premium collected for the increase
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct PolicyRenewed {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub premium_amount: u64,
+    pub end_timestamp: i64,
+}
+
+#[event]
+pub struct TriggerConditionsUpdated {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct PolicyFreezeToggled {
+    pub policy: Pubkey,
+    pub authority: Pubkey,
+    pub is_frozen: bool,
+}
+
+#[event]
+pub struct PolicyForceSettled {
+    pub policy: Pubkey,
+    pub authority: Pubkey,
+    pub previous_status: PolicyStatus,
+    pub status: PolicyStatus,
+}
+
+#[event]
+pub struct RiskPoolInitialized {
+    pub premium_mint: Pubkey,
+    pub risk_pool_vault: Pubkey,
+}
+
+#[event]
+pub struct MonitoringFrequencyUpdated {
+    pub policy: Pubkey,
+    pub previous_frequency: u32,
+    pub monitoring_frequency: u32,
+}
+
+#[event]
+pub struct BeneficiaryUpdated {
+    pub policy: Pubkey,
+    pub previous_beneficiary: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+#[event]
+pub struct PolicyStatusReport {
+    pub policy: Pubkey,
+    pub status: PolicyStatus,
+    pub premium_paid: bool,
+    pub is_expired: bool,
+    pub remaining_coverage: u64,
+    pub seconds_until_expiry: i64, // Zero once expired
+}
+
+#[event]
+pub struct PayoutPreviewed {
+    pub policy: Pubkey,
+    pub triggered: bool, // Whether an evaluation now would trigger
+    pub payout_amount: u64, // Formula result after the per-payout cap and remaining coverage
+    pub pending_claim: u64, // Claim already reserved, if the policy is triggered
+    pub payout_formula: PayoutFormula,
+    pub risk_score: u8,
+    pub severity: SeverityBreakdown,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskScoreComputed {
+    pub policy: Pubkey,
+    pub risk_score: u8, // Score the policy would hold if re-scored now
+    pub live_score: Option<u8>, // Score of the current readings alone; None when nothing qualifies
+    pub stored_risk_score: u8, // Score last written by submit_climate_data
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TriggersSimulated {
+    pub policy_type: ClimateRiskType,
+    pub readings: u32,
+    pub evaluations: u32, // Replayed evaluations, at most one per monitoring interval
+    pub triggers: u32,
+    pub total_payout: u64, // Sum of the payouts each trigger would have made
+    pub peak_severity_bps: u16,
+    pub first_timestamp: i64,
+    pub last_timestamp: i64,
+}
+
+#[event]
+pub struct FxRateSubmitted {
+    pub provider: Pubkey,
+    pub denomination: [u8; 3],
+    pub rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleDisputed {
+    pub provider: Pubkey,
+    pub policy: Pubkey,
+    pub slash_amount: u16,
+    pub bond_slashed: u64,
+    pub reputation_score: u16,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct OracleDisputeRaised {
+    pub provider: Pubkey,
+    pub open_disputes: u16,
+}
+
+#[event]
+pub struct OracleDisputeDismissed {
+    pub provider: Pubkey,
+    pub open_disputes: u16,
+}
+
+#[event]
+pub struct OracleUnregistered {
+    pub provider: Pubkey,
+    pub bond_returned: u64,
+}
+
+#[event]
+pub struct EmergencyWithdrawal {
+    pub authority: Pubkey,
+    pub mode: PaymentMode,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CoverageLimitUpdated {
+    pub policy_type: ClimateRiskType,
+    pub max_coverage: u64,
+}
+
+#[event]
+pub struct WaitingPeriodUpdated {
+    pub policy_type: ClimateRiskType,
+    pub waiting_period_seconds: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub solvency_ratio_bps: u16,
+    pub reputation_floor: u16,
+    pub max_policy_duration_seconds: i64,
+    pub min_oracle_bond: u64,
+    pub max_region_exposure: u64,
+    pub policy_creation_interval_seconds: i64,
+    pub no_data_refund_bps: u16,
+    pub reputation_smoothing_bps: u16,
+    pub min_premium_rate_bps: u16,
+    pub trigger_grace_period_seconds: i64,
+    pub max_stacked_coverage: u64,
+    pub keeper_reward: u64,
+    pub max_loss_ratio_bps: u32,
+    pub global_max_single_policy_payout: u64,
+}
+
+#[event]
+pub struct ReinsuranceDeposited {
+    pub reinsurer: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+    pub total_reinsurance: u64,
+}
+
+#[event]
+pub struct ReinsuranceWithdrawn {
+    pub reinsurer: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+    pub total_reinsurance: u64,
+}
+
+#[event]
+pub struct PolicyTransferOffered {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+}
+
+#[event]
+pub struct PolicyTransferred {
+    pub policy: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct PolicyTokenized {
+    pub policy: Pubkey,
+    pub owner: Pubkey,
+    pub policy_mint: Pubkey,
+}
+
 // Error definitions
 
 #[error_code]
@@ -733,4 +8238,192 @@ pub enum AmocaError {
     Unauthorized,
     #[msg("Program is paused")]
     ProgramPaused,
+    #[msg("Exponential payouts require a positive decay constant")]
+    InvalidDecayConstant,
+    #[msg("Composite weights must total 100")]
+    InvalidCompositeWeights,
+    #[msg("Policy can no longer be cancelled")]
+    PolicyNotCancellable,
+    #[msg("Policy has not reached its end timestamp")]
+    PolicyNotYetExpired,
+    #[msg("Policy cannot be expired in its current status")]
+    PolicyNotExpirable,
+    #[msg("Reputation score must be between 0 and 100")]
+    InvalidReputationScore,
+    #[msg("Oracle consensus must require at least one oracle")]
+    InvalidOracleConsensus,
+    #[msg("Risk pool cannot support additional coverage")]
+    PoolUndercollateralized,
+    #[msg("Protocol fee cannot exceed 10000 bps")]
+    InvalidProtocolFee,
+    #[msg("Fee vault balance too low")]
+    InsufficientFees,
+    #[msg("Token account mint does not match")]
+    InvalidTokenAccount,
+    #[msg("Oracle has not missed a monitoring interval")]
+    OracleNotStale,
+    #[msg("Policy cannot be renewed in its current status")]
+    PolicyNotRenewable,
+    #[msg("Policy terms are locked once the premium is paid")]
+    PolicyLocked,
+    #[msg("Single payout cap must be positive and within coverage")]
+    InvalidPayoutCap,
+    #[msg("Program must be paused")]
+    ProgramNotPaused,
+    #[msg("Policies can only be force settled as Expired or Claimed")]
+    InvalidSettlementStatus,
+    #[msg("Token account is not the risk pool vault")]
+    InvalidRiskPoolAccount,
+    #[msg("Payout account is not owned by the policy beneficiary")]
+    InvalidBeneficiary,
+    #[msg("Beneficiary can no longer be changed")]
+    BeneficiaryLocked,
+    #[msg("Account required by the policy's payment mode is missing")]
+    MissingPaymentAccount,
+    #[msg("Slash amount must be positive")]
+    InvalidSlashAmount,
+    #[msg("Owner's policy registry is full")]
+    PolicyRegistryFull,
+    #[msg("Policy duration exceeds the maximum coverage period")]
+    PolicyDurationTooLong,
+    #[msg("Solvency ratio must be positive")]
+    InvalidSolvencyRatio,
+    #[msg("Policy was evaluated less than monitoring_frequency seconds ago")]
+    EvaluationTooFrequent,
+    #[msg("Verification hash does not match the reading")]
+    InvalidVerificationHash,
+    #[msg("Reinsurance amount must be positive")]
+    InvalidReinsuranceAmount,
+    #[msg("Withdrawal exceeds the reinsurance position")]
+    InsufficientReinsurance,
+    #[msg("Caller does not hold the policy token")]
+    NotPolicyHolder,
+    #[msg("WeightedThreshold min_weight must be between 1 and 100")]
+    InvalidTriggerLogic,
+    #[msg("Oracle bond is below the required minimum")]
+    InsufficientOracleBond,
+    #[msg("Oracle has no open dispute")]
+    NoOpenDispute,
+    #[msg("Oracle has open disputes")]
+    OracleDisputePending,
+    #[msg("Oracle bond cooldown has not elapsed")]
+    BondCooldownActive,
+    #[msg("Batch accounts must be writable policy and readings pairs")]
+    InvalidBatchAccounts,
+    #[msg("Premium payment deadline has passed")]
+    PremiumDeadlinePassed,
+    #[msg("Premium payment deadline has not passed yet")]
+    PremiumDeadlineNotReached,
+    #[msg("Only claimed or expired policies with nothing owed can be closed")]
+    PolicyNotClosable,
+    #[msg("Season window must be two distinct offsets within a year")]
+    InvalidSeasonWindow,
+    #[msg("Coverage exceeds the limit for this risk type")]
+    CoverageExceedsTypeLimit,
+    #[msg("Committed coverage or triggered claims are still outstanding")]
+    ObligationsOutstanding,
+    #[msg("Minimum confidence must be between the submission floor and 100")]
+    InvalidMinConfidence,
+    #[msg("Reset band cannot exceed 10000 basis points")]
+    InvalidResetBand,
+    #[msg("Cold temperature threshold must sit below the heat threshold and cannot be combined with AllOf")]
+    InvalidTemperatureBounds,
+    #[msg("Admin calls must be routed through the configured authority program")]
+    AuthorityCallerMismatch,
+    #[msg("Coverage would exceed the cap for this region")]
+    RegionExposureExceeded,
+    #[msg("Monitoring frequency must be between 10 minutes and 7 days")]
+    InvalidMonitoringFrequency,
+    #[msg("Token account mint does not match the premium mint")]
+    InvalidMint,
+    #[msg("The policyholder has not filed a claim")]
+    ClaimNotFiled,
+    #[msg("Owner created a policy too recently")]
+    PolicyCreationTooFrequent,
+    #[msg("Altitude range must be finite with min at or below max")]
+    InvalidAltitudeRange,
+    #[msg("Policy is awaiting its holder's no-data refund")]
+    NoDataRefundPending,
+    #[msg("Policy received qualifying oracle data during its term")]
+    PolicyReceivedData,
+    #[msg("Refund fraction must not exceed 10000 bps")]
+    InvalidRefundFraction,
+    #[msg("Policy id is already in the owner's registry")]
+    PolicyIdTaken,
+    #[msg("Tokenized policies transfer with their policy token")]
+    PolicyIsTokenized,
+    #[msg("Policy cannot be transferred in its current state")]
+    PolicyNotTransferable,
+    #[msg("Payouts must be executed by a registered keeper or the policy's claimant")]
+    NotAuthorizedKeeper,
+    #[msg("Oracle sources must be distinct, at most 5, and enough to reach consensus")]
+    InvalidOracleSources,
+    #[msg("Reputation smoothing must be between 1 and 10000 bps")]
+    InvalidReputationSmoothing,
+    #[msg("Coverage can only be increased on an active or monitoring policy")]
+    CoverageNotIncreasable,
+    #[msg("Policy is still in its waiting period")]
+    WaitingPeriodActive,
+    #[msg("Denominated coverage needs an FX oracle and an SPL token payment mode")]
+    InvalidCoverageDenomination,
+    #[msg("FX rate is missing, zero or not the policy's designated rate")]
+    InvalidFxRate,
+    #[msg("FX rate is too old to convert a payout")]
+    StaleFxRate,
+    #[msg("Withdrawal would take funds reserved for triggered claims")]
+    PoolFundsReserved,
+    #[msg("Premium is below the minimum rate for its coverage")]
+    PremiumRatioTooLow,
+    #[msg("Minimum premium rate must not exceed 10000 bps")]
+    InvalidPremiumRate,
+    #[msg("Too few of the policy's oracles report a data type its risk type requires")]
+    OracleCapabilityMismatch,
+    #[msg("Supported data types must be a non-empty list of distinct data types")]
+    InvalidSupportedDataTypes,
+    #[msg("Oracle does not support this climate data type")]
+    UnsupportedDataType,
+    #[msg("Triggered policy is still within its payout grace period")]
+    TriggerGracePeriodActive,
+    #[msg("Trigger grace period must be positive")]
+    InvalidTriggerGracePeriod,
+    #[msg("Account is already at the current schema version")]
+    AccountAlreadyMigrated,
+    #[msg("Policy is frozen by the authority")]
+    PolicyFrozen,
+    #[msg("Premium schedule must hold positive installments in due-date order, within the policy term, summing to the premium")]
+    InvalidPremiumSchedule,
+    #[msg("A premium installment is overdue")]
+    PremiumInstallmentOverdue,
+    #[msg("Premium installments are still outstanding")]
+    PremiumInstallmentsOutstanding,
+    #[msg("Oracle submitted again before its minimum submission interval")]
+    SubmissionTooFrequent,
+    #[msg("Minimum submission interval exceeds the longest monitoring frequency")]
+    InvalidSubmissionInterval,
+    #[msg("Owner's overlapping coverage of this peril would exceed the stacked coverage limit")]
+    StackedCoverageExceeded,
+    #[msg("Remaining accounts must hold each policy in the owner's registry, in order")]
+    InvalidRegistryPolicies,
+    #[msg("No payout is due: the calculated payout is zero or coverage is exhausted")]
+    NoPayoutDue,
+    #[msg("Deductible must be below the per-payout cap and the coverage amount")]
+    InvalidDeductible,
+    #[msg("Payouts are halted by the loss-ratio circuit breaker")]
+    PayoutsHalted,
+    #[msg("Payouts are not halted")]
+    PayoutsNotHalted,
+    #[msg("Evaluation window must be a past span within the policy term, at most one measurement period long")]
+    InvalidEvaluationWindow,
+    #[msg("Reading is not in its data type's measurement unit")]
+    InvalidMeasurementUnit,
+    #[msg("Minimum oracle bond exceeds the allowed maximum")]
+    InvalidOracleBond,
+    #[msg("Keeper reward exceeds the allowed maximum")]
+    InvalidKeeperReward,
+    #[msg("Maximum loss ratio exceeds the allowed maximum")]
+    InvalidLossRatio,
+    #[msg("Policy id does not derive the policy account")]
+    PolicyIdMismatch,
+    #[msg("Region is not the policy's geohash cell")]
+    RegionMismatch,
 }
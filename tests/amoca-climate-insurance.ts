@@ -5,11 +5,12 @@ import {
   PublicKey,
   Keypair,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   SYSVAR_RENT_PUBKEY,
-  Transaction,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   MINT_SIZE,
   createInitializeMintInstruction,
   getMinimumBalanceForRentExemptMint,
@@ -17,8 +18,79 @@ import {
   createAccount,
   mintTo,
   getAccount,
+  getMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
 } from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
+
+// ClimateDataType variants in declaration order, for the Borsh enum index
+const CLIMATE_DATA_TYPES = [
+  "temperature",
+  "rainfall",
+  "windSpeed",
+  "humidity",
+  "waterLevel",
+  "fireDetection",
+  "vegetationIndex",
+  "atmosphericPressure",
+];
+
+// sha256 of the Borsh-encoded (data_type, location, value, timestamp, source_id)
+function withVerificationHash<T extends {
+  dataType: object;
+  location: { latitude: number; longitude: number; altitude: number | null };
+  value: number;
+  timestamp: BN;
+  sourceId: PublicKey;
+}>(point: T): T & { verificationHash: number[] } {
+  const f64 = (x: number) => {
+    const buf = Buffer.alloc(8);
+    buf.writeDoubleLE(x);
+    return buf;
+  };
+  const { altitude } = point.location;
+  const preimage = Buffer.concat([
+    Buffer.from([CLIMATE_DATA_TYPES.indexOf(Object.keys(point.dataType)[0])]),
+    f64(point.location.latitude),
+    f64(point.location.longitude),
+    altitude === null ? Buffer.from([0]) : Buffer.concat([Buffer.from([1]), f64(altitude)]),
+    f64(point.value),
+    point.timestamp.toArrayLike(Buffer, "le", 8),
+    point.sourceId.toBuffer(),
+  ]);
+  const verificationHash = [...createHash("sha256").update(preimage).digest()];
+  return { ...point, verificationHash };
+}
+
+// Standard geohash of a point, as keyed by `RegionExposure` accounts
+const GEOHASH_ALPHABET = "0123456789bcdefghjkmnpqrstuvwxyz";
+function regionGeohash(latitude: number, longitude: number, precision = 3): string {
+  const ranges = { latitude: [-90, 90], longitude: [-180, 180] };
+  let geohash = "";
+  let longitudeBit = true;
+  while (geohash.length < precision) {
+    let index = 0;
+    for (let bit = 0; bit < 5; bit++) {
+      const [range, value] = longitudeBit
+        ? [ranges.longitude, longitude]
+        : [ranges.latitude, latitude];
+      const mid = (range[0] + range[1]) / 2;
+      index <<= 1;
+      if (value > mid) {
+        index |= 1;
+        range[0] = mid;
+      } else {
+        range[1] = mid;
+      }
+      longitudeBit = !longitudeBit;
+    }
+    geohash += GEOHASH_ALPHABET[index];
+  }
+  return geohash;
+}
 
 describe("AMOCA Climate Insurance", () => {
   // Configure the client to use the local cluster
@@ -27,6 +99,48 @@ describe("AMOCA Climate Insurance", () => {
     .AmocaClimateInsurance as Program<AmocaClimateInsurance>;
   const provider = anchor.getProvider();
 
+  const regionExposurePda = (bounds: { latitude: number; longitude: number }) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("region"), Buffer.from(regionGeohash(bounds.latitude, bounds.longitude))],
+      program.programId
+    )[0];
+
+  const epochStatsPda = () =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("epoch_stats"),
+        new BN(Math.floor(Date.now() / 1000 / 86400)).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const oracleDataPdaFor = (oracle: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle"), oracle.toBuffer()],
+      program.programId
+    )[0];
+
+  // createClimatePolicy's remaining accounts: each source's OracleData, in order
+  const oracleSourceAccounts = (sources: PublicKey[] = [oracleProvider.publicKey]) =>
+    sources.map((source) => ({
+      pubkey: oracleDataPdaFor(source),
+      isWritable: false,
+      isSigner: false,
+    }));
+
+  // Every climate data type, declared by oracles that can report anything
+  const allDataTypes = [
+    { temperature: {} },
+    { rainfall: {} },
+    { windSpeed: {} },
+    { humidity: {} },
+    { waterLevel: {} },
+    { fireDetection: {} },
+    { vegetationIndex: {} },
+    { atmosphericPressure: {} },
+  ];
+  const bondAmount = 10 * 10 ** 6;
+
   // Test accounts
   let authority: Keypair;
   let policyOwner: Keypair;
@@ -36,11 +150,43 @@ describe("AMOCA Climate Insurance", () => {
   let globalStateBump: number;
   let riskPoolPda: PublicKey;
   let riskPoolBump: number;
+  let feeVaultPda: PublicKey;
+  let policyRegistryPda: PublicKey;
+  let policyPda: PublicKey;
+  let climateReadingsPda: PublicKey;
 
   // Token accounts
   let userTokenAccount: PublicKey;
   let riskPoolTokenAccount: PublicKey;
 
+  // Funds a provider token account and derives the bond PDAs for registerOracle
+  async function bondAccounts(oracle: Keypair) {
+    const providerTokenAccount = await createAccount(
+      provider.connection,
+      authority,
+      mint,
+      oracle.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority,
+      mint,
+      providerTokenAccount,
+      authority,
+      bondAmount
+    );
+    const [oracleBond] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_bond"), oracle.publicKey.toBuffer()],
+      program.programId
+    );
+    const [bondVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bond_vault"), oracle.publicKey.toBuffer()],
+      program.programId
+    );
+    return { providerTokenAccount, oracleBond, bondVault };
+  }
+  let oracleBondAccounts: Awaited<ReturnType<typeof bondAccounts>>;
+
   before(async () => {
     // Initialize keypairs
     authority = Keypair.generate();
@@ -90,6 +236,16 @@ describe("AMOCA Climate Insurance", () => {
       program.programId
     );
 
+    [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault")],
+      program.programId
+    );
+
+    [policyRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry"), policyOwner.publicKey.toBuffer()],
+      program.programId
+    );
+
     // Create token accounts
     userTokenAccount = await createAccount(
       provider.connection,
@@ -98,12 +254,8 @@ describe("AMOCA Climate Insurance", () => {
       policyOwner.publicKey
     );
 
-    riskPoolTokenAccount = await createAccount(
-      provider.connection,
-      authority,
-      mint,
-      riskPoolPda
-    );
+    // Created by initRiskPool as the risk pool PDA's associated token account
+    riskPoolTokenAccount = getAssociatedTokenAddressSync(mint, riskPoolPda, true);
 
     // Mint tokens to user
     await mintTo(
@@ -140,10 +292,85 @@ describe("AMOCA Climate Insurance", () => {
       expect(globalState.totalPayouts.toNumber()).to.equal(0);
       expect(globalState.isPaused).to.be.false;
     });
+
+    it("Should initialize the risk pool vault", async () => {
+      await program.methods
+        .initRiskPool()
+        .accounts({
+          authority: authority.publicKey,
+          riskPoolPda: riskPoolPda,
+          premiumMint: mint,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const globalState = await program.account.globalState.fetch(
+        globalStatePda
+      );
+      expect(globalState.premiumMint.equals(mint)).to.be.true;
+      expect(globalState.riskPoolVault.equals(riskPoolTokenAccount)).to.be.true;
+
+      const vault = await getAccount(provider.connection, riskPoolTokenAccount);
+      expect(vault.owner.equals(riskPoolPda)).to.be.true;
+    });
+
+    // Policies are created against this oracle, so it is registered up front
+    it("Should register the policies' oracle provider", async () => {
+      oracleBondAccounts = await bondAccounts(oracleProvider);
+
+      await program.methods
+        .registerOracle({
+          oracleType: { chainlinkWeather: {} },
+          supportedDataTypes: allDataTypes,
+          minSubmissionInterval: 0,
+          initialReputation: 100,
+          bondAmount: new BN(bondAmount),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          provider: oracleProvider.publicKey,
+          oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+          ...oracleBondAccounts,
+          premiumMint: mint,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority, oracleProvider])
+        .rpc();
+    });
+
+    it("Should initialize the protocol fee vault", async () => {
+      await program.methods
+        .initializeFeeVault(0)
+        .accounts({
+          authority: authority.publicKey,
+          feeMint: mint,
+          feeVault: feeVaultPda,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const globalState = await program.account.globalState.fetch(
+        globalStatePda
+      );
+      expect(globalState.protocolFeeBps).to.equal(0);
+      expect(globalState.totalFeesCollected.toNumber()).to.equal(0);
+
+      const feeVault = await getAccount(provider.connection, feeVaultPda);
+      expect(feeVault.mint.equals(mint)).to.be.true;
+    });
   });
 
   describe("Climate Policy Management", () => {
-    let policyPda: PublicKey;
     let policyBump: number;
     const policyId = new BN(1);
 
@@ -157,6 +384,11 @@ describe("AMOCA Climate Insurance", () => {
         program.programId
       );
 
+      [climateReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), policyPda.toBuffer()],
+        program.programId
+      );
+
       const policyParams = {
         policyId: policyId,
         policyType: { droughtProtection: {} },
@@ -168,16 +400,35 @@ describe("AMOCA Climate Insurance", () => {
         triggerConditions: {
           rainfallThreshold: 10.0,
           temperatureThreshold: null,
+          coldTemperatureThreshold: null,
           windSpeedThreshold: null,
           waterLevelThreshold: null,
           fireProximityThreshold: null,
           measurementPeriod: 7,
           minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
         },
         oracleSources: [oracleProvider.publicKey],
         coverageAmount: new BN(10000 * 10 ** 6), // 10,000 USDC
         premiumAmount: new BN(100 * 10 ** 6), // 100 USDC
-        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 365 * 24 * 3600), // 1 year from now
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600), // 6 months from now
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
       };
 
       const tx = await program.methods
@@ -185,9 +436,14 @@ describe("AMOCA Climate Insurance", () => {
         .accounts({
           owner: policyOwner.publicKey,
           policy: policyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: climateReadingsPda,
+          regionExposure: regionExposurePda(policyParams.geographicBounds),
+          fxRate: null,
           globalState: globalStatePda,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(oracleSourceAccounts(policyParams.oracleSources))
         .signers([policyOwner])
         .rpc();
 
@@ -200,12 +456,65 @@ describe("AMOCA Climate Insurance", () => {
       expect(policy.policyType).to.deep.equal({ droughtProtection: {} });
       expect(policy.coverageAmount.toNumber()).to.equal(10000 * 10 ** 6);
       expect(policy.premiumAmount.toNumber()).to.equal(100 * 10 ** 6);
+      expect(policy.minConfidence).to.equal(50);
+      expect(policy.deductibleAmount.toNumber()).to.equal(0);
 
       // Verify global state was updated
       const globalState = await program.account.globalState.fetch(
         globalStatePda
       );
       expect(globalState.totalPolicies.toNumber()).to.equal(1);
+
+      // Indexed in the owner's registry
+      const registry = await program.account.policyRegistry.fetch(policyRegistryPda);
+      expect(registry.owner.equals(policyOwner.publicKey)).to.be.true;
+      expect(registry.policyIds.map((id) => id.toNumber())).to.deep.equal([1]);
+    });
+
+    it("Should reject a premium paid in another mint", async () => {
+      const otherMint = await createMint(
+        provider.connection,
+        authority,
+        authority.publicKey,
+        null,
+        6
+      );
+      const otherTokenAccount = await createAccount(
+        provider.connection,
+        policyOwner,
+        otherMint,
+        policyOwner.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        authority,
+        otherMint,
+        otherTokenAccount,
+        authority,
+        100 * 10 ** 6
+      );
+
+      try {
+        await program.methods
+          .depositPremium(policyId, new BN(100 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            userTokenAccount: otherTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMint");
+      }
     });
 
     it("Should deposit premium and activate policy", async () => {
@@ -218,6 +527,9 @@ describe("AMOCA Climate Insurance", () => {
           policy: policyPda,
           userTokenAccount: userTokenAccount,
           riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          feeVault: feeVaultPda,
+          epochStats: epochStatsPda(),
           globalState: globalStatePda,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -249,6 +561,198 @@ describe("AMOCA Climate Insurance", () => {
       expect(globalState.totalPremiumsCollected.toNumber()).to.equal(
         100 * 10 ** 6
       );
+
+      // And today's analytics bucket
+      const epochStats = await program.account.epochStats.fetch(epochStatsPda());
+      expect(epochStats.day.toNumber()).to.equal(Math.floor(Date.now() / 1000 / 86400));
+      expect(epochStats.premiumsCollected.toNumber()).to.equal(100 * 10 ** 6);
+      expect(epochStats.policiesActivated).to.equal(1);
+      expect(epochStats.payouts.toNumber()).to.equal(0);
+    });
+
+    it("Should renew an active policy", async () => {
+      const before = await program.account.climatePolicy.fetch(policyPda);
+      const additionalDuration = new BN(30 * 24 * 3600); // 30 days
+
+      await program.methods
+        .renewPolicy(policyId, additionalDuration, new BN(10 * 10 ** 6))
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: policyPda,
+          regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+          userTokenAccount: userTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          feeVault: feeVaultPda,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.status).to.deep.equal({ active: {} });
+      expect(policy.endTimestamp.toNumber()).to.equal(
+        before.endTimestamp.add(additionalDuration).toNumber()
+      );
+      expect(policy.premiumAmount.toNumber()).to.equal(10 * 10 ** 6);
+    });
+
+    it("Should hold renewal premiums to the minimum premium rate", async () => {
+      const setMinPremiumRate = (minPremiumRateBps: number) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      await setMinPremiumRate(200);
+      try {
+        // A single base unit can't buy another term of 10,000 coverage
+        await program.methods
+          .renewPolicy(policyId, new BN(30 * 24 * 3600), new BN(1))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PremiumRatioTooLow");
+      } finally {
+        await setMinPremiumRate(0);
+      }
+    });
+
+    it("Should report policy status through a simulated call", async () => {
+      const { events } = await program.methods
+        .getPolicyStatus(policyId)
+        .accounts({ policy: policyPda })
+        .simulate();
+
+      const report = events.find((e) => e.name === "policyStatusReport").data;
+      expect(report.status).to.deep.equal({ active: {} });
+      expect(report.premiumPaid).to.be.true;
+      expect(report.isExpired).to.be.false;
+      expect(report.remainingCoverage.toNumber()).to.equal(10000 * 10 ** 6);
+      expect(report.secondsUntilExpiry.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Should default the beneficiary to the owner and allow updates", async () => {
+      let policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.beneficiary.equals(policyOwner.publicKey)).to.be.true;
+
+      const lender = Keypair.generate();
+      const updateBeneficiary = (beneficiary: PublicKey) =>
+        program.methods
+          .updateBeneficiary(policyId, beneficiary)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+      await updateBeneficiary(lender.publicKey);
+      policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.beneficiary.equals(lender.publicKey)).to.be.true;
+
+      // Restore the owner so later payout tests pay into userTokenAccount
+      await updateBeneficiary(policyOwner.publicKey);
+    });
+
+    it("Should let the owner tune the monitoring frequency", async () => {
+      const setMonitoringFrequency = (monitoringFrequency: number) =>
+        program.methods
+          .setMonitoringFrequency(policyId, monitoringFrequency)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            policyToken: null,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+      await setMonitoringFrequency(24 * 3600);
+      let policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.monitoringFrequency).to.equal(24 * 3600);
+
+      try {
+        await setMonitoringFrequency(60);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMonitoringFrequency");
+      }
+
+      // Restore the hourly default later evaluation tests rely on
+      await setMonitoringFrequency(3600);
+      policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.monitoringFrequency).to.equal(3600);
+    });
+
+    it("Should reject trigger condition updates once active", async () => {
+      try {
+        await program.methods
+          .updateTriggerConditions(policyId, {
+            rainfallThreshold: 50.0,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 7,
+            minimumDuration: 24,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            policyToken: null,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyLocked");
+      }
     });
 
     it("Should fail to create policy with invalid parameters", async () => {
@@ -263,16 +767,35 @@ describe("AMOCA Climate Insurance", () => {
         triggerConditions: {
           rainfallThreshold: 10.0,
           temperatureThreshold: null,
+          coldTemperatureThreshold: null,
           windSpeedThreshold: null,
           waterLevelThreshold: null,
           fireProximityThreshold: null,
           measurementPeriod: 7,
           minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
         },
         oracleSources: [oracleProvider.publicKey],
         coverageAmount: new BN(0), // Invalid coverage amount
         premiumAmount: new BN(100 * 10 ** 6),
         endTimestamp: new BN(Math.floor(Date.now() / 1000) - 1000), // Past timestamp
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
       };
 
       const [invalidPolicyPda] = PublicKey.findProgramAddressSync(
@@ -290,9 +813,13 @@ describe("AMOCA Climate Insurance", () => {
           .accounts({
             owner: policyOwner.publicKey,
             policy: invalidPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(invalidPolicyParams.geographicBounds),
+            fxRate: null,
             globalState: globalStatePda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(oracleSourceAccounts(invalidPolicyParams.oracleSources))
           .signers([policyOwner])
           .rpc();
 
@@ -301,235 +828,5873 @@ describe("AMOCA Climate Insurance", () => {
         expect(error.message).to.include("InvalidCoverageAmount");
       }
     });
-  });
 
-  describe("Oracle Data Management", () => {
-    let oracleDataPda: PublicKey;
-    let oracleDataBump: number;
+    it("Should reject a policy with a zero coverage radius", async () => {
+      const zeroRadiusParams = {
+        policyId: new BN(2),
+        policyType: { droughtProtection: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 0.0, // Can never contain a reading
+        },
+        triggerConditions: {
+          rainfallThreshold: 10.0,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
 
-    before(async () => {
-      [oracleDataPda, oracleDataBump] = PublicKey.findProgramAddressSync(
-        [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+      const [zeroRadiusPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
         program.programId
       );
 
-      // Initialize oracle data account (this would typically be done by an admin)
-      // For testing purposes, we'll create it manually
-      const initOracleTx = new Transaction().add(
-        SystemProgram.createAccount({
-          fromPubkey: oracleProvider.publicKey,
-          newAccountPubkey: oracleDataPda,
-          lamports: await provider.connection.getMinimumBalanceForRentExemption(
-            8 + 32 + 1 + 2 + 8 + 1 + 4 + 1 // Estimated size for OracleData
-          ),
-          space: 8 + 32 + 1 + 2 + 8 + 1 + 4 + 1,
-          programId: program.programId,
-        })
-      );
-
-      await provider.sendAndConfirm(initOracleTx, [oracleProvider]);
+      try {
+        await program.methods
+          .createClimatePolicy(zeroRadiusParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: zeroRadiusPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(zeroRadiusParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(zeroRadiusParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
 
-      // Mock initialize oracle data
-      const oracleData = {
-        bump: oracleDataBump,
-        provider: oracleProvider.publicKey,
-        oracleType: { chainlinkWeather: {} },
-        reputationScore: 100,
-        lastUpdate: new BN(0),
-        isActive: true,
-        dataPointsCount: 0,
-      };
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidGeographicBounds");
+      }
     });
 
-    it("Should submit climate data from oracle", async () => {
-      const dataPoints = [
-        {
-          dataType: { temperature: {} },
-          location: {
-            latitude: 40.7128,
-            longitude: -74.006,
-            altitude: null,
-          },
-          value: 25.5,
-          timestamp: new BN(Math.floor(Date.now() / 1000)),
-          confidenceLevel: 95,
-          sourceId: oracleProvider.publicKey,
-          verificationHash: Array(32).fill(0),
+    it("Should reject a deductible that absorbs every payout", async () => {
+      const deductibleParams = {
+        policyId: new BN(2),
+        policyType: { droughtProtection: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
         },
-        {
-          dataType: { rainfall: {} },
-          location: {
-            latitude: 40.7128,
-            longitude: -74.006,
-            altitude: null,
-          },
-          value: 5.2,
-          timestamp: new BN(Math.floor(Date.now() / 1000)),
-          confidenceLevel: 88,
-          sourceId: oracleProvider.publicKey,
-          verificationHash: Array(32).fill(1),
+        triggerConditions: {
+          rainfallThreshold: 10.0,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
         },
-      ];
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(10000 * 10 ** 6), // Equal to the coverage: nothing could pay out
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [deductiblePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
 
-      // Note: This test assumes the oracle data account is properly initialized
-      // In a real implementation, you would need proper oracle registration
       try {
-        const tx = await program.methods
-          .submitClimateData(dataPoints)
+        await program.methods
+          .createClimatePolicy(deductibleParams)
           .accounts({
-            oracleProvider: oracleProvider.publicKey,
-            oracleData: oracleDataPda,
+            owner: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(deductibleParams.geographicBounds),
+            fxRate: null,
             globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
           })
-          .signers([oracleProvider])
+          .remainingAccounts(oracleSourceAccounts(deductibleParams.oracleSources))
+          .signers([policyOwner])
           .rpc();
 
-        console.log("Submit climate data transaction signature:", tx);
+        expect.fail("Should have thrown an error");
       } catch (error) {
-        console.log("Expected error due to mock oracle setup:", error.message);
+        expect(error.message).to.include("InvalidDeductible");
+      }
+
+      // Below the coverage but equal to the per-payout cap
+      try {
+        await program.methods
+          .createClimatePolicy({
+            ...deductibleParams,
+            maxSinglePayout: new BN(1000 * 10 ** 6),
+            deductibleAmount: new BN(1000 * 10 ** 6),
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(deductibleParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(deductibleParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDeductible");
       }
     });
-  });
 
-  describe("Trigger Evaluation and Payouts", () => {
-    let policyPda: PublicKey;
-    const policyId = new BN(1);
+    it("Should reject a policy longer than the maximum duration", async () => {
+      const longPolicyParams = {
+        policyId: new BN(2),
+        policyType: { droughtProtection: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: 10.0,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 2 * 365 * 24 * 3600), // 2 years
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [longPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(longPolicyParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: longPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(longPolicyParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(longPolicyParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyDurationTooLong");
+      }
+    });
+
+    it("Should snapshot the risk type's waiting period onto new policies", async () => {
+      let globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.waitingPeriods[0].toNumber()).to.equal(30 * 24 * 3600);
+      expect(globalState.waitingPeriods[2].toNumber()).to.equal(7 * 24 * 3600);
+
+      const policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(policy.waitingPeriodSeconds.toNumber()).to.equal(30 * 24 * 3600);
+
+      await program.methods
+        .setWaitingPeriod({ hurricaneCoverage: {} }, new BN(14 * 24 * 3600))
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.waitingPeriods[2].toNumber()).to.equal(14 * 24 * 3600);
+    });
+
+    it("Should cap coverage per risk type", async () => {
+      await program.methods
+        .setCoverageLimit({ seaLevelRise: {} }, new BN(5000 * 10 ** 6))
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.coverageLimits[5].toNumber()).to.equal(5000 * 10 ** 6);
+
+      const [cappedPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy({
+            policyId: new BN(2),
+            policyType: { seaLevelRise: {} },
+            geographicBounds: {
+              latitude: 40.7128,
+              longitude: -74.006,
+              radius: 50.0,
+            },
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: 2.0,
+              fireProximityThreshold: null,
+              measurementPeriod: 7,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(10000 * 10 ** 6),
+            premiumAmount: new BN(100 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: cappedPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("CoverageExceedsTypeLimit");
+      }
+    });
+
+    it("Should reject a weighted trigger with no minimum weight", async () => {
+      const weightedParams = {
+        policyId: new BN(2),
+        policyType: { agriculturalClimate: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: 10.0,
+          temperatureThreshold: 38.0,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 50, temperature: 50, windSpeed: 0 },
+          triggerLogic: { weightedThreshold: { minWeight: 0 } },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [weightedPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(weightedParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: weightedPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(weightedParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(weightedParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidTriggerLogic");
+      }
+    });
+
+    it("Should reject a cold bound at or above the heat bound", async () => {
+      const temperatureParams = {
+        policyId: new BN(2),
+        policyType: { extremeTemperature: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: null,
+          temperatureThreshold: 35.0,
+          coldTemperatureThreshold: 36.0,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 100, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [temperaturePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(temperatureParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: temperaturePolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(temperatureParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(temperatureParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidTemperatureBounds");
+      }
+    });
+
+    it("Should reject an inverted altitude range", async () => {
+      const altitudeParams = {
+        policyId: new BN(2),
+        policyType: { seaLevelRise: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: null,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: 2.0,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: { min: 50.0, max: 0.0, includeUnknown: true },
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [altitudePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(altitudeParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: altitudePolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(altitudeParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(altitudeParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAltitudeRange");
+      }
+    });
+
+    it("Should reject duplicate oracle sources", async () => {
+      const duplicateParams = {
+        policyId: new BN(2),
+        policyType: { seaLevelRise: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: null,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: 2.0,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [oracleProvider.publicKey, oracleProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 2,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [duplicatePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(duplicateParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: duplicatePolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(duplicateParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(duplicateParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOracleSources");
+      }
+    });
+  });
+
+  describe("Policy Cancellation", () => {
+    const cancelPolicyId = new BN(3);
+    let cancelPolicyPda: PublicKey;
+
+    before(async () => {
+      [cancelPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          cancelPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      const [cancelReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), cancelPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: cancelPolicyId,
+          policyType: { floodInsurance: {} },
+          geographicBounds: {
+            latitude: 40.7128,
+            longitude: -74.006,
+            radius: 50.0,
+          },
+          triggerConditions: {
+            rainfallThreshold: 100.0,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 7,
+            minimumDuration: 24,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: new BN(1000 * 10 ** 6),
+          premiumAmount: new BN(10 * 10 ** 6),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: cancelPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: cancelReadingsPda,
+          regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+    });
+
+    it("Should update trigger conditions before the premium is paid", async () => {
+      await program.methods
+        .updateTriggerConditions(cancelPolicyId, {
+          rainfallThreshold: 150.0,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: 5.0,
+          fireProximityThreshold: null,
+          measurementPeriod: 3,
+          minimumDuration: 12,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          // 1 June to 1 December in a non-leap year
+          activeWindow: { start: new BN(151 * 86400), end: new BN(334 * 86400) },
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: cancelPolicyPda,
+          policyToken: null,
+          globalState: globalStatePda,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(cancelPolicyPda);
+      expect(policy.triggerThresholds.rainfallThreshold).to.equal(150.0);
+      expect(policy.triggerThresholds.waterLevelThreshold).to.equal(5.0);
+      expect(policy.triggerThresholds.measurementPeriod).to.equal(3);
+      expect(policy.triggerThresholds.activeWindow.start.toNumber()).to.equal(151 * 86400);
+    });
+
+    it("Should not close an unpaid policy before its premium deadline", async () => {
+      const policy = await program.account.climatePolicy.fetch(cancelPolicyPda);
+      expect(policy.premiumDeadline.toNumber()).to.equal(
+        policy.startTimestamp.toNumber() + 7 * 24 * 3600
+      );
+
+      const [cancelReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), cancelPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .closeUnpaidPolicy(cancelPolicyId)
+          .accounts({
+            closer: authority.publicKey,
+            owner: policyOwner.publicKey,
+            policy: cancelPolicyPda,
+            climateReadings: cancelReadingsPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            globalState: globalStatePda,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PremiumDeadlineNotReached");
+      }
+    });
+
+    it("Should cancel an unpaid policy without a refund", async () => {
+      const balanceBefore = await getAccount(
+        provider.connection,
+        userTokenAccount
+      );
+      const region = regionExposurePda({ latitude: 40.7128, longitude: -74.006 });
+      const regionBefore = await program.account.regionExposure.fetch(region);
+
+      await program.methods
+        .cancelPolicy(cancelPolicyId)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: cancelPolicyPda,
+          regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+          policyRegistry: policyRegistryPda,
+          ownerTokenAccount: userTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(
+        cancelPolicyPda
+      );
+      expect(policy.status).to.deep.equal({ expired: {} });
+
+      // The cancelled policy leaves its region as soon as it settles
+      const regionAfter = await program.account.regionExposure.fetch(region);
+      expect(regionBefore.totalCoverage.sub(regionAfter.totalCoverage).eq(policy.coverageAmount)).to.be.true;
+      expect(regionAfter.policyCount).to.equal(regionBefore.policyCount - 1);
+
+      const registry = await program.account.policyRegistry.fetch(policyRegistryPda);
+      expect(registry.policyIds.map((id) => id.toNumber())).to.not.include(3);
+
+      const balanceAfter = await getAccount(
+        provider.connection,
+        userTokenAccount
+      );
+      expect(balanceAfter.amount).to.equal(balanceBefore.amount);
+    });
+
+    it("Should reject cancelling an already expired policy", async () => {
+      try {
+        await program.methods
+          .cancelPolicy(cancelPolicyId)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: cancelPolicyPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            policyRegistry: policyRegistryPda,
+            ownerTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyNotCancellable");
+      }
+    });
+
+    it("Should reject a no-data refund before the policy ends", async () => {
+      try {
+        await program.methods
+          .refundOnNoData(cancelPolicyId)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: cancelPolicyPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            policyRegistry: policyRegistryPda,
+            ownerTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyNotYetExpired");
+      }
+    });
+
+    it("Should close an expired policy and reclaim its rent", async () => {
+      const [cancelReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), cancelPolicyPda.toBuffer()],
+        program.programId
+      );
+      const lamportsBefore = await provider.connection.getBalance(policyOwner.publicKey);
+
+      await program.methods
+        .closePolicy(cancelPolicyId)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: cancelPolicyPda,
+          policyToken: null,
+          climateReadings: cancelReadingsPda,
+          policyRegistry: policyRegistryPda,
+          globalState: globalStatePda,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      expect(await program.account.climatePolicy.fetchNullable(cancelPolicyPda)).to.be.null;
+      const lamportsAfter = await provider.connection.getBalance(policyOwner.publicKey);
+      expect(lamportsAfter).to.be.greaterThan(lamportsBefore);
+    });
+  });
+
+  describe("Policy Transfer", () => {
+    const transferPolicyId = new BN(10);
+    const buyer = Keypair.generate();
+    let transferPolicyPda: PublicKey;
+    let buyerRegistryPda: PublicKey;
+
+    before(async () => {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          buyer.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+
+      [transferPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          transferPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      [buyerRegistryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("registry"), buyer.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: transferPolicyId,
+          policyType: { droughtProtection: {} },
+          geographicBounds: {
+            latitude: 40.7128,
+            longitude: -74.006,
+            radius: 50.0,
+          },
+          triggerConditions: {
+            rainfallThreshold: 10.0,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 7,
+            minimumDuration: 24,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: new BN(100 * 10 ** 6),
+          premiumAmount: new BN(1 * 10 ** 6),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: transferPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: PublicKey.findProgramAddressSync(
+            [Buffer.from("readings"), transferPolicyPda.toBuffer()],
+            program.programId
+          )[0],
+          regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+
+      await program.methods
+        .offerPolicyTransfer(transferPolicyId, buyer.publicKey)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: transferPolicyPda,
+          globalState: globalStatePda,
+        })
+        .signers([policyOwner])
+        .rpc();
+    });
+
+    const acceptTransfer = (newOwner: Keypair) =>
+      program.methods
+        .acceptPolicyTransfer(transferPolicyId)
+        .accounts({
+          newOwner: newOwner.publicKey,
+          policy: transferPolicyPda,
+          ownerRegistry: policyRegistryPda,
+          newOwnerRegistry: PublicKey.findProgramAddressSync(
+            [Buffer.from("registry"), newOwner.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([newOwner])
+        .rpc();
+
+    it("Should only let the offered owner accept", async () => {
+      const policy = await program.account.climatePolicy.fetch(transferPolicyPda);
+      expect(policy.pendingOwner.equals(buyer.publicKey)).to.be.true;
+
+      try {
+        await acceptTransfer(oracleProvider);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should hand the policy and its registry entry to the new owner", async () => {
+      await acceptTransfer(buyer);
+
+      const policy = await program.account.climatePolicy.fetch(transferPolicyPda);
+      expect(policy.owner.equals(buyer.publicKey)).to.be.true;
+      expect(policy.creator.equals(policyOwner.publicKey)).to.be.true;
+      expect(policy.beneficiary.equals(buyer.publicKey)).to.be.true;
+      expect(policy.pendingOwner).to.be.null;
+
+      const sellerRegistry = await program.account.policyRegistry.fetch(policyRegistryPda);
+      expect(sellerRegistry.policyIds.map((id) => id.toNumber())).to.not.include(10);
+      const buyerRegistry = await program.account.policyRegistry.fetch(buyerRegistryPda);
+      expect(buyerRegistry.policyIds.map((id) => id.toNumber())).to.deep.equal([10]);
+    });
+
+    it("Should gate owner actions on the new owner", async () => {
+      const updateBeneficiary = (signer: Keypair) =>
+        program.methods
+          .updateBeneficiary(transferPolicyId, signer.publicKey)
+          .accounts({
+            owner: signer.publicKey,
+            policy: transferPolicyPda,
+            globalState: globalStatePda,
+          })
+          .signers([signer])
+          .rpc();
+
+      try {
+        await updateBeneficiary(policyOwner);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+
+      await updateBeneficiary(buyer);
+    });
+  });
+
+  describe("Native SOL Policies", () => {
+    const solPolicyId = new BN(5);
+    let solPolicyPda: PublicKey;
+
+    before(async () => {
+      [solPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          solPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      const [solReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), solPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: solPolicyId,
+          policyType: { hurricaneCoverage: {} },
+          geographicBounds: {
+            latitude: 25.7617,
+            longitude: -80.1918,
+            radius: 100.0,
+          },
+          triggerConditions: {
+            rainfallThreshold: null,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: 150.0,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 3,
+            minimumDuration: 6,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: new BN(anchor.web3.LAMPORTS_PER_SOL),
+          premiumAmount: new BN(0.05 * anchor.web3.LAMPORTS_PER_SOL),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { nativeSol: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: solPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: solReadingsPda,
+          regionExposure: regionExposurePda({ latitude: 25.7617, longitude: -80.1918 }),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+    });
+
+    it("Should take a SOL premium into the risk pool PDA", async () => {
+      const poolBefore = await provider.connection.getBalance(riskPoolPda);
+      const premium = new BN(0.05 * anchor.web3.LAMPORTS_PER_SOL);
+
+      await program.methods
+        .depositPremium(solPolicyId, premium)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: solPolicyPda,
+          userTokenAccount: null,
+          riskPoolTokenAccount: null,
+          riskPoolPda: riskPoolPda,
+          feeVault: null,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const poolAfter = await provider.connection.getBalance(riskPoolPda);
+      expect(poolAfter - poolBefore).to.equal(premium.toNumber());
+
+      const globalState = await program.account.globalState.fetch(
+        globalStatePda
+      );
+      expect(globalState.solPremiumsCollected.toNumber()).to.equal(
+        premium.toNumber()
+      );
+      expect(globalState.solCoverageCommitted.toNumber()).to.equal(
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+    });
+
+    it("Should raise SOL coverage against a pro-rated premium", async () => {
+      const poolBefore = await provider.connection.getBalance(riskPoolPda);
+      const solRegionPda = regionExposurePda({ latitude: 25.7617, longitude: -80.1918 });
+      const regionBefore = await program.account.regionExposure.fetch(solRegionPda);
+
+      await program.methods
+        .increaseCoverage(solPolicyId, new BN(anchor.web3.LAMPORTS_PER_SOL))
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: solPolicyPda,
+          policyToken: null,
+          policyRegistry: policyRegistryPda,
+          regionExposure: regionExposurePda({ latitude: 25.7617, longitude: -80.1918 }),
+          userTokenAccount: null,
+          riskPoolTokenAccount: null,
+          riskPoolPda: riskPoolPda,
+          feeVault: null,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      // Doubling coverage costs the original premium again, for the term left
+      const premium = (await provider.connection.getBalance(riskPoolPda)) - poolBefore;
+      expect(premium).to.be.greaterThan(0);
+      expect(premium).to.be.at.most(0.05 * anchor.web3.LAMPORTS_PER_SOL);
+
+      const policy = await program.account.climatePolicy.fetch(solPolicyPda);
+      expect(policy.coverageAmount.toNumber()).to.equal(2 * anchor.web3.LAMPORTS_PER_SOL);
+      expect(policy.maxSinglePayout.toNumber()).to.equal(2 * anchor.web3.LAMPORTS_PER_SOL);
+      expect(policy.premiumAmount.toNumber()).to.equal(0.05 * anchor.web3.LAMPORTS_PER_SOL + premium);
+      // A full term of the doubled coverage costs twice the original premium
+      expect(policy.fullTermPremium.toNumber()).to.equal(0.1 * anchor.web3.LAMPORTS_PER_SOL);
+
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.solCoverageCommitted.toNumber()).to.equal(
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+
+      // The region counts the extra coverage but not a second policy
+      const regionAfter = await program.account.regionExposure.fetch(solRegionPda);
+      expect(regionAfter.solTotalCoverage.toNumber()).to.equal(
+        regionBefore.solTotalCoverage.toNumber() + anchor.web3.LAMPORTS_PER_SOL
+      );
+      expect(regionAfter.policyCount).to.equal(regionBefore.policyCount);
+    });
+
+    it("Should refund SOL on cancellation", async () => {
+      const ownerBefore = await provider.connection.getBalance(
+        policyOwner.publicKey
+      );
+
+      await program.methods
+        .cancelPolicy(solPolicyId)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: solPolicyPda,
+          regionExposure: regionExposurePda({ latitude: 25.7617, longitude: -80.1918 }),
+          policyRegistry: policyRegistryPda,
+          ownerTokenAccount: null,
+          riskPoolTokenAccount: null,
+          riskPoolPda: riskPoolPda,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(solPolicyPda);
+      expect(policy.status).to.deep.equal({ expired: {} });
+
+      const ownerAfter = await provider.connection.getBalance(
+        policyOwner.publicKey
+      );
+      expect(ownerAfter).to.be.greaterThan(ownerBefore);
+
+      const globalState = await program.account.globalState.fetch(
+        globalStatePda
+      );
+      expect(globalState.solCoverageCommitted.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Premium Installments", () => {
+    const installmentPolicyId = new BN(14);
+    const bounds = { latitude: -1.2921, longitude: 36.8219, radius: 50.0 };
+    let installmentPolicyPda: PublicKey;
+    let installmentReadingsPda: PublicKey;
+
+    const depositInstallment = (amount: number) =>
+      program.methods
+        .depositPremium(installmentPolicyId, new BN(amount))
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: installmentPolicyPda,
+          userTokenAccount: userTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          feeVault: feeVaultPda,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+    before(async () => {
+      [installmentPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          installmentPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      [installmentReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), installmentPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      // 4 USDC up front, the remaining 6 USDC due a few seconds later
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .createClimatePolicy({
+          policyId: installmentPolicyId,
+          policyType: { hurricaneCoverage: {} },
+          geographicBounds: bounds,
+          triggerConditions: {
+            rainfallThreshold: null,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: 150.0,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 3,
+            minimumDuration: 6,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: new BN(1000 * 10 ** 6),
+          premiumAmount: new BN(10 * 10 ** 6),
+          endTimestamp: new BN(now + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [
+            { dueTimestamp: new BN(now), amount: new BN(4 * 10 ** 6) },
+            { dueTimestamp: new BN(now + 3), amount: new BN(6 * 10 ** 6) },
+          ],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: installmentPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: installmentReadingsPda,
+          regionExposure: regionExposurePda(bounds),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+    });
+
+    it("Should activate once the first installment clears", async () => {
+      try {
+        await depositInstallment(3 * 10 ** 6);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientPremium");
+      }
+
+      await depositInstallment(4 * 10 ** 6);
+
+      const policy = await program.account.climatePolicy.fetch(installmentPolicyPda);
+      expect(policy.status).to.deep.equal({ active: {} });
+      expect(policy.premiumPaidSoFar.toNumber()).to.equal(4 * 10 ** 6);
+      expect(policy.premiumAmount.toNumber()).to.equal(10 * 10 ** 6);
+    });
+
+    it("Should lapse on a missed installment until caught up", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      await program.methods
+        .evaluateClimateTrigger(installmentPolicyId)
+        .accounts({
+          evaluator: policyOwner.publicKey,
+          policy: installmentPolicyPda,
+          climateReadings: installmentReadingsPda,
+          globalState: globalStatePda,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      let policy = await program.account.climatePolicy.fetch(installmentPolicyPda);
+      expect(policy.status).to.deep.equal({ lapsed: {} });
+
+      // Paying more than the premium left is rejected
+      try {
+        await depositInstallment(7 * 10 ** 6);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidPremiumAmount");
+      }
+
+      await depositInstallment(6 * 10 ** 6);
+
+      policy = await program.account.climatePolicy.fetch(installmentPolicyPda);
+      expect(policy.status).to.deep.equal({ monitoring: {} });
+      expect(policy.premiumPaidSoFar.toNumber()).to.equal(10 * 10 ** 6);
+    });
+  });
+
+  describe("Tokenized Policies", () => {
+    const nftPolicyId = new BN(6);
+    let nftPolicyPda: PublicKey;
+    let policyMintPda: PublicKey;
+    let buyer: Keypair;
+    let buyerTokenAccount: PublicKey;
+    let buyerPolicyToken: PublicKey;
+
+    before(async () => {
+      [nftPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          nftPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      const [nftReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), nftPolicyPda.toBuffer()],
+        program.programId
+      );
+      [policyMintPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("policy_mint"), nftPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: nftPolicyId,
+          policyType: { floodInsurance: {} },
+          geographicBounds: {
+            latitude: 29.9511,
+            longitude: -90.0715,
+            radius: 50.0,
+          },
+          triggerConditions: {
+            rainfallThreshold: 200.0,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: 3.0,
+            fireProximityThreshold: null,
+            measurementPeriod: 3,
+            minimumDuration: 12,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: new BN(1000 * 10 ** 6),
+          premiumAmount: new BN(10 * 10 ** 6),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: nftPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: nftReadingsPda,
+          regionExposure: regionExposurePda({ latitude: 29.9511, longitude: -90.0715 }),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+
+      buyer = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          buyer.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+      buyerTokenAccount = await createAccount(
+        provider.connection,
+        buyer,
+        mint,
+        buyer.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        authority,
+        mint,
+        buyerTokenAccount,
+        authority,
+        10 * 10 ** 6
+      );
+    });
+
+    it("Should mint a single policy token to the owner", async () => {
+      const ownerPolicyToken = getAssociatedTokenAddressSync(
+        policyMintPda,
+        policyOwner.publicKey
+      );
+
+      await program.methods
+        .initPolicyNft(nftPolicyId)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: nftPolicyPda,
+          policyMint: policyMintPda,
+          ownerPolicyToken: ownerPolicyToken,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(nftPolicyPda);
+      expect(policy.policyMint.equals(policyMintPda)).to.be.true;
+
+      const policyMint = await getMint(provider.connection, policyMintPda);
+      expect(Number(policyMint.supply)).to.equal(1);
+      expect(policyMint.mintAuthority).to.be.null;
+
+      // Sell the coverage on to the buyer
+      buyerPolicyToken = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          buyer,
+          policyMintPda,
+          buyer.publicKey
+        )
+      ).address;
+      await transfer(
+        provider.connection,
+        policyOwner,
+        ownerPolicyToken,
+        buyerPolicyToken,
+        policyOwner,
+        1
+      );
+    });
+
+    it("Should reject a premium from the former owner", async () => {
+      try {
+        await program.methods
+          .depositPremium(nftPolicyId, new BN(10 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: nftPolicyPda,
+            policyToken: getAssociatedTokenAddressSync(
+              policyMintPda,
+              policyOwner.publicKey
+            ),
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("NotPolicyHolder");
+      }
+    });
+
+    it("Should accept a premium from the token holder", async () => {
+      await program.methods
+        .depositPremium(nftPolicyId, new BN(10 * 10 ** 6))
+        .accounts({
+          owner: buyer.publicKey,
+          policy: nftPolicyPda,
+          policyToken: buyerPolicyToken,
+          userTokenAccount: buyerTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          feeVault: feeVaultPda,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(nftPolicyPda);
+      expect(policy.status).to.deep.equal({ active: {} });
+    });
+
+    it("Should refuse to evaluate without fresh oracle data", async () => {
+      const [nftReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), nftPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .evaluateClimateTrigger(nftPolicyId)
+          .accounts({
+            evaluator: buyer.publicKey,
+            policy: nftPolicyPda,
+            climateReadings: nftReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([buyer])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("StaleOracleData");
+      }
+    });
+
+    it("Should let only the token holder tune the monitoring frequency", async () => {
+      try {
+        await program.methods
+          .setMonitoringFrequency(nftPolicyId, 86_400)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: nftPolicyPda,
+            policyToken: getAssociatedTokenAddressSync(
+              policyMintPda,
+              policyOwner.publicKey
+            ),
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("NotPolicyHolder");
+      }
+
+      await program.methods
+        .setMonitoringFrequency(nftPolicyId, 86_400)
+        .accounts({
+          owner: buyer.publicKey,
+          policy: nftPolicyPda,
+          policyToken: buyerPolicyToken,
+          globalState: globalStatePda,
+        })
+        .signers([buyer])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(nftPolicyPda);
+      expect(policy.monitoringFrequency).to.equal(86_400);
+    });
+
+    it("Should refuse to redirect a tokenized policy's payouts", async () => {
+      try {
+        await program.methods
+          .updateBeneficiary(nftPolicyId, policyOwner.publicKey)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: nftPolicyPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyIsTokenized");
+      }
+    });
+  });
+
+  describe("Reinsurance", () => {
+    let reinsurer: Keypair;
+    let reinsurerTokenAccount: PublicKey;
+    let positionPda: PublicKey;
+
+    before(async () => {
+      reinsurer = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          reinsurer.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+
+      reinsurerTokenAccount = await createAccount(
+        provider.connection,
+        reinsurer,
+        mint,
+        reinsurer.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        authority,
+        mint,
+        reinsurerTokenAccount,
+        authority,
+        500 * 10 ** 6
+      );
+
+      [positionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reinsurance"), reinsurer.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    const accounts = () => ({
+      reinsurer: reinsurer.publicKey,
+      reinsurancePosition: positionPda,
+      reinsurerTokenAccount: reinsurerTokenAccount,
+      riskPoolTokenAccount: riskPoolTokenAccount,
+      riskPoolPda: riskPoolPda,
+      globalState: globalStatePda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    it("Should deposit reinsurance capital into the risk pool", async () => {
+      const before = await program.account.globalState.fetch(globalStatePda);
+
+      await program.methods
+        .depositReinsurance(new BN(500 * 10 ** 6))
+        .accounts(accounts())
+        .signers([reinsurer])
+        .rpc();
+
+      const position = await program.account.reinsurancePosition.fetch(positionPda);
+      expect(position.reinsurer.equals(reinsurer.publicKey)).to.be.true;
+      expect(position.amount.toNumber()).to.equal(500 * 10 ** 6);
+
+      const after = await program.account.globalState.fetch(globalStatePda);
+      expect(after.totalReinsurance.sub(before.totalReinsurance).toNumber()).to.equal(
+        500 * 10 ** 6
+      );
+    });
+
+    it("Should withdraw uncommitted reinsurance capital", async () => {
+      await program.methods
+        .withdrawReinsurance(new BN(200 * 10 ** 6))
+        .accounts(accounts())
+        .signers([reinsurer])
+        .rpc();
+
+      const position = await program.account.reinsurancePosition.fetch(positionPda);
+      expect(position.amount.toNumber()).to.equal(300 * 10 ** 6);
+
+      const tokenAccount = await getAccount(provider.connection, reinsurerTokenAccount);
+      expect(Number(tokenAccount.amount)).to.equal(200 * 10 ** 6);
+    });
+
+    it("Should reject withdrawing more than the position", async () => {
+      try {
+        await program.methods
+          .withdrawReinsurance(new BN(301 * 10 ** 6))
+          .accounts(accounts())
+          .signers([reinsurer])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientReinsurance");
+      }
+    });
+  });
+
+  describe("Oracle Data Management", () => {
+    let oracleDataPda: PublicKey;
+    let oracleDataBump: number;
+
+    before(async () => {
+      [oracleDataPda, oracleDataBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Should register the oracle as active", async () => {
+      const oracleData = await program.account.oracleData.fetch(oracleDataPda);
+      expect(oracleData.provider.equals(oracleProvider.publicKey)).to.be.true;
+      expect(oracleData.isActive).to.be.true;
+      expect(oracleData.reputationScore).to.equal(100);
+
+      const [oracleBondPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_bond"), oracleProvider.publicKey.toBuffer()],
+        program.programId
+      );
+      const oracleBond = await program.account.oracleBond.fetch(oracleBondPda);
+      expect(oracleBond.amount.toNumber()).to.equal(bondAmount);
+    });
+
+    it("Should reject a policy whose oracles can't report its data types", async () => {
+      const windProvider = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          windProvider.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+
+      await program.methods
+        .registerOracle({
+          oracleType: { weatherStation: {} },
+          supportedDataTypes: [{ windSpeed: {} }],
+          minSubmissionInterval: 0,
+          initialReputation: 100,
+          bondAmount: new BN(bondAmount),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          provider: windProvider.publicKey,
+          oracleData: oracleDataPdaFor(windProvider.publicKey),
+          ...(await bondAccounts(windProvider)),
+          premiumMint: mint,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority, windProvider])
+        .rpc();
+
+      // A sea level policy is judged on water level, which this oracle never reports
+      const mismatchedParams = {
+        policyId: new BN(2),
+        policyType: { seaLevelRise: {} },
+        geographicBounds: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          radius: 50.0,
+        },
+        triggerConditions: {
+          rainfallThreshold: null,
+          temperatureThreshold: null,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: 2.0,
+          fireProximityThreshold: null,
+          measurementPeriod: 7,
+          minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources: [windProvider.publicKey],
+        coverageAmount: new BN(10000 * 10 ** 6),
+        premiumAmount: new BN(100 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
+      };
+
+      const [mismatchedPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy(mismatchedParams)
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: mismatchedPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(mismatchedParams.geographicBounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(mismatchedParams.oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("OracleCapabilityMismatch");
+      }
+    });
+
+    it("Should reject readings outside an oracle's supported data types", async () => {
+      // A fire-detection-only satellite feed alongside the general oracle
+      const fireProvider = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          fireProvider.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+
+      await program.methods
+        .registerOracle({
+          oracleType: { nasaModis: {} },
+          supportedDataTypes: [{ fireDetection: {} }],
+          minSubmissionInterval: 0,
+          initialReputation: 100,
+          bondAmount: new BN(bondAmount),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          provider: fireProvider.publicKey,
+          oracleData: oracleDataPdaFor(fireProvider.publicKey),
+          ...(await bondAccounts(fireProvider)),
+          premiumMint: mint,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority, fireProvider])
+        .rpc();
+
+      const wildfirePolicyId = new BN(12);
+      const bounds = { latitude: 34.0522, longitude: -118.2437, radius: 50.0 };
+      const [wildfirePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          wildfirePolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      const [wildfireReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), wildfirePolicyPda.toBuffer()],
+        program.programId
+      );
+      const oracleSources = [oracleProvider.publicKey, fireProvider.publicKey];
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: wildfirePolicyId,
+          policyType: { wildfireProtection: {} },
+          geographicBounds: bounds,
+          triggerConditions: {
+            rainfallThreshold: null,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: null,
+            fireProximityThreshold: 5.0,
+            measurementPeriod: 1,
+            minimumDuration: 1,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources,
+          coverageAmount: new BN(1000 * 10 ** 6),
+          premiumAmount: new BN(10 * 10 ** 6),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: null,
+          fxOracle: null,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: wildfirePolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: wildfireReadingsPda,
+          regionExposure: regionExposurePda(bounds),
+          fxRate: null,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts(oracleSources))
+        .signers([policyOwner])
+        .rpc();
+
+      try {
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 45.0,
+              timestamp: new BN(Math.floor(Date.now() / 1000)),
+              confidenceLevel: 95,
+              sourceId: fireProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: fireProvider.publicKey,
+            oracleData: oracleDataPdaFor(fireProvider.publicKey),
+            policy: wildfirePolicyPda,
+            climateReadings: wildfireReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([fireProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("UnsupportedDataType");
+      }
+    });
+
+    it("Should only trigger cross-validated policies on two oracle types", async () => {
+      // A ground station confirming the general (Chainlink) oracle's heat readings
+      const groundProvider = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          groundProvider.publicKey,
+          anchor.web3.LAMPORTS_PER_SOL
+        ),
+        "confirmed"
+      );
+
+      await program.methods
+        .registerOracle({
+          oracleType: { weatherStation: {} },
+          supportedDataTypes: [{ temperature: {} }],
+          minSubmissionInterval: 3600,
+          initialReputation: 100,
+          bondAmount: new BN(bondAmount),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          provider: groundProvider.publicKey,
+          oracleData: oracleDataPdaFor(groundProvider.publicKey),
+          ...(await bondAccounts(groundProvider)),
+          premiumMint: mint,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority, groundProvider])
+        .rpc();
+
+      const crossPolicyId = new BN(13);
+      const bounds = { latitude: 41.8781, longitude: -87.6298, radius: 50.0 };
+      const [crossPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          crossPolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      const [crossReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), crossPolicyPda.toBuffer()],
+        program.programId
+      );
+      const crossParams = (oracleSources: PublicKey[]) => ({
+        policyId: crossPolicyId,
+        policyType: { extremeTemperature: {} },
+        geographicBounds: bounds,
+        triggerConditions: {
+          rainfallThreshold: null,
+          temperatureThreshold: 40.0,
+          coldTemperatureThreshold: null,
+          windSpeedThreshold: null,
+          waterLevelThreshold: null,
+          fireProximityThreshold: null,
+          measurementPeriod: 1,
+          minimumDuration: 1,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
+        },
+        oracleSources,
+        coverageAmount: new BN(1000 * 10 ** 6),
+        premiumAmount: new BN(10 * 10 ** 6),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: true,
+        premiumSchedule: [],
+      });
+      const createCrossPolicy = (oracleSources: PublicKey[]) =>
+        program.methods
+          .createClimatePolicy(crossParams(oracleSources))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: crossPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: crossReadingsPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts(oracleSources))
+          .signers([policyOwner])
+          .rpc();
+
+      // A single oracle type can never cross-validate its own readings
+      try {
+        await createCrossPolicy([oracleProvider.publicKey]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("OracleCapabilityMismatch");
+      }
+
+      await createCrossPolicy([oracleProvider.publicKey, groundProvider.publicKey]);
+
+      const submitHeat = (oracle: Keypair) =>
+        program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 44.0,
+              timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+              confidenceLevel: 95,
+              sourceId: oracle.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracle.publicKey,
+            oracleData: oracleDataPdaFor(oracle.publicKey),
+            policy: crossPolicyPda,
+            climateReadings: crossReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracle])
+          .rpc();
+      const previewPayout = () =>
+        program.methods
+          .dryRunPayout(crossPolicyId)
+          .accounts({
+            policy: crossPolicyPda,
+            climateReadings: crossReadingsPda,
+            globalState: globalStatePda,
+          })
+          .view();
+
+      // The Chainlink feed alone meets consensus but not cross validation
+      await submitHeat(oracleProvider);
+      expect((await previewPayout()).toNumber()).to.equal(0);
+
+      // Once the ground station agrees, 44.0 is 1000 bps past the threshold
+      await submitHeat(groundProvider);
+      expect((await previewPayout()).toNumber()).to.equal(100 * 10 ** 6);
+
+      // The ground station reports hourly, so a second batch is too soon
+      try {
+        await submitHeat(groundProvider);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SubmissionTooFrequent");
+      }
+    });
+
+    it("Should reject oracle registration by non-authority", async () => {
+      const rogueProvider = Keypair.generate();
+      const [rogueOraclePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle"), rogueProvider.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .registerOracle({
+            oracleType: { iotSensor: {} },
+            supportedDataTypes: [{ waterLevel: {} }],
+            minSubmissionInterval: 0,
+            initialReputation: 100,
+            bondAmount: new BN(bondAmount),
+          })
+          .accounts({
+            authority: policyOwner.publicKey,
+            provider: rogueProvider.publicKey,
+            oracleData: rogueOraclePda,
+            ...(await bondAccounts(rogueProvider)),
+            premiumMint: mint,
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([policyOwner, rogueProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should submit climate data from oracle", async () => {
+      const dataPoints = [
+        {
+          dataType: { temperature: {} },
+          unit: { celsius: {} },
+          location: {
+            latitude: 40.7128,
+            longitude: -74.006,
+            altitude: null,
+          },
+          value: 25.5,
+          timestamp: new BN(Math.floor(Date.now() / 1000)),
+          confidenceLevel: 95,
+          sourceId: oracleProvider.publicKey,
+        },
+        {
+          dataType: { rainfall: {} },
+          unit: { millimeters: {} },
+          location: {
+            latitude: 40.7128,
+            longitude: -74.006,
+            altitude: null,
+          },
+          value: 5.2,
+          timestamp: new BN(Math.floor(Date.now() / 1000)),
+          confidenceLevel: 88,
+          sourceId: oracleProvider.publicKey,
+        },
+      ].map(withVerificationHash);
+
+      try {
+        const tx = await program.methods
+          .submitClimateData(dataPoints)
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        console.log("Submit climate data transaction signature:", tx);
+      } catch (error) {
+        console.log("Expected error due to mock oracle setup:", error.message);
+      }
+    });
+
+    it("Should reject a verification hash that does not match the reading", async () => {
+      const reading = withVerificationHash({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value: 25.5,
+        timestamp: new BN(Math.floor(Date.now() / 1000)),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+      });
+
+      try {
+        await program.methods
+          .submitClimateData([{ ...reading, value: 12.0 }]) // Hash covers 25.5
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidVerificationHash");
+      }
+    });
+
+    it("Should reject readings in the wrong unit for their data type", async () => {
+      try {
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { fahrenheit: {} }, // Temperature thresholds are in Celsius
+              location: {
+                latitude: 40.7128,
+                longitude: -74.006,
+                altitude: null,
+              },
+              value: 77.9,
+              timestamp: new BN(Math.floor(Date.now() / 1000)),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMeasurementUnit");
+      }
+    });
+
+    it("Should reject readings attributed to another oracle", async () => {
+      const impersonated = Keypair.generate().publicKey;
+      try {
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: {
+                latitude: 40.7128,
+                longitude: -74.006,
+                altitude: null,
+              },
+              value: 25.5,
+              timestamp: new BN(Math.floor(Date.now() / 1000)),
+              confidenceLevel: 95,
+              sourceId: impersonated,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOracleData");
+        expect(error.logs.join("\n")).to.include(
+          `Data point attributed to ${impersonated.toBase58()} was submitted by oracle ${oracleProvider.publicKey.toBase58()}`
+        );
+      }
+    });
+
+    it("Should publish an FX rate for denominated coverage", async () => {
+      const denomination = Array.from(Buffer.from("KES"));
+      const [fxRatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fx_rate"), oracleProvider.publicKey.toBuffer(), Buffer.from("KES")],
+        program.programId
+      );
+
+      // 0.0077 premium mint units per shilling
+      await program.methods
+        .submitFxRate(denomination, new BN(7_700))
+        .accounts({
+          oracleProvider: oracleProvider.publicKey,
+          oracleData: oracleDataPda,
+          fxRate: fxRatePda,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracleProvider])
+        .rpc();
+
+      const fxRate = await program.account.fxRate.fetch(fxRatePda);
+      expect(fxRate.provider.equals(oracleProvider.publicKey)).to.be.true;
+      expect(Buffer.from(fxRate.denomination).toString()).to.equal("KES");
+      expect(fxRate.rate.toNumber()).to.equal(7_700);
+      expect(fxRate.updatedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Should commit denominated coverage in the premium mint at a margin over the FX rate", async () => {
+      const kesPolicyId = new BN(21);
+      const nairobi = { latitude: -1.2921, longitude: 36.8219 };
+      const [fxRatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fx_rate"), oracleProvider.publicKey.toBuffer(), Buffer.from("KES")],
+        program.programId
+      );
+      const [kesPolicyPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("policy"), policyOwner.publicKey.toBuffer(), kesPolicyId.toArray("le", 8)],
+        program.programId
+      );
+      const [kesReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), kesPolicyPda.toBuffer()],
+        program.programId
+      );
+
+      // One billion shillings at 0.0077, plus the 20% margin, rounded up
+      const coverage = new BN(1_000_000_000);
+      const committed = 9_240_000;
+
+      await program.methods
+        .createClimatePolicy({
+          policyId: kesPolicyId,
+          policyType: { floodInsurance: {} },
+          geographicBounds: { ...nairobi, radius: 50.0 },
+          triggerConditions: {
+            rainfallThreshold: 100.0,
+            temperatureThreshold: null,
+            coldTemperatureThreshold: null,
+            windSpeedThreshold: null,
+            waterLevelThreshold: null,
+            fireProximityThreshold: null,
+            measurementPeriod: 7,
+            minimumDuration: 24,
+            compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+            triggerLogic: { anyOf: {} },
+            resetBandBps: 0,
+            activeWindow: null,
+          },
+          oracleSources: [oracleProvider.publicKey],
+          coverageAmount: coverage,
+          premiumAmount: new BN(1 * 10 ** 6),
+          endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+          payoutFormula: { linearScale: {} },
+          decayConstant: 0,
+          minOracleConsensus: 1,
+          minConfidence: null,
+          monitoringFrequency: null,
+          altitudeRange: null,
+          maxSinglePayout: null,
+          deductibleAmount: new BN(0),
+          beneficiary: null,
+          paymentMode: { splToken: {} },
+          coverageDenomination: Array.from(Buffer.from("KES")),
+          fxOracle: oracleProvider.publicKey,
+          requireCrossValidation: false,
+          premiumSchedule: [],
+        })
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: kesPolicyPda,
+          policyRegistry: policyRegistryPda,
+          climateReadings: kesReadingsPda,
+          regionExposure: regionExposurePda(nairobi),
+          fxRate: fxRatePda,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(oracleSourceAccounts())
+        .signers([policyOwner])
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(kesPolicyPda);
+      expect(policy.coverageAmount.eq(coverage)).to.be.true;
+      expect(policy.commitmentFxRate.toNumber()).to.equal(9_240);
+      const region = await program.account.regionExposure.fetch(regionExposurePda(nairobi));
+      expect(region.totalCoverage.toNumber()).to.equal(committed);
+
+      const committedBefore = (await program.account.globalState.fetch(globalStatePda))
+        .totalCoverageCommitted;
+      await program.methods
+        .depositPremium(kesPolicyId, new BN(1 * 10 ** 6))
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: kesPolicyPda,
+          policyToken: null,
+          userTokenAccount: userTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          feeVault: feeVaultPda,
+          epochStats: epochStatsPda(),
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([policyOwner])
+        .rpc();
+      let globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.totalCoverageCommitted.sub(committedBefore).toNumber()).to.equal(committed);
+
+      // Cancelling releases the same mint-denominated amount it committed
+      await program.methods
+        .cancelPolicy(kesPolicyId)
+        .accounts({
+          owner: policyOwner.publicKey,
+          policy: kesPolicyPda,
+          policyToken: null,
+          policyRegistry: policyRegistryPda,
+          regionExposure: regionExposurePda(nairobi),
+          ownerTokenAccount: userTokenAccount,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          riskPoolPda: riskPoolPda,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([policyOwner])
+        .rpc();
+      globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.totalCoverageCommitted.eq(committedBefore)).to.be.true;
+      const regionAfter = await program.account.regionExposure.fetch(regionExposurePda(nairobi));
+      expect(regionAfter.totalCoverage.toNumber()).to.equal(0);
+    });
+
+    it("Should average confidence over a full batch without overflow", async () => {
+      const before = await program.account.oracleData.fetch(oracleDataPda);
+      const dataPoints = Array.from({ length: 10 }, (_, i) => withVerificationHash({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value: 25.0 + i,
+        timestamp: new BN(Math.floor(Date.now() / 1000)),
+        confidenceLevel: 100,
+        sourceId: oracleProvider.publicKey,
+      }));
+
+      await program.methods
+        .submitClimateData(dataPoints)
+        .accounts({
+          oracleProvider: oracleProvider.publicKey,
+          oracleData: oracleDataPda,
+          policy: policyPda,
+          climateReadings: climateReadingsPda,
+          globalState: globalStatePda,
+        })
+        .signers([oracleProvider])
+        .rpc();
+
+      // Ten points at 100 average to 100, pulling reputation 20% of the way there
+      const oracleData = await program.account.oracleData.fetch(oracleDataPda);
+      expect(oracleData.dataPointsCount).to.equal(before.dataPointsCount + 10);
+      expect(oracleData.reputationScore).to.equal(
+        Math.round(before.reputationScore * 0.8 + 100 * 0.2)
+      );
+    });
+
+    it("Should block unregistering during the bond cooldown", async () => {
+      try {
+        await program.methods
+          .unregisterOracle()
+          .accounts({
+            provider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            ...oracleBondAccounts,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("BondCooldownActive");
+      }
+    });
+
+    it("Should slash and deactivate a disputed oracle", async () => {
+      const disputedProvider = Keypair.generate();
+      const [disputedOraclePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle"), disputedProvider.publicKey.toBuffer()],
+        program.programId
+      );
+      const [disputePda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dispute"),
+          disputedProvider.publicKey.toBuffer(),
+          new BN(0).toArrayLike(Buffer, "le", 4),
+        ],
+        program.programId
+      );
+
+      const disputedBond = await bondAccounts(disputedProvider);
+
+      await program.methods
+        .registerOracle({
+          oracleType: { iotSensor: {} },
+          supportedDataTypes: [{ waterLevel: {} }],
+          minSubmissionInterval: 0,
+          initialReputation: 60,
+          bondAmount: new BN(bondAmount),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          provider: disputedProvider.publicKey,
+          oracleData: disputedOraclePda,
+          ...disputedBond,
+          premiumMint: mint,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority, disputedProvider])
+        .rpc();
+
+      await program.methods
+        .raiseOracleDispute()
+        .accounts({
+          authority: authority.publicKey,
+          oracleData: disputedOraclePda,
+          globalState: globalStatePda,
+        })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await program.methods
+          .unregisterOracle()
+          .accounts({
+            provider: disputedProvider.publicKey,
+            oracleData: disputedOraclePda,
+            ...disputedBond,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([disputedProvider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("OracleDisputePending");
+      }
+
+      const poolBefore = await getAccount(provider.connection, riskPoolTokenAccount);
+
+      await program.methods
+        .disputeOracleData({
+          policy: policyPda,
+          slashAmount: 50,
+          bondSlashAmount: new BN(4 * 10 ** 6),
+          evidenceHash: Array(32).fill(7),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          oracleData: disputedOraclePda,
+          dispute: disputePda,
+          oracleBond: disputedBond.oracleBond,
+          bondVault: disputedBond.bondVault,
+          riskPoolTokenAccount: riskPoolTokenAccount,
+          globalState: globalStatePda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const poolAfter = await getAccount(provider.connection, riskPoolTokenAccount);
+      expect(Number(poolAfter.amount - poolBefore.amount)).to.equal(4 * 10 ** 6);
+      const bond = await program.account.oracleBond.fetch(disputedBond.oracleBond);
+      expect(bond.amount.toNumber()).to.equal(6 * 10 ** 6);
+
+      const oracleData = await program.account.oracleData.fetch(disputedOraclePda);
+      expect(oracleData.reputationScore).to.equal(10);
+      expect(oracleData.isActive).to.be.false;
+      expect(oracleData.disputeCount).to.equal(1);
+      expect(oracleData.openDisputes).to.equal(0);
+
+      const dispute = await program.account.oracleDispute.fetch(disputePda);
+      expect(dispute.policy.equals(policyPda)).to.be.true;
+      expect(dispute.slashAmount).to.equal(50);
+      expect(dispute.reputationAfter).to.equal(10);
+      expect(dispute.bondSlashed.toNumber()).to.equal(4 * 10 ** 6);
+    });
+
+    it("Should not decay the reputation of an oracle that is reporting", async () => {
+      try {
+        await program.methods
+          .decayOracleReputation()
+          .accounts({
+            cranker: policyOwner.publicKey,
+            oracleData: oracleDataPda,
+            policy: policyPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("OracleNotStale");
+      }
+    });
+  });
+
+  describe("Trigger Evaluation and Payouts", () => {
+    const policyId = new BN(1);
+
+    describe("Breach persistence", () => {
+      const spikePolicyId = new BN(4);
+      let spikePolicyPda: PublicKey;
+      let spikeReadingsPda: PublicKey;
+      let oracleDataPda: PublicKey;
+
+      const temperatureReading = (value: number, secondsAgo: number) => withVerificationHash({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value,
+        timestamp: new BN(Math.floor(Date.now() / 1000) - secondsAgo),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+      });
+
+      before(async () => {
+        [spikePolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            spikePolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [spikeReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), spikePolicyPda.toBuffer()],
+          program.programId
+        );
+        [oracleDataPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: spikePolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: {
+              latitude: 40.7128,
+              longitude: -74.006,
+              radius: 50.0,
+            },
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: spikePolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: spikeReadingsPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(spikePolicyId, new BN(10 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: spikePolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should keep monitoring after a spike that recovers", async () => {
+        // Spike, recovery, then a fresh spike: the run restarts at the last one
+        await program.methods
+          .submitClimateData([
+            temperatureReading(45.0, 1800),
+            temperatureReading(30.0, 1200),
+            temperatureReading(46.0, 60),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPda,
+            policy: spikePolicyPda,
+            climateReadings: spikeReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        await program.methods
+          .evaluateClimateTrigger(spikePolicyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: spikePolicyPda,
+            climateReadings: spikeReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const policy = await program.account.climatePolicy.fetch(spikePolicyPda);
+        expect(policy.status).to.deep.equal({ monitoring: {} });
+        expect(policy.breachStartedAt.toNumber()).to.be.greaterThan(
+          Math.floor(Date.now() / 1000) - 600
+        );
+        // Two breaching readings and one at 30 of a 40 threshold
+        expect(policy.riskScore).to.be.at.least(90);
+      });
+
+      it("Should preview the payout without changing the policy", async () => {
+        const before = await program.account.climatePolicy.fetch(spikePolicyPda);
+        const dryRun = program.methods
+          .dryRunPayout(spikePolicyId)
+          .accounts({
+            policy: spikePolicyPda,
+            climateReadings: spikeReadingsPda,
+            globalState: globalStatePda,
+          });
+
+        const { events } = await dryRun.simulate();
+        const preview = events.find((e) => e.name === "payoutPreviewed").data;
+        // The breach hasn't persisted for minimum_duration yet
+        expect(preview.triggered).to.be.false;
+        expect(preview.severity.temperatureBps).to.be.greaterThan(0);
+        expect(preview.riskScore).to.equal(before.riskScore);
+        expect(preview.payoutFormula).to.deep.equal(before.payoutCalculation);
+
+        const payoutAmount = await dryRun.view();
+        expect(payoutAmount.eq(preview.payoutAmount)).to.be.true;
+
+        const after = await program.account.climatePolicy.fetch(spikePolicyPda);
+        expect(after.lastDataUpdate.eq(before.lastDataUpdate)).to.be.true;
+        expect(after.status).to.deep.equal(before.status);
+      });
+
+      it("Should only accept claims on a triggered policy", async () => {
+        try {
+          await program.methods
+            .fileClaim(spikePolicyId, new BN(100 * 10 ** 6))
+            .accounts({
+              claimant: policyOwner.publicKey,
+              policy: spikePolicyPda,
+              policyToken: null,
+              globalState: globalStatePda,
+            })
+            .signers([policyOwner])
+            .rpc();
+
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("TriggerNotMet");
+        }
+
+        const policy = await program.account.climatePolicy.fetch(spikePolicyPda);
+        expect(policy.filedClaim.toNumber()).to.equal(0);
+      });
+
+      it("Should reject payouts executed by an unregistered keeper", async () => {
+        try {
+          await program.methods
+            .executeClimatePayout(spikePolicyId, new BN(1))
+            .accounts({
+              executor: oracleProvider.publicKey,
+              policy: spikePolicyPda,
+              regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+              claimLog: PublicKey.findProgramAddressSync(
+                [Buffer.from("claims"), spikePolicyPda.toBuffer()],
+                program.programId
+              )[0],
+              keeper: null,
+              policyToken: null,
+              policyholderTokenAccount: userTokenAccount,
+              fxRate: null,
+              beneficiary: null,
+              riskPoolTokenAccount: riskPoolTokenAccount,
+              riskPoolPda: riskPoolPda,
+              epochStats: epochStatsPda(),
+              globalState: globalStatePda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([oracleProvider])
+            .rpc();
+
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("NotAuthorizedKeeper");
+          expect(error.logs.join("\n")).to.include(
+            `Executor ${oracleProvider.publicKey.toBase58()} is neither a keeper nor a claimant of policy ${spikePolicyPda.toBase58()}`
+          );
+        }
+      });
+
+      it("Should reject a second evaluation within the monitoring interval", async () => {
+        try {
+          await program.methods
+            .evaluateClimateTrigger(spikePolicyId)
+            .accounts({
+              evaluator: policyOwner.publicKey,
+              policy: spikePolicyPda,
+              climateReadings: spikeReadingsPda,
+              globalState: globalStatePda,
+            })
+            .signers([policyOwner])
+            .rpc();
+
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("EvaluationTooFrequent");
+        }
+      });
+
+      it("Should skip policies not yet due in a batch evaluation", async () => {
+        const before = await program.account.climatePolicy.fetch(spikePolicyPda);
+
+        await program.methods
+          .batchEvaluateTriggers()
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            globalState: globalStatePda,
+          })
+          .remainingAccounts([
+            { pubkey: spikePolicyPda, isWritable: true, isSigner: false },
+            { pubkey: spikeReadingsPda, isWritable: false, isSigner: false },
+          ])
+          .signers([policyOwner])
+          .rpc();
+
+        const after = await program.account.climatePolicy.fetch(spikePolicyPda);
+        expect(after.lastDataUpdate.toNumber()).to.equal(before.lastDataUpdate.toNumber());
+        expect(after.status).to.deep.equal(before.status);
+      });
+    });
+
+    describe("Payouts with nothing due", () => {
+      const zeroPolicyId = new BN(17);
+      const bounds = { latitude: 33.4484, longitude: -112.074, radius: 50.0 };
+      let zeroPolicyPda: PublicKey;
+      let zeroReadingsPda: PublicKey;
+
+      const executePayout = () =>
+        program.methods
+          .executeClimatePayout(zeroPolicyId, new BN(1))
+          .accounts({
+            executor: policyOwner.publicKey,
+            policy: zeroPolicyPda,
+            regionExposure: regionExposurePda(bounds),
+            claimLog: PublicKey.findProgramAddressSync(
+              [Buffer.from("claims"), zeroPolicyPda.toBuffer()],
+              program.programId
+            )[0],
+            keeper: null,
+            policyToken: null,
+            policyholderTokenAccount: userTokenAccount,
+            fxRate: null,
+            beneficiary: null,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+      before(async () => {
+        [zeroPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            zeroPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [zeroReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), zeroPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        // 30 units of cover, so a shallow breach rounds to a zero payout
+        await program.methods
+          .createClimatePolicy({
+            policyId: zeroPolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 0,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(30),
+            premiumAmount: new BN(1),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: zeroPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: zeroReadingsPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(zeroPolicyId, new BN(1))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: zeroPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should reject a payout on a trigger that calculated nothing", async () => {
+        // 40.5 is 125 bps past the threshold: 0.375 units, rounded to zero
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 40.5,
+              timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+            policy: zeroPolicyPda,
+            climateReadings: zeroReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        await program.methods
+          .evaluateClimateTrigger(zeroPolicyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: zeroPolicyPda,
+            climateReadings: zeroReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const policy = await program.account.climatePolicy.fetch(zeroPolicyPda);
+        expect(policy.status).to.deep.equal({ triggered: {} });
+        expect(policy.pendingClaim.toNumber()).to.equal(0);
+
+        try {
+          await executePayout();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("NoPayoutDue");
+        }
+      });
+
+      it("Should reject a payout once coverage is exhausted", async () => {
+        const setPaused = (paused: boolean) =>
+          (paused ? program.methods.pauseProgram() : program.methods.unpauseProgram())
+            .accounts({
+              authority: authority.publicKey,
+              globalState: globalStatePda,
+              instructions: null,
+            })
+            .signers([authority])
+            .rpc();
+
+        await setPaused(true);
+        try {
+          await program.methods
+            .forceSettlePolicy(zeroPolicyId, { claimed: {} })
+            .accounts({
+              authority: authority.publicKey,
+              policy: zeroPolicyPda,
+              regionExposure: regionExposurePda(bounds),
+              globalState: globalStatePda,
+            })
+            .signers([authority])
+            .rpc();
+        } finally {
+          await setPaused(false);
+        }
+
+        try {
+          await executePayout();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("NoPayoutDue");
+        }
+      });
+    });
+
+    describe("Deductibles", () => {
+      const deductiblePolicyId = new BN(20);
+      const bounds = { latitude: 37.9838, longitude: 23.7275, radius: 50.0 };
+      let deductiblePolicyPda: PublicKey;
+      let deductibleReadingsPda: PublicKey;
+      let claimLogPda: PublicKey;
+
+      const setTemperatureWaitingPeriod = (seconds: number) =>
+        program.methods
+          .setWaitingPeriod({ extremeTemperature: {} }, new BN(seconds))
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      before(async () => {
+        [deductiblePolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            deductiblePolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [deductibleReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), deductiblePolicyPda.toBuffer()],
+          program.programId
+        );
+        [claimLogPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("claims"), deductiblePolicyPda.toBuffer()],
+          program.programId
+        );
+
+        // Created without a waiting period so the trigger can be paid at once
+        await setTemperatureWaitingPeriod(0);
+        try {
+          await program.methods
+            .createClimatePolicy({
+              policyId: deductiblePolicyId,
+              policyType: { extremeTemperature: {} },
+              geographicBounds: bounds,
+              triggerConditions: {
+                rainfallThreshold: null,
+                temperatureThreshold: 40.0,
+                coldTemperatureThreshold: null,
+                windSpeedThreshold: null,
+                waterLevelThreshold: null,
+                fireProximityThreshold: null,
+                measurementPeriod: 1,
+                minimumDuration: 0,
+                compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+                triggerLogic: { anyOf: {} },
+                resetBandBps: 0,
+                activeWindow: null,
+              },
+              oracleSources: [oracleProvider.publicKey],
+              coverageAmount: new BN(1000 * 10 ** 6),
+              premiumAmount: new BN(20 * 10 ** 6),
+              endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+              payoutFormula: { linearScale: {} },
+              decayConstant: 0,
+              minOracleConsensus: 1,
+              minConfidence: null,
+              monitoringFrequency: null,
+              altitudeRange: null,
+              maxSinglePayout: null,
+              deductibleAmount: new BN(50 * 10 ** 6),
+              beneficiary: null,
+              paymentMode: { splToken: {} },
+              coverageDenomination: null,
+              fxOracle: null,
+              requireCrossValidation: false,
+              premiumSchedule: [],
+            })
+            .accounts({
+              owner: policyOwner.publicKey,
+              policy: deductiblePolicyPda,
+              policyRegistry: policyRegistryPda,
+              climateReadings: deductibleReadingsPda,
+              regionExposure: regionExposurePda(bounds),
+              fxRate: null,
+              globalState: globalStatePda,
+              systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(oracleSourceAccounts())
+            .signers([policyOwner])
+            .rpc();
+        } finally {
+          await setTemperatureWaitingPeriod(3 * 24 * 3600);
+        }
+
+        await program.methods
+          .depositPremium(deductiblePolicyId, new BN(20 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should settle a claim within the deductible at a zero payout", async () => {
+        // 40.5 is 125 bps past the threshold: a 12.5 claim, under the 50 deductible
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 40.5,
+              timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+            policy: deductiblePolicyPda,
+            climateReadings: deductibleReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        await program.methods
+          .evaluateClimateTrigger(deductiblePolicyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            climateReadings: deductibleReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        let policy = await program.account.climatePolicy.fetch(deductiblePolicyPda);
+        expect(policy.status).to.deep.equal({ triggered: {} });
+        expect(policy.pendingClaim.toNumber()).to.equal(12.5 * 10 ** 6);
+
+        await program.methods
+          .fileClaim(deductiblePolicyId, policy.pendingClaim)
+          .accounts({
+            claimant: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            policyToken: null,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const balanceBefore = (await getAccount(provider.connection, userTokenAccount)).amount;
+        await program.methods
+          .executeClimatePayout(deductiblePolicyId, policy.pendingClaim)
+          .accounts({
+            executor: policyOwner.publicKey,
+            policy: deductiblePolicyPda,
+            regionExposure: regionExposurePda(bounds),
+            claimLog: claimLogPda,
+            keeper: null,
+            policyToken: null,
+            policyholderTokenAccount: userTokenAccount,
+            fxRate: null,
+            beneficiary: null,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const balanceAfter = (await getAccount(provider.connection, userTokenAccount)).amount;
+        expect(balanceAfter).to.equal(balanceBefore);
+
+        // The trigger is settled, and the deducted part stays in coverage
+        policy = await program.account.climatePolicy.fetch(deductiblePolicyPda);
+        expect(policy.status).to.deep.equal({ monitoring: {} });
+        expect(policy.pendingClaim.toNumber()).to.equal(0);
+        expect(policy.filedClaim.toNumber()).to.equal(0);
+        expect(policy.paidOutSoFar.toNumber()).to.equal(0);
+
+        const claimLog = await program.account.claimLog.fetch(claimLogPda);
+        expect(claimLog.totalClaims).to.equal(1);
+        expect(claimLog.records[0].amount.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("Payout ceiling", () => {
+      const ceilingPolicyId = new BN(22);
+      const bounds = { latitude: 40.4168, longitude: -3.7038, radius: 50.0 };
+      const payoutCeiling = 30 * 10 ** 6;
+      let ceilingPolicyPda: PublicKey;
+      let ceilingReadingsPda: PublicKey;
+
+      const setPayoutCeiling = (globalMaxSinglePolicyPayout: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      const setTemperatureWaitingPeriod = (seconds: number) =>
+        program.methods
+          .setWaitingPeriod({ extremeTemperature: {} }, new BN(seconds))
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      before(async () => {
+        [ceilingPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            ceilingPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [ceilingReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), ceilingPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        // Created without a waiting period so the trigger can be paid at once
+        await setTemperatureWaitingPeriod(0);
+        try {
+          await program.methods
+            .createClimatePolicy({
+              policyId: ceilingPolicyId,
+              policyType: { extremeTemperature: {} },
+              geographicBounds: bounds,
+              triggerConditions: {
+                rainfallThreshold: null,
+                temperatureThreshold: 40.0,
+                coldTemperatureThreshold: null,
+                windSpeedThreshold: null,
+                waterLevelThreshold: null,
+                fireProximityThreshold: null,
+                measurementPeriod: 1,
+                minimumDuration: 0,
+                compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+                triggerLogic: { anyOf: {} },
+                resetBandBps: 0,
+                activeWindow: null,
+              },
+              oracleSources: [oracleProvider.publicKey],
+              coverageAmount: new BN(1000 * 10 ** 6),
+              premiumAmount: new BN(20 * 10 ** 6),
+              endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+              payoutFormula: { linearScale: {} },
+              decayConstant: 0,
+              minOracleConsensus: 1,
+              minConfidence: null,
+              monitoringFrequency: null,
+              altitudeRange: null,
+              maxSinglePayout: null,
+              deductibleAmount: new BN(0),
+              beneficiary: null,
+              paymentMode: { splToken: {} },
+              coverageDenomination: null,
+              fxOracle: null,
+              requireCrossValidation: false,
+              premiumSchedule: [],
+            })
+            .accounts({
+              owner: policyOwner.publicKey,
+              policy: ceilingPolicyPda,
+              policyRegistry: policyRegistryPda,
+              climateReadings: ceilingReadingsPda,
+              regionExposure: regionExposurePda(bounds),
+              fxRate: null,
+              globalState: globalStatePda,
+              systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(oracleSourceAccounts())
+            .signers([policyOwner])
+            .rpc();
+        } finally {
+          await setTemperatureWaitingPeriod(3 * 24 * 3600);
+        }
+
+        await program.methods
+          .depositPremium(ceilingPolicyId, new BN(20 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: ceilingPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should reserve and transfer no more than the lifetime payout ceiling", async () => {
+        await setPayoutCeiling(new BN(payoutCeiling));
+        try {
+          // 42.0 is 500 bps past the threshold: a 50 claim, over the 30 ceiling
+          await program.methods
+            .submitClimateData([
+              withVerificationHash({
+                dataType: { temperature: {} },
+                unit: { celsius: {} },
+                location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+                value: 42.0,
+                timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+                confidenceLevel: 95,
+                sourceId: oracleProvider.publicKey,
+              }),
+            ])
+            .accounts({
+              oracleProvider: oracleProvider.publicKey,
+              oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+              policy: ceilingPolicyPda,
+              climateReadings: ceilingReadingsPda,
+              globalState: globalStatePda,
+            })
+            .signers([oracleProvider])
+            .rpc();
+
+          await program.methods
+            .evaluateClimateTrigger(ceilingPolicyId)
+            .accounts({
+              evaluator: policyOwner.publicKey,
+              policy: ceilingPolicyPda,
+              climateReadings: ceilingReadingsPda,
+              globalState: globalStatePda,
+            })
+            .signers([policyOwner])
+            .rpc();
+
+          let policy = await program.account.climatePolicy.fetch(ceilingPolicyPda);
+          expect(policy.status).to.deep.equal({ triggered: {} });
+          expect(policy.pendingClaim.toNumber()).to.equal(payoutCeiling);
+
+          await program.methods
+            .fileClaim(ceilingPolicyId, policy.pendingClaim)
+            .accounts({
+              claimant: policyOwner.publicKey,
+              policy: ceilingPolicyPda,
+              policyToken: null,
+              globalState: globalStatePda,
+            })
+            .signers([policyOwner])
+            .rpc();
+
+          const [claimLogPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("claims"), ceilingPolicyPda.toBuffer()],
+            program.programId
+          );
+          const balanceBefore = (await getAccount(provider.connection, userTokenAccount)).amount;
+          await program.methods
+            .executeClimatePayout(ceilingPolicyId, policy.pendingClaim)
+            .accounts({
+              executor: policyOwner.publicKey,
+              policy: ceilingPolicyPda,
+              regionExposure: regionExposurePda(bounds),
+              claimLog: claimLogPda,
+              keeper: null,
+              policyToken: null,
+              policyholderTokenAccount: userTokenAccount,
+              fxRate: null,
+              beneficiary: null,
+              riskPoolTokenAccount: riskPoolTokenAccount,
+              riskPoolPda: riskPoolPda,
+              epochStats: epochStatsPda(),
+              globalState: globalStatePda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([policyOwner])
+            .rpc();
+
+          const balanceAfter = (await getAccount(provider.connection, userTokenAccount)).amount;
+          expect(Number(balanceAfter - balanceBefore)).to.equal(payoutCeiling);
+
+          // At the ceiling the policy ends, releasing the coverage it can't draw on
+          policy = await program.account.climatePolicy.fetch(ceilingPolicyPda);
+          expect(policy.transferredSoFar.toNumber()).to.equal(payoutCeiling);
+          expect(policy.status).to.deep.equal({ claimed: {} });
+          const region = await program.account.regionExposure.fetch(regionExposurePda(bounds));
+          expect(region.totalCoverage.toNumber()).to.equal(0);
+        } finally {
+          await setPayoutCeiling(new BN("18446744073709551615"));
+        }
+      });
+    });
+
+    describe("Index-based products", () => {
+      const indexPolicyId = new BN(18);
+      const bounds = { latitude: 30.0444, longitude: 31.2357, radius: 50.0 };
+      let indexPolicyPda: PublicKey;
+      let indexReadingsPda: PublicKey;
+
+      before(async () => {
+        [indexPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            indexPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [indexReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), indexPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        // A day-long minimum_duration that index products don't wait for
+        await program.methods
+          .createClimatePolicy({
+            policyId: indexPolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { indexBased: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(20 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: indexPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: indexReadingsPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(indexPolicyId, new BN(20 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: indexPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should reserve a claim graduated by the measured index", async () => {
+        // 44 is 1000 bps past the threshold: a tenth of the coverage
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 44.0,
+              timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+            policy: indexPolicyPda,
+            climateReadings: indexReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        await program.methods
+          .evaluateClimateTrigger(indexPolicyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: indexPolicyPda,
+            climateReadings: indexReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const policy = await program.account.climatePolicy.fetch(indexPolicyPda);
+        expect(policy.status).to.deep.equal({ triggered: {} });
+        expect(policy.indexValue).to.equal(1000);
+        expect(policy.pendingClaim.toNumber()).to.equal(100 * 10 ** 6);
+      });
+    });
+
+    describe("Historical window evaluation", () => {
+      const historicalPolicyId = new BN(19);
+      const bounds = { latitude: 24.8607, longitude: 67.0011, radius: 50.0 };
+      let historicalPolicyPda: PublicKey;
+      let historicalReadingsPda: PublicKey;
+      let startTimestamp: number;
+
+      const evaluateWindow = (signer: Keypair, windowStart: number, windowEnd: number) =>
+        program.methods
+          .evaluateHistoricalWindow(historicalPolicyId, new BN(windowStart), new BN(windowEnd))
+          .accounts({
+            authority: signer.publicKey,
+            policy: historicalPolicyPda,
+            climateReadings: historicalReadingsPda,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([signer])
+          .rpc();
+
+      before(async () => {
+        [historicalPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            historicalPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [historicalReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), historicalPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: historicalPolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 0,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(20 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: historicalPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: historicalReadingsPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(historicalPolicyId, new BN(20 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: historicalPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        const policy = await program.account.climatePolicy.fetch(historicalPolicyPda);
+        startTimestamp = policy.startTimestamp.toNumber();
+      });
+
+      it("Should only let the authority re-evaluate a past window of the term", async () => {
+        try {
+          await evaluateWindow(policyOwner, startTimestamp, startTimestamp + 1);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("Unauthorized");
+        }
+
+        // Starts before the policy's term
+        try {
+          await evaluateWindow(authority, startTimestamp - 60, startTimestamp);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("InvalidEvaluationWindow");
+        }
+      });
+
+      it("Should fire a trigger missed within a past window", async () => {
+        // A breach at the start of the term that receded before anyone evaluated
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 44.0,
+              timestamp: new BN(startTimestamp),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: oracleDataPdaFor(oracleProvider.publicKey),
+            policy: historicalPolicyPda,
+            climateReadings: historicalReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        // The window has to have closed
+        await new Promise((resolve) => setTimeout(resolve, 2000));
+        const before = await program.account.climatePolicy.fetch(historicalPolicyPda);
+        await evaluateWindow(authority, startTimestamp, startTimestamp + 1);
+
+        const policy = await program.account.climatePolicy.fetch(historicalPolicyPda);
+        expect(policy.status).to.deep.equal({ triggered: {} });
+        expect(policy.pendingClaim.toNumber()).to.equal(100 * 10 ** 6);
+
+        // The window's severity is recorded for the claim; breach tracking is left alone
+        expect(policy.lastDataUpdate.eq(before.lastDataUpdate)).to.be.true;
+        expect(policy.breachStartedAt).to.deep.equal(before.breachStartedAt);
+        expect(policy.indexValue).to.be.greaterThan(0);
+        expect(policy.lastSeverity.temperatureBps).to.equal(policy.indexValue);
+      });
+    });
+
+    describe("Hysteresis band", () => {
+      const bandPolicyId = new BN(7);
+      let bandPolicyPda: PublicKey;
+      let bandReadingsPda: PublicKey;
+
+      const temperatureReading = (value: number, secondsAgo: number) => withVerificationHash({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value,
+        timestamp: new BN(Math.floor(Date.now() / 1000) - secondsAgo),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+      });
+
+      before(async () => {
+        [bandPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            bandPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [bandReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), bandPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: bandPolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: {
+              latitude: 40.7128,
+              longitude: -74.006,
+              radius: 50.0,
+            },
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 2500, // Resets below 30.0
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: bandPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: bandReadingsPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(bandPolicyId, new BN(10 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: bandPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should hold a breach through dips that stay inside the band", async () => {
+        // Dips to the reset level itself and into the band, then back below
+        // the trigger level: none of these recede past 30.0
+        await program.methods
+          .submitClimateData([
+            temperatureReading(45.0, 1800),
+            temperatureReading(30.0, 1500),
+            temperatureReading(35.0, 1200),
+            temperatureReading(38.0, 60),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: PublicKey.findProgramAddressSync(
+              [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            policy: bandPolicyPda,
+            climateReadings: bandReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        await program.methods
+          .evaluateClimateTrigger(bandPolicyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: bandPolicyPda,
+            climateReadings: bandReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        // The run still dates from the first breach, although the latest
+        // reading sits below the 40.0 trigger level
+        const policy = await program.account.climatePolicy.fetch(bandPolicyPda);
+        expect(policy.status).to.deep.equal({ monitoring: {} });
+        expect(policy.breachStartedAt.toNumber()).to.be.at.most(
+          Math.floor(Date.now() / 1000) - 1700
+        );
+      });
+    });
+
+    describe("Linear payout scale", () => {
+      const linearPolicyId = new BN(8);
+      let linearPolicyPda: PublicKey;
+      let linearReadingsPda: PublicKey;
+
+      const temperatureReading = (value: number, secondsAgo: number) => withVerificationHash({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value,
+        timestamp: new BN(Math.floor(Date.now() / 1000) - secondsAgo),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+      });
+
+      // Submit readings, then preview the payout the window would produce
+      const previewAfterReadings = async (...readings: [number, number][]) => {
+        await program.methods
+          .submitClimateData(readings.map(([value, secondsAgo]) => temperatureReading(value, secondsAgo)))
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: PublicKey.findProgramAddressSync(
+              [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            policy: linearPolicyPda,
+            climateReadings: linearReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        return program.methods
+          .dryRunPayout(linearPolicyId)
+          .accounts({
+            policy: linearPolicyPda,
+            climateReadings: linearReadingsPda,
+            globalState: globalStatePda,
+          })
+          .view();
+      };
+
+      before(async () => {
+        [linearPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            linearPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [linearReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), linearPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: linearPolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: {
+              latitude: 40.7128,
+              longitude: -74.006,
+              radius: 50.0,
+            },
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: linearPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: linearReadingsPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .depositPremium(linearPolicyId, new BN(10 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: linearPolicyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should pay nothing at the threshold itself", async () => {
+        const payout = await previewAfterReadings([40.0, 120]);
+        expect(payout.toNumber()).to.equal(0);
+      });
+
+      it("Should ramp continuously just past the threshold", async () => {
+        // The window median of 40.0 and 40.5 is 40.25, 62 bps past a 40.0 threshold
+        const payout = await previewAfterReadings([40.5, 90]);
+        expect(payout.toNumber()).to.equal(6_200_000);
+      });
+
+      it("Should scale in proportion to the breach magnitude", async () => {
+        // The median moves to 40.5, 125 bps past: twice the previous breach
+        const payout = await previewAfterReadings([44.0, 60]);
+        expect(payout.toNumber()).to.equal(12.5 * 10 ** 6);
+      });
+
+      it("Should not let a glitched reading drive the payout", async () => {
+        // A 500.0 sensor glitch alone would pay full coverage; the window
+        // median stays at 41.0, 250 bps past the threshold
+        const payout = await previewAfterReadings([41.0, 50], [41.0, 45], [41.0, 40], [500.0, 30]);
+        expect(payout.toNumber()).to.equal(25 * 10 ** 6);
+      });
+
+      it("Should scale a maximal coverage amount without overflowing", async () => {
+        const hugePolicyId = new BN(9);
+        const coverageAmount = new BN("18446744073709551615"); // u64::MAX
+        const bounds = { latitude: -33.8688, longitude: 151.2093, radius: 50.0 };
+        const [hugePolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            hugePolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        const [hugeReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), hugePolicyPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: hugePolicyId,
+            policyType: { extremeTemperature: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount,
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: hugePolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: hugeReadingsPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        await program.methods
+          .submitClimateData([
+            withVerificationHash({
+              dataType: { temperature: {} },
+              unit: { celsius: {} },
+              location: { latitude: bounds.latitude, longitude: bounds.longitude, altitude: null },
+              value: 44.0,
+              timestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+              confidenceLevel: 95,
+              sourceId: oracleProvider.publicKey,
+            }),
+          ])
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: PublicKey.findProgramAddressSync(
+              [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            policy: hugePolicyPda,
+            climateReadings: hugeReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        // 44.0 is 1000 bps past the 40.0 threshold
+        const payout = await program.methods
+          .dryRunPayout(hugePolicyId)
+          .accounts({
+            policy: hugePolicyPda,
+            climateReadings: hugeReadingsPda,
+            globalState: globalStatePda,
+          })
+          .view();
+        expect(payout.eq(coverageAmount.muln(1000).divn(10_000))).to.be.true;
+      });
+    });
+
+    describe("Rolling rainfall totals", () => {
+      const rollingPolicyId = new BN(11);
+      let rollingPolicyPda: PublicKey;
+      let rollingReadingsPda: PublicKey;
+
+      const rainfallReading = (value: number, secondsAgo: number) => withVerificationHash({
+        dataType: { rainfall: {} },
+        unit: { millimeters: {} },
+        location: {
+          latitude: 40.7128,
+          longitude: -74.006,
+          altitude: null,
+        },
+        value,
+        timestamp: new BN(Math.floor(Date.now() / 1000) - secondsAgo),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+      });
+
+      // Submit readings, then preview the payout the window would produce
+      const previewAfterReadings = async (...readings: [number, number][]) => {
+        await program.methods
+          .submitClimateData(readings.map(([value, secondsAgo]) => rainfallReading(value, secondsAgo)))
+          .accounts({
+            oracleProvider: oracleProvider.publicKey,
+            oracleData: PublicKey.findProgramAddressSync(
+              [Buffer.from("oracle"), oracleProvider.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            policy: rollingPolicyPda,
+            climateReadings: rollingReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([oracleProvider])
+          .rpc();
+
+        return program.methods
+          .dryRunPayout(rollingPolicyId)
+          .accounts({
+            policy: rollingPolicyPda,
+            climateReadings: rollingReadingsPda,
+            globalState: globalStatePda,
+          })
+          .view();
+      };
+
+      before(async () => {
+        [rollingPolicyPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("policy"),
+            policyOwner.publicKey.toBuffer(),
+            rollingPolicyId.toArray("le", 8),
+          ],
+          program.programId
+        );
+        [rollingReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), rollingPolicyPda.toBuffer()],
+          program.programId
+        );
+
+        await program.methods
+          .createClimatePolicy({
+            policyId: rollingPolicyId,
+            policyType: { droughtProtection: {} },
+            geographicBounds: {
+              latitude: 40.7128,
+              longitude: -74.006,
+              radius: 50.0,
+            },
+            triggerConditions: {
+              rainfallThreshold: 40.0, // mm over the trailing day
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(100 * 10 ** 6),
+            premiumAmount: new BN(1 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: rollingPolicyPda,
+            policyRegistry: policyRegistryPda,
+            climateReadings: rollingReadingsPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+      });
+
+      it("Should judge drought on the window's rainfall total", async () => {
+        // 20 mm in total is 5000 bps short of 40 mm; a median of 10 mm would be 7500
+        const payout = await previewAfterReadings([10.0, 3000], [10.0, 2000]);
+        expect(payout.toNumber()).to.equal(50 * 10 ** 6);
+      });
+
+      it("Should leave out readings dated after the evaluation time", async () => {
+        // The window ends at the evaluation's clock, so a reading stamped
+        // 50 minutes ahead doesn't count yet
+        const payout = await previewAfterReadings([30.0, -3000]);
+        expect(payout.toNumber()).to.equal(50 * 10 ** 6);
+      });
+
+      it("Should recover as rain accumulates within the window", async () => {
+        // 35 mm is 1250 bps short; 45 mm clears the threshold
+        expect((await previewAfterReadings([15.0, 1000])).toNumber()).to.equal(12.5 * 10 ** 6);
+        expect((await previewAfterReadings([10.0, 500])).toNumber()).to.equal(0);
+      });
+
+      it("Should compute the same risk score the policy stores", async () => {
+        const riskScore = await program.methods
+          .computeRiskScore(rollingPolicyId)
+          .accounts({
+            policy: rollingPolicyPda,
+            climateReadings: rollingReadingsPda,
+            globalState: globalStatePda,
+          })
+          .view();
+
+        const policy = await program.account.climatePolicy.fetch(rollingPolicyPda);
+        expect(riskScore).to.equal(policy.riskScore);
+      });
+    });
+
+    it("Should backtest trigger conditions over historical readings", async () => {
+      // Two hours-long heat spells in 2020; each persists for an hour at
+      // 10% past the threshold, and the second must persist afresh after
+      // the first one's payout
+      const start = 1_600_000_000;
+      const history = [0, 1800, 3600, 5400, 7200, 9000].map((offset) => ({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: { latitude: 40.7128, longitude: -74.006, altitude: null },
+        value: 44.0,
+        timestamp: new BN(start + offset),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+        verificationHash: Buffer.alloc(0),
+      }));
+
+      const simulation = program.methods
+        .simulateTriggers(
+          {
+            policyType: { extremeTemperature: {} },
+            geographicBounds: { latitude: 40.7128, longitude: -74.006, radius: 50.0 },
+            triggerConditions: {
+              rainfallThreshold: null,
+              temperatureThreshold: 40.0,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 1,
+              minimumDuration: 1,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            coverageAmount: new BN(1000 * 10 ** 6),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            monitoringFrequency: 600,
+          },
+          history
+        )
+        .accounts({ globalState: globalStatePda });
+
+      const { events } = await simulation.simulate();
+      const summary = events.find((e) => e.name === "triggersSimulated").data;
+      expect(summary.readings).to.equal(6);
+      expect(summary.evaluations).to.equal(6);
+      expect(summary.triggers).to.equal(2);
+      expect(summary.peakSeverityBps).to.equal(1000);
+
+      const totalPayout = await simulation.view();
+      expect(totalPayout.toNumber()).to.equal(200 * 10 ** 6);
+      expect(summary.totalPayout.eq(totalPayout)).to.be.true;
+    });
+
+    it("Should round small payouts to the nearest unit", async () => {
+      // One hour-long spell 125 bps past the threshold
+      const start = 1_600_000_000;
+      const history = [0, 1800, 3600].map((offset) => ({
+        dataType: { temperature: {} },
+        unit: { celsius: {} },
+        location: { latitude: 40.7128, longitude: -74.006, altitude: null },
+        value: 40.5,
+        timestamp: new BN(start + offset),
+        confidenceLevel: 95,
+        sourceId: oracleProvider.publicKey,
+        verificationHash: Buffer.alloc(0),
+      }));
+
+      const backtest = (coverageAmount: BN) =>
+        program.methods
+          .simulateTriggers(
+            {
+              policyType: { extremeTemperature: {} },
+              geographicBounds: { latitude: 40.7128, longitude: -74.006, radius: 50.0 },
+              triggerConditions: {
+                rainfallThreshold: null,
+                temperatureThreshold: 40.0,
+                coldTemperatureThreshold: null,
+                windSpeedThreshold: null,
+                waterLevelThreshold: null,
+                fireProximityThreshold: null,
+                measurementPeriod: 1,
+                minimumDuration: 1,
+                compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+                triggerLogic: { anyOf: {} },
+                resetBandBps: 0,
+                activeWindow: null,
+              },
+              coverageAmount,
+              payoutFormula: { linearScale: {} },
+              decayConstant: 0,
+              minOracleConsensus: 1,
+              monitoringFrequency: 600,
+            },
+            history
+          )
+          .accounts({ globalState: globalStatePda })
+          .view();
+
+      // 1.25% of each coverage: 0.375 rounds down, 0.625 and a 1.5 tie round up
+      expect((await backtest(new BN(30))).toNumber()).to.equal(0);
+      expect((await backtest(new BN(50))).toNumber()).to.equal(1);
+      expect((await backtest(new BN(120))).toNumber()).to.equal(2);
+      expect((await backtest(new BN(1000 * 10 ** 6 + 20))).toNumber()).to.equal(12.5 * 10 ** 6);
+    });
+
+    it("Should evaluate climate triggers", async () => {
+      try {
+        const tx = await program.methods
+          .evaluateClimateTrigger(policyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        console.log("Evaluate trigger transaction signature:", tx);
+
+        // Check if policy status changed
+        const policy = await program.account.climatePolicy.fetch(policyPda);
+        console.log("Policy status after evaluation:", policy.status);
+      } catch (error) {
+        console.log(
+          "Expected error due to simplified implementation:",
+          error.message
+        );
+      }
+    });
+
+    it("Should execute payout when triggered", async () => {
+      // First, manually set policy status to triggered for testing
+      // In a real scenario, this would happen through trigger evaluation
+
+      const payoutAmount = new BN(5000 * 10 ** 6); // 5,000 USDC (50% of coverage)
+
+      try {
+        const tx = await program.methods
+          .executeClimatePayout(payoutAmount)
+          .accounts({
+            executor: policyOwner.publicKey,
+            policy: policyPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            claimLog: PublicKey.findProgramAddressSync(
+              [Buffer.from("claims"), policyPda.toBuffer()],
+              program.programId
+            )[0],
+            keeper: null,
+            policyholderTokenAccount: userTokenAccount,
+            fxRate: null,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        console.log("Execute payout transaction signature:", tx);
+
+        // Verify payout was executed
+        const userAccount = await getAccount(
+          provider.connection,
+          userTokenAccount
+        );
+        const riskPoolAccount = await getAccount(
+          provider.connection,
+          riskPoolTokenAccount
+        );
+
+        console.log("User balance after payout:", Number(userAccount.amount));
+        console.log(
+          "Risk pool balance after payout:",
+          Number(riskPoolAccount.amount)
+        );
+
+        // Verify policy status
+        const policy = await program.account.climatePolicy.fetch(policyPda);
+        console.log("Policy status after payout:", policy.status);
+
+        // Verify global state
+        const globalState = await program.account.globalState.fetch(
+          globalStatePda
+        );
+        console.log("Total payouts:", globalState.totalPayouts.toNumber());
+      } catch (error) {
+        console.log(
+          "Expected error due to policy not being triggered:",
+          error.message
+        );
+      }
+    });
+  });
+
+  describe("Admin Functions", () => {
+    it("Should add and remove a payout keeper", async () => {
+      const keeper = Keypair.generate();
+      const [keeperPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("keeper"), keeper.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .addKeeper(keeper.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          keeperAccount: keeperPda,
+          globalState: globalStatePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const keeperAccount = await program.account.keeper.fetch(keeperPda);
+      expect(keeperAccount.keeper.equals(keeper.publicKey)).to.be.true;
+
+      await program.methods
+        .removeKeeper()
+        .accounts({
+          authority: authority.publicKey,
+          keeperAccount: keeperPda,
+          globalState: globalStatePda,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect(await program.account.keeper.fetchNullable(keeperPda)).to.be.null;
+    });
+
+    it("Should pause and unpause the program", async () => {
+      // Pause program
+      const pauseTx = await program.methods
+        .pauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      console.log("Pause program transaction signature:", pauseTx);
+
+      // Verify program is paused
+      let globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.isPaused).to.be.true;
+
+      // Unpause program
+      const unpauseTx = await program.methods
+        .unpauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      console.log("Unpause program transaction signature:", unpauseTx);
+
+      // Verify program is unpaused
+      globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.isPaused).to.be.false;
+    });
+
+    it("Should guard emergency withdrawals from the risk pool", async () => {
+      const treasuryTokenAccount = await createAccount(
+        provider.connection,
+        authority,
+        mint,
+        authority.publicKey
+      );
+      const emergencyWithdraw = () =>
+        program.methods
+          .emergencyWithdraw({ splToken: {} }, new BN(1))
+          .accounts({
+            authority: authority.publicKey,
+            treasuryTokenAccount: treasuryTokenAccount,
+            riskPoolTokenAccount: riskPoolTokenAccount,
+            riskPoolPda: riskPoolPda,
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await emergencyWithdraw();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ProgramNotPaused");
+      }
+
+      await program.methods
+        .pauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Active policies still have coverage committed against the pool
+      try {
+        await emergencyWithdraw();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ObligationsOutstanding");
+      }
+
+      await program.methods
+        .unpauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Should fail admin actions with unauthorized user", async () => {
+      try {
+        await program.methods
+          .pauseProgram()
+          .accounts({
+            authority: policyOwner.publicKey, // Wrong authority
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should refuse an authority program the call isn't routed through", async () => {
+      try {
+        await program.methods
+          .setAuthorityProgram(Keypair.generate().publicKey)
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AuthorityCallerMismatch");
+      }
+
+      const state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.authorityProgram).to.be.null;
+    });
+
+    it("Should update only the provided config fields", async () => {
+      const before = await program.account.globalState.fetch(globalStatePda);
+      const maxDuration = new BN(200 * 24 * 3600);
+
+      await program.methods
+        .setConfig({
+          protocolFeeBps: null,
+          solvencyRatioBps: null,
+          reputationFloor: null,
+          maxPolicyDurationSeconds: maxDuration,
+          minOracleBond: null,
+          maxRegionExposure: null,
+          policyCreationIntervalSeconds: null,
+          noDataRefundBps: null,
+          reputationSmoothingBps: null,
+          minPremiumRateBps: null,
+          triggerGracePeriodSeconds: null,
+          maxStackedCoverage: null,
+          keeperReward: null,
+          maxLossRatioBps: null,
+          globalMaxSinglePolicyPayout: null,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      const after = await program.account.globalState.fetch(globalStatePda);
+      expect(after.maxPolicyDurationSeconds.eq(maxDuration)).to.be.true;
+      expect(after.protocolFeeBps).to.equal(before.protocolFeeBps);
+      expect(after.solvencyRatioBps).to.equal(before.solvencyRatioBps);
+      expect(after.reputationFloor).to.equal(before.reputationFloor);
+      expect(after.reputationSmoothingBps).to.equal(2000);
+      expect(after.triggerGracePeriodSeconds.toNumber()).to.equal(3 * 24 * 3600);
+      expect(after.keeperReward.toNumber()).to.equal(0);
+      expect(after.maxLossRatioBps).to.equal(0);
+      expect(after.payoutsHalted).to.be.false;
+      expect(after.globalMaxSinglePolicyPayout.eq(new BN("18446744073709551615"))).to.be.true;
+
+      try {
+        await program.methods
+          .setConfig({
+            protocolFeeBps: 10001,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidProtocolFee");
+      }
+    });
+
+    it("Should reject a zero trigger grace period", async () => {
+      try {
+        await program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: new BN(0),
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidTriggerGracePeriod");
+      }
+    });
+
+    it("Should configure the keeper reward for cranked triggers and payouts", async () => {
+      const setKeeperReward = (keeperReward: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      await setKeeperReward(new BN(10 ** 6));
+      let state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.keeperReward.toNumber()).to.equal(10 ** 6);
+
+      // Zero turns the reward off again
+      await setKeeperReward(new BN(0));
+      state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.keeperReward.toNumber()).to.equal(0);
+
+      try {
+        await setKeeperReward(new BN(100_000_001));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidKeeperReward");
+      }
+    });
+
+    it("Should configure the loss-ratio circuit breaker", async () => {
+      const setMaxLossRatio = (maxLossRatioBps: number) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      // Halt payouts once a day's payouts exceed 150% of its premiums
+      await setMaxLossRatio(15000);
+      let state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.maxLossRatioBps).to.equal(15000);
+      expect(state.payoutsHalted).to.be.false;
+
+      // Only a tripped breaker can be cleared
+      try {
+        await program.methods
+          .resumePayouts()
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PayoutsNotHalted");
+      }
+
+      try {
+        await program.methods
+          .resumePayouts()
+          .accounts({
+            authority: policyOwner.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([policyOwner])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+
+      try {
+        await setMaxLossRatio(1_000_001);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidLossRatio");
+      }
+
+      await setMaxLossRatio(0);
+      state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.maxLossRatioBps).to.equal(0);
+    });
+
+    it("Should reject an oracle bond minimum above the allowed maximum", async () => {
+      try {
+        await program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: new BN("1000000000001"),
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOracleBond");
+      }
+    });
+
+    it("Should configure a ceiling on any one policy's lifetime payouts", async () => {
+      const setPayoutCeiling = (globalMaxSinglePolicyPayout: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      await setPayoutCeiling(new BN(5000 * 10 ** 6));
+      let state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.globalMaxSinglePolicyPayout.toNumber()).to.equal(5000 * 10 ** 6);
+
+      try {
+        await setPayoutCeiling(new BN(0));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidCoverageAmount");
+      }
+
+      // The maximum lifts the ceiling again
+      await setPayoutCeiling(new BN("18446744073709551615"));
+      state = await program.account.globalState.fetch(globalStatePda);
+      expect(state.globalMaxSinglePolicyPayout.eq(new BN("18446744073709551615"))).to.be.true;
+    });
+
+    it("Should freeze a single policy's evaluation and payouts", async () => {
+      const policyId = new BN(1);
+      const setFrozen = (frozen: boolean) =>
+        (frozen ? program.methods.freezePolicy(policyId) : program.methods.unfreezePolicy(policyId))
+          .accounts({
+            authority: authority.publicKey,
+            policy: policyPda,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      await setFrozen(true);
+      expect((await program.account.climatePolicy.fetch(policyPda)).isFrozen).to.be.true;
+
+      try {
+        await program.methods
+          .evaluateClimateTrigger(policyId)
+          .accounts({
+            evaluator: policyOwner.publicKey,
+            policy: policyPda,
+            climateReadings: climateReadingsPda,
+            globalState: globalStatePda,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyFrozen");
+      }
+
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.isPaused).to.be.false;
+
+      await setFrozen(false);
+      expect((await program.account.climatePolicy.fetch(policyPda)).isFrozen).to.be.false;
+    });
+
+    it("Should stamp schema versions and refuse to re-migrate current accounts", async () => {
+      const globalState = await program.account.globalState.fetch(globalStatePda);
+      const policy = await program.account.climatePolicy.fetch(policyPda);
+      expect(globalState.version).to.equal(5);
+      expect(policy.version).to.equal(10);
+
+      try {
+        await program.methods
+          .migrateGlobalState()
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+
+      const bounds = policy.geographicBounds;
+      try {
+        await program.methods
+          .migratePolicy(
+            new BN(1),
+            Array.from(Buffer.from(regionGeohash(bounds.latitude, bounds.longitude)))
+          )
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            policy: policyPda,
+            owner: policyOwner.publicKey,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(bounds),
+            instructions: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    // Loaded by Anchor.toml from tests/fixtures/baseline-policy.json, with
+    // stale bytes after its serialized fields: policy 1 of its owner, an
+    // active flood policy over New York whose term has ended
+    const baselinePolicy = new PublicKey("HC5MShup8in53gcP4GZATDtYiWntH81de8QGUmfUF5rx");
+    const baselineOwner = new PublicKey("BXZkYHG2ofpg8Z48tpaFhKySmzakXbcMdw7E93xdZYot");
+    const baselineBounds = { latitude: 40.7128, longitude: -74.006 };
+    const [baselineRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry"), baselineOwner.toBuffer()],
+      program.programId
+    );
+
+    it("Should migrate a policy written in the unversioned baseline layout", async () => {
+      const before = await provider.connection.getAccountInfo(baselinePolicy);
+      expect(before.data.length).to.equal(330);
+      const stateBefore = await program.account.globalState.fetch(globalStatePda);
+      const regionBefore = await program.account.regionExposure.fetch(regionExposurePda(baselineBounds));
+
+      await program.methods
+        .migratePolicy(new BN(1), Array.from(Buffer.from("dr5")))
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          policy: baselinePolicy,
+          owner: baselineOwner,
+          policyRegistry: baselineRegistry,
+          regionExposure: regionExposurePda(baselineBounds),
+          instructions: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      // The baseline kept no counters, so its coverage joins them on migration
+      const stateAfter = await program.account.globalState.fetch(globalStatePda);
+      const regionAfter = await program.account.regionExposure.fetch(regionExposurePda(baselineBounds));
+      expect(stateAfter.totalCoverageCommitted.sub(stateBefore.totalCoverageCommitted).toNumber()).to.equal(1_000_000);
+      expect(regionAfter.totalCoverage.sub(regionBefore.totalCoverage).toNumber()).to.equal(1_000_000);
+      expect(regionAfter.policyCount).to.equal(regionBefore.policyCount + 1);
+      const registry = await program.account.policyRegistry.fetch(baselineRegistry);
+      expect(registry.policyIds.map((id) => id.toNumber())).to.deep.equal([1]);
+
+      const after = await provider.connection.getAccountInfo(baselinePolicy);
+      expect(after.data.length).to.be.greaterThan(330);
+
+      const policy = await program.account.climatePolicy.fetch(baselinePolicy);
+      expect(policy.version).to.equal(10);
+      expect(policy.owner.toString()).to.equal(baselineOwner.toString());
+      expect(policy.creator.toString()).to.equal(baselineOwner.toString());
+      expect(policy.beneficiary.toString()).to.equal(baselineOwner.toString());
+      expect(policy.status).to.deep.equal({ active: {} });
+      expect(policy.policyType).to.deep.equal({ floodInsurance: {} });
+      expect(policy.geographicBounds.radius).to.equal(50.0);
+      expect(policy.triggerThresholds.rainfallThreshold).to.equal(100.0);
+      expect(policy.triggerThresholds.coldTemperatureThreshold).to.be.null;
+      expect(policy.triggerThresholds.measurementPeriod).to.equal(7);
+      expect(policy.oracleSources).to.be.empty;
+      expect(policy.coverageAmount.toNumber()).to.equal(1_000_000);
+      expect(policy.maxSinglePayout.toNumber()).to.equal(1_000_000);
+      expect(policy.premiumPaidSoFar.toNumber()).to.equal(50_000);
+      expect(policy.commitmentFxRate.toNumber()).to.equal(1_000_000);
+      expect(policy.transferredSoFar.toNumber()).to.equal(0);
+      expect(policy.fullTermPremium.toNumber()).to.equal(50_000);
+      expect(policy.premiumSchedule).to.be.empty;
+      expect(policy.requireCrossValidation).to.be.false;
+      expect(policy.isFrozen).to.be.false;
+      expect(policy.deductibleAmount.toNumber()).to.equal(0);
+    });
+
+    it("Should expire a migrated baseline policy, releasing its coverage", async () => {
+      const stateBefore = await program.account.globalState.fetch(globalStatePda);
+      const regionBefore = await program.account.regionExposure.fetch(regionExposurePda(baselineBounds));
+
+      await program.methods
+        .expirePolicy(new BN(1))
+        .accounts({
+          policy: baselinePolicy,
+          policyRegistry: baselineRegistry,
+          regionExposure: regionExposurePda(baselineBounds),
+          globalState: globalStatePda,
+        })
+        .rpc();
+
+      const policy = await program.account.climatePolicy.fetch(baselinePolicy);
+      expect(policy.status).to.deep.equal({ expired: {} });
+      const stateAfter = await program.account.globalState.fetch(globalStatePda);
+      const regionAfter = await program.account.regionExposure.fetch(regionExposurePda(baselineBounds));
+      expect(stateBefore.totalCoverageCommitted.sub(stateAfter.totalCoverageCommitted).toNumber()).to.equal(1_000_000);
+      expect(regionBefore.totalCoverage.sub(regionAfter.totalCoverage).toNumber()).to.equal(1_000_000);
+      expect(regionAfter.policyCount).to.equal(regionBefore.policyCount - 1);
+      const registry = await program.account.policyRegistry.fetch(baselineRegistry);
+      expect(registry.policyIds).to.be.empty;
+    });
+
+    it("Should cap coverage written in one region", async () => {
+      const bounds = { latitude: 40.7128, longitude: -74.006, radius: 50.0 };
+      const region = await program.account.regionExposure.fetch(regionExposurePda(bounds));
+      expect(Buffer.from(region.geohash).toString()).to.equal("dr5");
+      expect(region.totalCoverage.gtn(0)).to.be.true;
+
+      const setRegionCap = (maxRegionExposure: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
+
+      await setRegionCap(region.totalCoverage);
 
-    before(async () => {
-      [policyPda] = PublicKey.findProgramAddressSync(
+      const [cappedPolicyPda] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("policy"),
           policyOwner.publicKey.toBuffer(),
-          policyId.toArray("le", 8),
+          new BN(2).toArray("le", 8),
         ],
         program.programId
       );
-    });
+      const [cappedReadingsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("readings"), cappedPolicyPda.toBuffer()],
+        program.programId
+      );
 
-    it("Should evaluate climate triggers", async () => {
       try {
-        const tx = await program.methods
-          .evaluateClimateTrigger()
+        await program.methods
+          .createClimatePolicy({
+            policyId: new BN(2),
+            policyType: { floodInsurance: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: 100.0,
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 7,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 100, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
           .accounts({
-            evaluator: policyOwner.publicKey,
-            policy: policyPda,
-            oracleDataAccounts: [], // Would include oracle data accounts
+            owner: policyOwner.publicKey,
+            policy: cappedPolicyPda,
+            climateReadings: cappedReadingsPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
             globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(oracleSourceAccounts())
           .signers([policyOwner])
           .rpc();
 
-        console.log("Evaluate trigger transaction signature:", tx);
-
-        // Check if policy status changed
-        const policy = await program.account.climatePolicy.fetch(policyPda);
-        console.log("Policy status after evaluation:", policy.status);
+        expect.fail("Should have thrown an error");
       } catch (error) {
-        console.log(
-          "Expected error due to simplified implementation:",
-          error.message
-        );
+        expect(error.message).to.include("RegionExposureExceeded");
+      } finally {
+        await setRegionCap(new BN("18446744073709551615"));
       }
     });
 
-    it("Should execute payout when triggered", async () => {
-      // First, manually set policy status to triggered for testing
-      // In a real scenario, this would happen through trigger evaluation
+    it("Should cap an owner's overlapping coverage of one peril", async () => {
+      const setStackedCap = (maxStackedCoverage: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
 
-      const payoutAmount = new BN(5000 * 10 ** 6); // 5,000 USDC (50% of coverage)
+      const stackedPolicyPda = (policyId: BN) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("policy"), policyOwner.publicKey.toBuffer(), policyId.toArray("le", 8)],
+          program.programId
+        )[0];
+      const registryPolicyAccounts = async () => {
+        const registry = await program.account.policyRegistry.fetch(policyRegistryPda);
+        return registry.policyIds.map((registeredId) => ({
+          pubkey: stackedPolicyPda(registeredId),
+          isWritable: false,
+          isSigner: false,
+        }));
+      };
+
+      const createDroughtPolicy = async (
+        id: number,
+        latitude: number,
+        withRegistryPolicies = true
+      ) => {
+        const policyId = new BN(id);
+        const bounds = { latitude, longitude: -77.0428, radius: 30.0 };
+        const [stackedReadingsPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("readings"), stackedPolicyPda(policyId).toBuffer()],
+          program.programId
+        );
+        const registryPolicies = await registryPolicyAccounts();
+
+        await program.methods
+          .createClimatePolicy({
+            policyId,
+            policyType: { droughtProtection: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: 10.0,
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 7,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: stackedPolicyPda(policyId),
+            climateReadings: stackedReadingsPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            ...oracleSourceAccounts(),
+            ...(withRegistryPolicies ? registryPolicies : []),
+          ])
+          .signers([policyOwner])
+          .rpc();
+      };
 
+      await setStackedCap(new BN(1500 * 10 ** 6));
       try {
-        const tx = await program.methods
-          .executeClimatePayout(payoutAmount)
+        // The owner's existing policies must all be presented
+        try {
+          await createDroughtPolicy(15, -12.0464, false);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("InvalidRegistryPolicies");
+        }
+
+        await createDroughtPolicy(15, -12.0464);
+
+        // 20 km north of the first policy, so the 30 km areas overlap
+        try {
+          await createDroughtPolicy(16, -11.8664);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("StackedCoverageExceeded");
+        }
+
+        // Raising the first policy's coverage past the cap fails the same way
+        await program.methods
+          .depositPremium(new BN(15), new BN(10 * 10 ** 6))
           .accounts({
-            executor: policyOwner.publicKey,
-            policy: policyPda,
-            policyholderTokenAccount: userTokenAccount,
+            owner: policyOwner.publicKey,
+            policy: stackedPolicyPda(new BN(15)),
+            policyToken: null,
+            userTokenAccount: userTokenAccount,
             riskPoolTokenAccount: riskPoolTokenAccount,
             riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
             globalState: globalStatePda,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([policyOwner])
           .rpc();
+        try {
+          await program.methods
+            .increaseCoverage(new BN(15), new BN(600 * 10 ** 6))
+            .accounts({
+              owner: policyOwner.publicKey,
+              policy: stackedPolicyPda(new BN(15)),
+              policyToken: null,
+              policyRegistry: policyRegistryPda,
+              regionExposure: regionExposurePda({ latitude: -12.0464, longitude: -77.0428 }),
+              userTokenAccount: userTokenAccount,
+              riskPoolTokenAccount: riskPoolTokenAccount,
+              riskPoolPda: riskPoolPda,
+              feeVault: feeVaultPda,
+              epochStats: epochStatsPda(),
+              globalState: globalStatePda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(await registryPolicyAccounts())
+            .signers([policyOwner])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("StackedCoverageExceeded");
+        }
+      } finally {
+        await setStackedCap(new BN("18446744073709551615"));
+      }
+    });
 
-        console.log("Execute payout transaction signature:", tx);
-
-        // Verify payout was executed
-        const userAccount = await getAccount(
-          provider.connection,
-          userTokenAccount
-        );
-        const riskPoolAccount = await getAccount(
-          provider.connection,
-          riskPoolTokenAccount
-        );
+    it("Should reject premiums below the minimum rate", async () => {
+      const setMinPremiumRate = (minPremiumRateBps: number) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: null,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
 
-        console.log("User balance after payout:", Number(userAccount.amount));
-        console.log(
-          "Risk pool balance after payout:",
-          Number(riskPoolAccount.amount)
-        );
+      const bounds = { latitude: 40.7128, longitude: -74.006, radius: 50.0 };
+      const [cheapPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
 
-        // Verify policy status
-        const policy = await program.account.climatePolicy.fetch(policyPda);
-        console.log("Policy status after payout:", policy.status);
+      await setMinPremiumRate(200);
+      try {
+        // 10 against 1,000 is a 1% rate, under the 2% floor
+        await program.methods
+          .createClimatePolicy({
+            policyId: new BN(2),
+            policyType: { floodInsurance: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: 100.0,
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 7,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 100, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: cheapPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
 
-        // Verify global state
-        const globalState = await program.account.globalState.fetch(
-          globalStatePda
-        );
-        console.log("Total payouts:", globalState.totalPayouts.toNumber());
+        expect.fail("Should have thrown an error");
       } catch (error) {
-        console.log(
-          "Expected error due to policy not being triggered:",
-          error.message
-        );
+        expect(error.message).to.include("PremiumRatioTooLow");
+      } finally {
+        await setMinPremiumRate(0);
       }
     });
-  });
 
-  describe("Admin Functions", () => {
-    it("Should pause and unpause the program", async () => {
-      // Pause program
-      const pauseTx = await program.methods
-        .pauseProgram()
-        .accounts({
-          authority: authority.publicKey,
-          globalState: globalStatePda,
-        })
-        .signers([authority])
-        .rpc();
+    it("Should space out one owner's policy creations", async () => {
+      const setCreationInterval = (interval: BN) =>
+        program.methods
+          .setConfig({
+            protocolFeeBps: null,
+            solvencyRatioBps: null,
+            reputationFloor: null,
+            maxPolicyDurationSeconds: null,
+            minOracleBond: null,
+            maxRegionExposure: null,
+            policyCreationIntervalSeconds: interval,
+            noDataRefundBps: null,
+            reputationSmoothingBps: null,
+            minPremiumRateBps: null,
+            triggerGracePeriodSeconds: null,
+            maxStackedCoverage: null,
+            keeperReward: null,
+            maxLossRatioBps: null,
+            globalMaxSinglePolicyPayout: null,
+          })
+          .accounts({
+            authority: authority.publicKey,
+            globalState: globalStatePda,
+            instructions: null,
+          })
+          .signers([authority])
+          .rpc();
 
-      console.log("Pause program transaction signature:", pauseTx);
+      await setCreationInterval(new BN(24 * 3600));
 
-      // Verify program is paused
-      let globalState = await program.account.globalState.fetch(globalStatePda);
-      expect(globalState.isPaused).to.be.true;
+      const registry = await program.account.policyRegistry.fetch(policyRegistryPda);
+      expect(registry.lastPolicyCreated.toNumber()).to.be.greaterThan(0);
 
-      // Unpause program
-      const unpauseTx = await program.methods
-        .unpauseProgram()
+      const bounds = { latitude: 40.7128, longitude: -74.006, radius: 50.0 };
+      const [spamPolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          new BN(2).toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createClimatePolicy({
+            policyId: new BN(2),
+            policyType: { floodInsurance: {} },
+            geographicBounds: bounds,
+            triggerConditions: {
+              rainfallThreshold: 100.0,
+              temperatureThreshold: null,
+              coldTemperatureThreshold: null,
+              windSpeedThreshold: null,
+              waterLevelThreshold: null,
+              fireProximityThreshold: null,
+              measurementPeriod: 7,
+              minimumDuration: 24,
+              compositeWeights: { rainfall: 100, temperature: 0, windSpeed: 0 },
+              triggerLogic: { anyOf: {} },
+              resetBandBps: 0,
+              activeWindow: null,
+            },
+            oracleSources: [oracleProvider.publicKey],
+            coverageAmount: new BN(1000 * 10 ** 6),
+            premiumAmount: new BN(10 * 10 ** 6),
+            endTimestamp: new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 3600),
+            payoutFormula: { linearScale: {} },
+            decayConstant: 0,
+            minOracleConsensus: 1,
+            minConfidence: null,
+            monitoringFrequency: null,
+            altitudeRange: null,
+            maxSinglePayout: null,
+            deductibleAmount: new BN(0),
+            beneficiary: null,
+            paymentMode: { splToken: {} },
+            coverageDenomination: null,
+            fxOracle: null,
+            requireCrossValidation: false,
+            premiumSchedule: [],
+          })
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: spamPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(bounds),
+            fxRate: null,
+            globalState: globalStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(oracleSourceAccounts())
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PolicyCreationTooFrequent");
+      } finally {
+        await setCreationInterval(new BN(0));
+      }
+    });
+
+    it("Should transfer authority in two steps", async () => {
+      const newAuthority = Keypair.generate();
+
+      await program.methods
+        .proposeAuthority(newAuthority.publicKey)
         .accounts({
           authority: authority.publicKey,
           globalState: globalStatePda,
+          instructions: null,
         })
         .signers([authority])
         .rpc();
 
-      console.log("Unpause program transaction signature:", unpauseTx);
-
-      // Verify program is unpaused
-      globalState = await program.account.globalState.fetch(globalStatePda);
-      expect(globalState.isPaused).to.be.false;
-    });
+      let globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.pendingAuthority.equals(newAuthority.publicKey)).to.be
+        .true;
+      expect(globalState.authority.equals(authority.publicKey)).to.be.true;
 
-    it("Should fail admin actions with unauthorized user", async () => {
+      // Only the pending key can accept
       try {
         await program.methods
-          .pauseProgram()
+          .acceptAuthority()
           .accounts({
-            authority: policyOwner.publicKey, // Wrong authority
+            pendingAuthority: policyOwner.publicKey,
             globalState: globalStatePda,
           })
           .signers([policyOwner])
@@ -539,6 +6704,38 @@ describe("AMOCA Climate Insurance", () => {
       } catch (error) {
         expect(error.message).to.include("Unauthorized");
       }
+
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          pendingAuthority: newAuthority.publicKey,
+          globalState: globalStatePda,
+        })
+        .signers([newAuthority])
+        .rpc();
+
+      globalState = await program.account.globalState.fetch(globalStatePda);
+      expect(globalState.authority.equals(newAuthority.publicKey)).to.be.true;
+      expect(globalState.pendingAuthority).to.be.null;
+
+      // Hand authority back for the remaining tests
+      await program.methods
+        .proposeAuthority(authority.publicKey)
+        .accounts({
+          authority: newAuthority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([newAuthority])
+        .rpc();
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          pendingAuthority: authority.publicKey,
+          globalState: globalStatePda,
+        })
+        .signers([authority])
+        .rpc();
     });
   });
 
@@ -550,6 +6747,7 @@ describe("AMOCA Climate Insurance", () => {
         .accounts({
           authority: authority.publicKey,
           globalState: globalStatePda,
+          instructions: null,
         })
         .signers([authority])
         .rpc();
@@ -566,16 +6764,35 @@ describe("AMOCA Climate Insurance", () => {
         triggerConditions: {
           rainfallThreshold: 100.0,
           temperatureThreshold: null,
+          coldTemperatureThreshold: null,
           windSpeedThreshold: null,
           waterLevelThreshold: null,
           fireProximityThreshold: null,
           measurementPeriod: 7,
           minimumDuration: 24,
+          compositeWeights: { rainfall: 0, temperature: 0, windSpeed: 0 },
+          triggerLogic: { anyOf: {} },
+          resetBandBps: 0,
+          activeWindow: null,
         },
         oracleSources: [oracleProvider.publicKey],
         coverageAmount: new BN(10000 * 10 ** 6),
         premiumAmount: new BN(100 * 10 ** 6),
-        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 365 * 24 * 3600),
+        endTimestamp: new BN(Math.floor(Date.now() / 1000) + 180 * 24 * 3600),
+        payoutFormula: { linearScale: {} },
+        decayConstant: 0,
+        minOracleConsensus: 1,
+        minConfidence: null,
+        monitoringFrequency: null,
+        altitudeRange: null,
+        maxSinglePayout: null,
+        deductibleAmount: new BN(0),
+        beneficiary: null,
+        paymentMode: { splToken: {} },
+        coverageDenomination: null,
+        fxOracle: null,
+        requireCrossValidation: false,
+        premiumSchedule: [],
       };
 
       const [pausedPolicyPda] = PublicKey.findProgramAddressSync(
@@ -593,9 +6810,13 @@ describe("AMOCA Climate Insurance", () => {
           .accounts({
             owner: policyOwner.publicKey,
             policy: pausedPolicyPda,
+            policyRegistry: policyRegistryPda,
+            regionExposure: regionExposurePda(policyParams.geographicBounds),
+            fxRate: null,
             globalState: globalStatePda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(oracleSourceAccounts(policyParams.oracleSources))
           .signers([policyOwner])
           .rpc();
 
@@ -610,6 +6831,95 @@ describe("AMOCA Climate Insurance", () => {
         .accounts({
           authority: authority.publicKey,
           globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Should reject a token account posing as the risk pool", async () => {
+      const policyId = new BN(1);
+      const [policyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          policyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .depositPremium(policyId, new BN(100 * 10 ** 6))
+          .accounts({
+            owner: policyOwner.publicKey,
+            policy: policyPda,
+            userTokenAccount: userTokenAccount,
+            riskPoolTokenAccount: userTokenAccount, // Not the pool vault
+            riskPoolPda: riskPoolPda,
+            feeVault: feeVaultPda,
+            epochStats: epochStatsPda(),
+            globalState: globalStatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([policyOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRiskPoolAccount");
+      }
+    });
+
+    it("Should only force settle policies while paused", async () => {
+      const settlePolicyId = new BN(4);
+      const [settlePolicyPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          policyOwner.publicKey.toBuffer(),
+          settlePolicyId.toArray("le", 8),
+        ],
+        program.programId
+      );
+      const forceSettle = () =>
+        program.methods
+          .forceSettlePolicy(settlePolicyId, { expired: {} })
+          .accounts({
+            authority: authority.publicKey,
+            policy: settlePolicyPda,
+            regionExposure: regionExposurePda({ latitude: 40.7128, longitude: -74.006 }),
+            globalState: globalStatePda,
+          })
+          .signers([authority])
+          .rpc();
+
+      try {
+        await forceSettle();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ProgramNotPaused");
+      }
+
+      await program.methods
+        .pauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
+        })
+        .signers([authority])
+        .rpc();
+
+      await forceSettle();
+      const policy = await program.account.climatePolicy.fetch(settlePolicyPda);
+      expect(policy.status).to.deep.equal({ expired: {} });
+
+      await program.methods
+        .unpauseProgram()
+        .accounts({
+          authority: authority.publicKey,
+          globalState: globalStatePda,
+          instructions: null,
         })
         .signers([authority])
         .rpc();